@@ -0,0 +1,19 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuilderError {
+    pub builder: &'static str,
+    pub field: &'static str,
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: missing required field `{}`",
+            self.builder, self.field
+        )
+    }
+}
+
+impl std::error::Error for BuilderError {}
@@ -0,0 +1,153 @@
+use crate::Render;
+use crate::render::report;
+use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::OnceLock;
+
+/// Renders `T` once and replays the stored output on every later render.
+pub struct Cached<T> {
+    value: T,
+    rendered: OnceLock<String>,
+}
+
+impl<T> Cached<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            rendered: OnceLock::new(),
+        }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    pub fn invalidate(&mut self) {
+        self.rendered = OnceLock::new();
+    }
+}
+
+impl<T: Render> Render for Cached<T> {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if let Some(output) = self.rendered.get() {
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_cache_lookup::<T>(true);
+            return f.write_str(output);
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_cache_lookup::<T>(false);
+        let output = self.value.try_render_to_string().map_err(report)?;
+        f.write_str(self.rendered.get_or_init(|| output))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.rendered
+            .get()
+            .map_or_else(|| self.value.size_hint(), String::len)
+    }
+}
+
+impl<T: Render> Display for Cached<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+/// A strong ETag for a rendered body, quoted as it appears in the header.
+pub fn etag(body: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Whether an `If-None-Match` header value matches `etag` (weak comparison).
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(feature = "axum")]
+pub use conditional::{Conditional, IfNoneMatch};
+
+#[cfg(feature = "axum")]
+mod conditional {
+    use super::{etag, etag_matches};
+    use crate::Render;
+    use axum::{
+        extract::FromRequestParts,
+        http::{StatusCode, header, request::Parts},
+        response::{IntoResponse, Response},
+    };
+    use std::convert::Infallible;
+
+    /// The request's `If-None-Match` header, if it sent one.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct IfNoneMatch(pub Option<String>);
+
+    impl IfNoneMatch {
+        pub fn respond<T>(self, body: T) -> Conditional<T> {
+            Conditional {
+                if_none_match: self.0,
+                body,
+            }
+        }
+    }
+
+    impl<S: Send + Sync> FromRequestParts<S> for IfNoneMatch {
+        type Rejection = Infallible;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            Ok(IfNoneMatch(
+                parts
+                    .headers
+                    .get(header::IF_NONE_MATCH)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+            ))
+        }
+    }
+
+    /// Renders `T` with an `ETag`, answering `304 Not Modified` when the
+    /// request's `If-None-Match` already names it.
+    pub struct Conditional<T> {
+        if_none_match: Option<String>,
+        body: T,
+    }
+
+    impl<T: Render> IntoResponse for Conditional<T> {
+        fn into_response(self) -> Response {
+            let body = match self.body.try_render_to_string() {
+                Ok(body) => body,
+                Err(error) => return error.into_response(),
+            };
+            let etag = etag(&body);
+            let not_modified = self
+                .if_none_match
+                .as_deref()
+                .is_some_and(|if_none_match| etag_matches(if_none_match, &etag));
+            #[cfg(feature = "metrics")]
+            if self.if_none_match.is_some() {
+                crate::metrics::record_conditional::<T>(not_modified);
+            }
+            if not_modified {
+                return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+            }
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_response::<T>(body.len());
+
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+                    (header::ETAG, etag),
+                ],
+                body,
+            )
+                .into_response()
+        }
+    }
+}
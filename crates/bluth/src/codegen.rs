@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::SignalEnum;
+use crate::url::UrlPath;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    TypeScript,
+    JavaScript,
+}
+
+enum Export {
+    Signals {
+        name: &'static str,
+        selectors: &'static [(&'static str, &'static str)],
+    },
+    Url {
+        name: &'static str,
+        pattern: &'static str,
+    },
+}
+
+pub struct ConstModule {
+    language: Language,
+    exports: Vec<Export>,
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn js_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+impl ConstModule {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            exports: Vec::new(),
+        }
+    }
+
+    pub fn typescript() -> Self {
+        Self::new(Language::TypeScript)
+    }
+
+    pub fn javascript() -> Self {
+        Self::new(Language::JavaScript)
+    }
+
+    pub fn signals<E: SignalEnum>(mut self) -> Self {
+        self.exports.push(Export::Signals {
+            name: short_type_name::<E>(),
+            selectors: E::selectors(),
+        });
+        self
+    }
+
+    pub fn url<U: UrlPath>(mut self) -> Self {
+        self.exports.push(Export::Url {
+            name: short_type_name::<U>(),
+            pattern: U::pattern(),
+        });
+        self
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let contents = self.to_string();
+        if std::fs::read_to_string(path.as_ref()).is_ok_and(|existing| existing == contents) {
+            return Ok(());
+        }
+        std::fs::write(path, contents)
+    }
+}
+
+impl fmt::Display for ConstModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "// Generated by bluth. Do not edit.")?;
+        for export in &self.exports {
+            writeln!(f)?;
+            match export {
+                Export::Signals { name, selectors } => {
+                    writeln!(f, "export const {} = {{", name)?;
+                    for (selector, signal) in selectors.iter() {
+                        writeln!(f, "  {}: {},", selector, js_string(signal))?;
+                    }
+                    match self.language {
+                        Language::TypeScript => writeln!(f, "}} as const;")?,
+                        Language::JavaScript => writeln!(f, "}};")?,
+                    }
+                }
+                Export::Url { name, pattern } => {
+                    writeln!(f, "export const {} = {};", name, js_string(pattern))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
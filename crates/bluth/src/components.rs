@@ -0,0 +1,602 @@
+#[cfg(feature = "axum")]
+use axum::http::{StatusCode, header};
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::Duration;
+use strum::AsRefStr;
+
+use crate::datastar::{DatastarIntersect, PatchElements, PatchMode};
+use crate::html::{RootAttrs, WithAttrs, escape_attr};
+use crate::id::UniqueId;
+use crate::signal::SignalSelector;
+use crate::url::{UrlNode, UrlPath, ancestry};
+use crate::{Element, Render};
+
+#[derive(Element)]
+#[element("nav")]
+#[attr(aria_label = "breadcrumb")]
+pub struct Breadcrumbs {
+    #[element("ol")]
+    pub items: Vec<Crumb>,
+}
+
+#[derive(Element)]
+#[element("li")]
+pub enum Crumb {
+    Link(CrumbLink),
+    Current(CurrentCrumb),
+}
+
+#[derive(Element)]
+#[element("a")]
+pub struct CrumbLink {
+    #[attr]
+    pub href: String,
+
+    #[element]
+    pub label: String,
+}
+
+#[derive(Element)]
+#[element("span")]
+#[attr(aria_current = "page")]
+pub struct CurrentCrumb {
+    #[element]
+    pub label: String,
+}
+
+impl Breadcrumbs {
+    pub fn for_url(url: &dyn UrlNode) -> Self {
+        let chain = ancestry(url);
+        let last = chain.len() - 1;
+        let items = chain
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, href))| {
+                if i == last {
+                    Crumb::Current(CurrentCrumb {
+                        label: label.to_string(),
+                    })
+                } else {
+                    Crumb::Link(CrumbLink {
+                        href,
+                        label: label.to_string(),
+                    })
+                }
+            })
+            .collect();
+        Self { items }
+    }
+
+    pub fn current_label(mut self, label: impl Into<String>) -> Self {
+        if let Some(Crumb::Current(current)) = self.items.last_mut() {
+            current.label = label.into();
+        }
+        self
+    }
+}
+
+pub const TOAST_REGION_ID: &str = "toasts";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn class(&self) -> String {
+        format!("toast toast-{}", self.as_ref())
+    }
+
+    fn role(&self) -> &'static str {
+        match self {
+            ToastLevel::Error => "alert",
+            _ => "status",
+        }
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr(
+    class = self.level.class(),
+    role = self.level.role(),
+    "data-init" = "setTimeout(() => el.remove(), {dismiss_ms})"
+)]
+pub struct Toast {
+    level: ToastLevel,
+    dismiss_ms: u128,
+
+    #[element]
+    pub message: String,
+}
+
+impl Toast {
+    pub fn new(level: ToastLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            dismiss_ms: 5000,
+            message: message.into(),
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Success, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    pub fn dismiss_after(mut self, duration: Duration) -> Self {
+        self.dismiss_ms = duration.as_millis();
+        self
+    }
+
+    pub fn level(&self) -> ToastLevel {
+        self.level
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr(id = "toasts", "aria-live" = "polite")]
+pub struct ToastRegion {}
+
+#[derive(Default)]
+pub struct ToastQueue {
+    toasts: Vec<Toast>,
+}
+
+impl ToastQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, toast: Toast) -> &mut Self {
+        self.toasts.push(toast);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    pub fn into_patch(self) -> PatchElements<Toast> {
+        PatchElements::new(self.toasts)
+            .selector(format!("#{}", TOAST_REGION_ID))
+            .mode(PatchMode::Append)
+    }
+}
+
+#[derive(Element)]
+#[element("dialog")]
+#[attr(
+    id = "{id}",
+    "aria-modal" = "true",
+    "data-effect" = Self::sync_open(),
+    "data-on:close" = Self::close(),
+    "data-on:keydown__window" = Self::close_on_escape(),
+    "data-on:click" = Self::close_on_backdrop()
+)]
+pub struct Modal<S, T>
+where
+    S: SignalSelector<Value = bool>,
+    T: Display,
+{
+    id: UniqueId,
+    selector: PhantomData<S>,
+
+    #[element]
+    pub content: T,
+}
+
+impl<S, T> Modal<S, T>
+where
+    S: SignalSelector<Value = bool>,
+    T: Display,
+{
+    pub fn new(content: T) -> Self {
+        Self {
+            id: UniqueId::with_prefix("modal"),
+            selector: PhantomData,
+            content,
+        }
+    }
+
+    pub fn id(&self) -> &UniqueId {
+        &self.id
+    }
+
+    pub fn open() -> String {
+        format!("{} = true", S::JS_REF)
+    }
+
+    pub fn close() -> String {
+        format!("{} = false", S::JS_REF)
+    }
+
+    fn sync_open() -> String {
+        format!(
+            "{} ? (el.open || el.showModal()) : (el.open && el.close())",
+            S::JS_REF
+        )
+    }
+
+    fn close_on_escape() -> String {
+        format!("evt.key === 'Escape' && ({})", Self::close())
+    }
+
+    fn close_on_backdrop() -> String {
+        format!("evt.target === el && ({})", Self::close())
+    }
+}
+
+pub const LOAD_MORE_ID: &str = "load-more";
+
+pub struct LoadMore {
+    id: String,
+    path: String,
+    once: bool,
+    threshold: Option<u8>,
+}
+
+impl LoadMore {
+    pub fn new(next: &dyn UrlPath) -> Self {
+        Self {
+            id: LOAD_MORE_ID.to_string(),
+            path: next.path(),
+            once: true,
+            threshold: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn repeat(mut self) -> Self {
+        self.once = false;
+        self
+    }
+
+    pub fn threshold(mut self, percent: u8) -> Self {
+        self.threshold = Some(percent);
+        self
+    }
+
+    fn intersect(&self) -> DatastarIntersect {
+        let mut intersect = DatastarIntersect::new();
+        if self.once {
+            intersect = intersect.once();
+        }
+        if let Some(threshold) = self.threshold {
+            intersect = intersect.threshold(threshold);
+        }
+        intersect
+    }
+}
+
+impl Display for LoadMore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<div id=\"{}\" {}=\"@get('{}')\"></div>",
+            escape_attr(&self.id),
+            self.intersect(),
+            escape_attr(&self.path)
+        )
+    }
+}
+
+pub struct LoadMorePage<T> {
+    items: PatchElements<T>,
+    sentinel_id: String,
+    next: Option<LoadMore>,
+}
+
+impl<T: Display> LoadMorePage<T> {
+    pub fn new(list_selector: impl Into<String>, items: Vec<T>) -> Self {
+        Self {
+            items: PatchElements::new(items)
+                .selector(list_selector)
+                .mode(PatchMode::Append),
+            sentinel_id: LOAD_MORE_ID.to_string(),
+            next: None,
+        }
+    }
+
+    pub fn sentinel_id(mut self, id: impl Into<String>) -> Self {
+        self.sentinel_id = id.into();
+        self
+    }
+
+    pub fn next(mut self, next: LoadMore) -> Self {
+        self.next = Some(next.id(self.sentinel_id.clone()));
+        self
+    }
+}
+
+impl<T: Display> Render for LoadMorePage<T> {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.items.render(f)?;
+
+        let selector = format!("#{}", self.sentinel_id);
+        match &self.next {
+            Some(next) => write!(
+                f,
+                "{}",
+                PatchElements::new(vec![next])
+                    .selector(selector)
+                    .mode(PatchMode::Outer)
+            ),
+            None => write!(
+                f,
+                "{}",
+                PatchElements::<&str>::new(vec![])
+                    .selector(selector)
+                    .mode(PatchMode::Remove)
+            ),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.items.size_hint()
+    }
+}
+
+impl<T: Display> Display for LoadMorePage<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<T: Display> IntoResponse for LoadMorePage<T> {
+    fn into_response(self) -> Response {
+        let body = match self.try_render_to_string() {
+            Ok(body) => body,
+            Err(error) => return error.into_response(),
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_response::<T>(body.len());
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/event-stream")],
+            body,
+        )
+            .into_response()
+    }
+}
+
+#[derive(Element)]
+#[element("option")]
+#[attr(value = "{value}", selected if self.selected)]
+pub struct SelectOption {
+    value: String,
+    selected: bool,
+
+    #[element]
+    label: String,
+}
+
+impl SelectOption {
+    pub fn new<T: Display + PartialEq>(value: T, current: &T, label: impl Into<String>) -> Self {
+        Self {
+            selected: &value == current,
+            value: value.to_string(),
+            label: label.into(),
+        }
+    }
+}
+
+#[derive(Element)]
+#[element("input")]
+#[attr(
+    "type" = "radio",
+    name = self.name,
+    value = "{value}",
+    "data-bind" = self.name,
+    checked if self.checked
+)]
+pub struct Radio {
+    name: &'static str,
+    value: String,
+    checked: bool,
+}
+
+impl Radio {
+    pub fn new<S>(_signal: S, value: S::Value, current: &S::Value) -> Self
+    where
+        S: SignalSelector,
+        S::Value: Display + PartialEq,
+    {
+        Self {
+            name: S::NAME,
+            checked: &value == current,
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Element)]
+#[element("input")]
+#[attr(
+    "type" = "checkbox",
+    name = self.name,
+    "data-bind" = self.name,
+    checked if self.checked
+)]
+pub struct Checkbox {
+    name: &'static str,
+    checked: bool,
+}
+
+impl Checkbox {
+    pub fn new<S: SignalSelector<Value = bool>>(_signal: S, checked: bool) -> Self {
+        Self {
+            name: S::NAME,
+            checked,
+        }
+    }
+}
+
+#[derive(Element)]
+#[element("select")]
+pub struct Select {
+    #[attr]
+    name: Option<&'static str>,
+
+    #[attr(name = "data-bind")]
+    bind: Option<&'static str>,
+
+    #[element]
+    pub options: Vec<SelectOption>,
+}
+
+impl Select {
+    pub fn new(options: Vec<SelectOption>) -> Self {
+        Self {
+            name: None,
+            bind: None,
+            options,
+        }
+    }
+
+    #[cfg(feature = "strum")]
+    pub fn from_enum<E>(current: &E) -> Self
+    where
+        E: strum::IntoEnumIterator + AsRef<str> + Display + PartialEq,
+    {
+        Self::new(
+            E::iter()
+                .map(|variant| SelectOption {
+                    selected: &variant == current,
+                    value: variant.as_ref().to_string(),
+                    label: variant.to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn bind<S: SignalSelector>(mut self, _signal: S) -> Self {
+        self.name = Some(S::NAME);
+        self.bind = Some(S::NAME);
+        self
+    }
+}
+
+#[derive(Element)]
+#[element("label")]
+#[attr("for" = "{target}")]
+struct FieldLabel<'a> {
+    target: &'a UniqueId,
+
+    #[element]
+    text: &'a str,
+}
+
+pub struct Labeled<T> {
+    id: UniqueId,
+    label: String,
+    pub input: T,
+}
+
+impl<T: Display> Labeled<T> {
+    pub fn new(label: impl Into<String>, input: T) -> Self {
+        Self {
+            id: UniqueId::with_prefix("field"),
+            label: label.into(),
+            input,
+        }
+    }
+
+    pub fn id(&self) -> &UniqueId {
+        &self.id
+    }
+}
+
+impl<T: Display> Display for Labeled<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = FieldLabel {
+            target: &self.id,
+            text: &self.label,
+        };
+        let input = WithAttrs::new(&self.input).with_attr("id", &self.id);
+        write!(f, "{}{}", label, input)
+    }
+}
+
+#[derive(Element, Default)]
+#[element("div")]
+#[attr(class = "bluth-error-boundary", role = "alert")]
+pub struct ErrorFallback {}
+
+pub struct ErrorBoundary<T, F = ErrorFallback> {
+    child: T,
+    fallback: F,
+}
+
+impl<T: Render> ErrorBoundary<T> {
+    pub fn new(child: T) -> Self {
+        Self {
+            child,
+            fallback: ErrorFallback::default(),
+        }
+    }
+}
+
+impl<T, F> ErrorBoundary<T, F> {
+    pub fn fallback<G: Display>(self, fallback: G) -> ErrorBoundary<T, G> {
+        ErrorBoundary {
+            child: self.child,
+            fallback,
+        }
+    }
+}
+
+impl<T: Render, F: Display> Render for ErrorBoundary<T, F> {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        match self.child.try_render_to_string() {
+            Ok(output) => f.write_str(&output),
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    error = %_error,
+                    component = std::any::type_name::<T>(),
+                    "render failed, substituting fallback"
+                );
+                write!(f, "{}", self.fallback)
+            }
+        }
+    }
+
+    // Either the child or the fallback ends up in the output, and the
+    // fallback is only `Display`, so no non-zero hint is a safe lower bound.
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Render, F: Display> Display for ErrorBoundary<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+impl<T: Render, F: Display> RootAttrs for ErrorBoundary<T, F> {}
@@ -0,0 +1,172 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Clone, Default)]
+pub struct RenderContext {
+    values: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<RenderContext>> = const { RefCell::new(None) };
+}
+
+struct Restore(Option<RenderContext>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+impl RenderContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: 'static>(mut self, value: T) -> Self {
+        self.values.insert(TypeId::of::<T>(), Rc::new(value));
+        self
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    pub fn render<R>(&self, render: impl FnOnce() -> R) -> R {
+        let mut context = current().unwrap_or_default();
+        context.values.extend(
+            self.values
+                .iter()
+                .map(|(id, value)| (*id, Rc::clone(value))),
+        );
+        let _restore = Restore(CURRENT.with(|current| current.borrow_mut().replace(context)));
+        render()
+    }
+}
+
+pub fn current() -> Option<RenderContext> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn with_value<T: 'static, R>(read: impl FnOnce(Option<&T>) -> R) -> R {
+    CURRENT.with(|current| read(current.borrow().as_ref().and_then(RenderContext::get)))
+}
+
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ku", "ps", "sd", "syr", "ug", "ur", "yi",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dir {
+    Ltr,
+    Rtl,
+}
+
+impl AsRef<str> for Dir {
+    fn as_ref(&self) -> &str {
+        match self {
+            Dir::Ltr => "ltr",
+            Dir::Rtl => "rtl",
+        }
+    }
+}
+
+impl std::fmt::Display for Dir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+fn lang_dir(lang: &str) -> Dir {
+    let mut subtags = lang.split(['-', '_']);
+    let language = subtags.next().unwrap_or("").to_ascii_lowercase();
+    let script = subtags.find(|subtag| subtag.len() == 4);
+    match script.map(str::to_ascii_lowercase).as_deref() {
+        Some("arab" | "hebr" | "syrc" | "thaa" | "nkoo" | "adlm" | "rohg") => Dir::Rtl,
+        Some(_) => Dir::Ltr,
+        None if RTL_LANGUAGES.contains(&language.as_str()) => Dir::Rtl,
+        None => Dir::Ltr,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale(String);
+
+impl Locale {
+    pub fn new(lang: impl Into<String>) -> Self {
+        Self(lang.into())
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.0
+    }
+
+    pub fn dir(&self) -> Dir {
+        lang_dir(&self.0)
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        self.dir() == Dir::Rtl
+    }
+}
+
+pub fn locale() -> Option<Locale> {
+    with_value(|locale: Option<&Locale>| locale.cloned())
+}
+
+pub fn is_rtl() -> bool {
+    with_value(|locale: Option<&Locale>| locale.is_some_and(Locale::is_rtl))
+}
+
+pub fn lang_or(lang: &str) -> std::borrow::Cow<'_, str> {
+    match locale() {
+        Some(locale) if lang.is_empty() => std::borrow::Cow::Owned(locale.0),
+        _ => std::borrow::Cow::Borrowed(lang),
+    }
+}
+
+/// The `dir` to pair with [`lang_or`]: follows the language actually
+/// emitted, so an explicit `lang` wins over the context locale.
+pub fn dir_or(lang: &str) -> Option<Dir> {
+    match lang_or(lang) {
+        lang if lang_dir(&lang) == Dir::Rtl => Some(Dir::Rtl),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Locale(&'static str);
+
+    #[test]
+    fn values_are_scoped_to_render() {
+        let context = RenderContext::new().with(Locale("de"));
+        assert_eq!(context.get::<Locale>(), Some(&Locale("de")));
+
+        let inside = context.render(|| with_value(|locale: Option<&Locale>| locale.map(|l| l.0)));
+        assert_eq!(inside, Some("de"));
+        assert!(current().is_none());
+    }
+
+    #[test]
+    fn nested_contexts_override_and_restore() {
+        let outer = RenderContext::new().with(Locale("de")).with(1u8);
+        let inner = RenderContext::new().with(Locale("fr"));
+
+        outer.render(|| {
+            inner.render(|| {
+                assert_eq!(with_value(|l: Option<&Locale>| l.map(|l| l.0)), Some("fr"));
+                assert_eq!(with_value(|n: Option<&u8>| n.copied()), Some(1));
+            });
+            assert_eq!(with_value(|l: Option<&Locale>| l.map(|l| l.0)), Some("de"));
+        });
+    }
+}
@@ -0,0 +1,225 @@
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::sign::{decode, encode, sign, verify};
+use crate::signal::{SignalEnum, SignalMap, SignalSelector, merge_signals};
+
+pub const DEFAULT_NAME: &str = "bluth-signals";
+pub const DEFAULT_MAX_BYTES: usize = 2048;
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+static COOKIE: OnceLock<SignalCookie> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieError {
+    TooLarge { bytes: usize, max_bytes: usize },
+    AlreadyInstalled,
+}
+
+impl Display for CookieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CookieError::TooLarge { bytes, max_bytes } => {
+                write!(
+                    f,
+                    "signal cookie is {} bytes, limit is {}",
+                    bytes, max_bytes
+                )
+            }
+            CookieError::AlreadyInstalled => write!(f, "signal cookie already installed"),
+        }
+    }
+}
+
+impl std::error::Error for CookieError {}
+
+#[derive(Clone)]
+pub struct SignalCookie {
+    secret: Vec<u8>,
+    name: &'static str,
+    max_bytes: usize,
+    max_age: Duration,
+    secure: bool,
+    allowed: Vec<&'static str>,
+}
+
+#[derive(serde::Deserialize)]
+struct Payload {
+    iat: u64,
+    signals: SignalMap,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+impl std::fmt::Debug for SignalCookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalCookie")
+            .field("name", &self.name)
+            .field("max_bytes", &self.max_bytes)
+            .field("max_age", &self.max_age)
+            .field("secure", &self.secure)
+            .field("allowed", &self.allowed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SignalCookie {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            name: DEFAULT_NAME,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_age: DEFAULT_MAX_AGE,
+            secure: true,
+            allowed: Vec::new(),
+        }
+    }
+
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Whether the cookie carries `Secure` (the default). Turn it off for
+    /// local development over plain `http://`.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    pub fn allow<S: SignalSelector>(mut self) -> Self {
+        self.allowed.push(S::NAME);
+        self
+    }
+
+    pub fn install(self) -> Result<(), CookieError> {
+        COOKIE.set(self).map_err(|_| CookieError::AlreadyInstalled)
+    }
+
+    pub fn encode(&self, signals: &serde_json::Value) -> Result<String, CookieError> {
+        self.encode_at(signals, unix_now())
+    }
+
+    pub(crate) fn encode_at(
+        &self,
+        signals: &serde_json::Value,
+        issued_at: u64,
+    ) -> Result<String, CookieError> {
+        let subset: SignalMap = signals
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(name, _)| self.allowed.contains(&name.as_str()))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let payload =
+            encode(serde_json::json!({ "iat": issued_at, "signals": subset }).to_string());
+        let signature = encode(sign(&self.secret, payload.as_bytes()));
+        let value = format!("{}.{}", payload, signature);
+
+        let bytes = self.name.len() + 1 + value.len();
+        if bytes > self.max_bytes {
+            return Err(CookieError::TooLarge {
+                bytes,
+                max_bytes: self.max_bytes,
+            });
+        }
+        Ok(value)
+    }
+
+    pub fn decode(&self, value: &str) -> Option<SignalMap> {
+        let (payload, signature) = value.split_once('.')?;
+        if !verify(&self.secret, payload.as_bytes(), &decode(signature)?) {
+            return None;
+        }
+        let json = decode(payload)?;
+        let payload: Payload = serde_json::from_slice(&json).ok()?;
+        if unix_now().saturating_sub(payload.iat) > self.max_age.as_secs() {
+            return None;
+        }
+        Some(
+            payload
+                .signals
+                .into_iter()
+                .filter(|(name, _)| self.allowed.contains(&name.as_str()))
+                .collect(),
+        )
+    }
+
+    pub fn set_cookie<T: SignalEnum>(&self, signals: &[T]) -> Result<String, CookieError> {
+        let value = self.encode(&merge_signals(signals))?;
+        Ok(format!(
+            "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{}",
+            self.name,
+            value,
+            self.max_age.as_secs(),
+            if self.secure { "; Secure" } else { "" }
+        ))
+    }
+
+    pub fn from_headers(&self, headers: &HeaderMap) -> Option<SignalMap> {
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(name, _)| *name == self.name)
+            .and_then(|(_, value)| self.decode(value))
+    }
+}
+
+pub fn installed() -> Option<&'static SignalCookie> {
+    COOKIE.get()
+}
+
+pub fn signals_from_cookie(headers: &HeaderMap) -> Option<SignalMap> {
+    installed()?.from_headers(headers)
+}
+
+pub struct MirrorSignals<R> {
+    response: R,
+    cookie: Option<String>,
+}
+
+pub fn mirror<R, T: SignalEnum>(response: R, signals: &[T]) -> MirrorSignals<R> {
+    let cookie = installed().and_then(|cookie| {
+        cookie
+            .set_cookie(signals)
+            .inspect_err(|_error| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_error, "signal cookie not mirrored");
+            })
+            .ok()
+    });
+    MirrorSignals { response, cookie }
+}
+
+impl<R: IntoResponse> IntoResponse for MirrorSignals<R> {
+    fn into_response(self) -> Response {
+        let mut response = self.response.into_response();
+        if let Some(cookie) = self.cookie
+            && let Ok(value) = HeaderValue::try_from(cookie)
+        {
+            response.headers_mut().append(header::SET_COOKIE, value);
+        }
+        response
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use crate::diff::parse_open_tag;
+use crate::render::{RenderError, report};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CspViolation {
+    InlineHandler(String),
+    InlineScript,
+}
+
+impl fmt::Display for CspViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CspViolation::InlineHandler(name) => {
+                write!(f, "inline event handler attribute `{}`", name)
+            }
+            CspViolation::InlineScript => write!(f, "inline <script> without a nonce"),
+        }
+    }
+}
+
+fn is_inline_handler(name: &str) -> bool {
+    name.len() > 2
+        && name
+            .get(..2)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("on"))
+        && name[2..].bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn violation(violation: CspViolation) -> Result<bool, fmt::Error> {
+    if cfg!(debug_assertions) {
+        Err(report(RenderError::Csp(violation)))
+    } else {
+        Ok(false)
+    }
+}
+
+pub fn allow_attr(name: &str) -> Result<bool, fmt::Error> {
+    if is_inline_handler(name) {
+        return violation(CspViolation::InlineHandler(name.to_string()));
+    }
+    Ok(true)
+}
+
+pub fn allow_script(html: &str) -> Result<bool, fmt::Error> {
+    let Some(open) = parse_open_tag(html) else {
+        return Ok(true);
+    };
+    let body = &html[open.end..];
+    let body = body
+        .len()
+        .checked_sub("</script>".len())
+        .filter(|&end| {
+            body.get(end..)
+                .is_some_and(|close| close.eq_ignore_ascii_case("</script>"))
+        })
+        .map_or("", |end| &body[..end]);
+    let has_nonce = open.attrs.iter().any(|(name, _)| *name == "nonce");
+
+    if body.trim().is_empty() || has_nonce {
+        Ok(true)
+    } else {
+        violation(CspViolation::InlineScript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_inline_handlers() {
+        assert!(is_inline_handler("onclick"));
+        assert!(is_inline_handler("onMouseOver"));
+        assert!(!is_inline_handler("on"));
+        assert!(!is_inline_handler("data-on:click"));
+        assert!(!is_inline_handler("one-time"));
+        assert_eq!(allow_attr("class"), Ok(true));
+    }
+
+    #[test]
+    fn scripts_need_nonce_or_no_body() {
+        assert_eq!(allow_script(r#"<script src="/app.js"></script>"#), Ok(true));
+        assert_eq!(
+            allow_script(r#"<script nonce="abc">go()</script>"#),
+            Ok(true)
+        );
+        assert!(allow_script("<script>go()</script>").is_err());
+    }
+}
@@ -4,12 +4,14 @@ use axum::http::{StatusCode, header};
 use axum::response::{IntoResponse, Response};
 use std::fmt::Display;
 use std::time::Duration;
-use strum::AsRefStr;
+use strum::{AsRefStr, EnumString, VariantArray};
 
-use crate::signal::SignalEnum;
+use crate::render::{RenderError, report, try_display};
+use crate::signal::{SignalEnum, SignalSelector};
+use crate::{Element, Render, Signal};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
-#[strum(serialize_all = "kebab-case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, VariantArray, strum::Display)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
 pub enum PatchMode {
     Outer,
     Inner,
@@ -21,17 +23,41 @@ pub enum PatchMode {
     Remove,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, VariantArray, strum::Display)]
+#[strum(ascii_case_insensitive)]
 pub enum PatchNamespace {
     Svg,
     MathML,
 }
 
+macro_rules! serde_as_str {
+    ($ty:ty, $expecting:literal) => {
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_ref())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                value.parse().map_err(|_| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(&value), &$expecting)
+                })
+            }
+        }
+    };
+}
+
+serde_as_str!(PatchMode, "a datastar patch mode");
+serde_as_str!(PatchNamespace, "a datastar patch namespace");
+
 pub struct PatchElements<T> {
     pub selector: Option<String>,
     pub mode: Option<PatchMode>,
     pub namespace: Option<PatchNamespace>,
     pub use_view_transition: Option<bool>,
+    pub split_above: Option<usize>,
     pub elements: Vec<T>,
 }
 
@@ -45,6 +71,7 @@ where
             mode: None,
             namespace: None,
             use_view_transition: None,
+            split_above: None,
             elements,
         }
     }
@@ -68,20 +95,46 @@ where
         self.use_view_transition = Some(value);
         self
     }
+
+    /// Sends the elements as several events of roughly `bytes` each. Only
+    /// patches with a selector and an inner, append, prepend, before or after
+    /// mode are split: outer and replace patches (including the default mode)
+    /// remove their target, so they are always sent whole, which
+    /// `strict-patches` reports as `PatchConsistencyError::Unsplittable`.
+    pub fn split_above(mut self, bytes: usize) -> Self {
+        self.split_above = Some(bytes);
+        self
+    }
 }
 
-impl<T> Display for PatchElements<T>
-where
-    T: Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T> PatchElements<T> {
+    fn splittable(&self) -> bool {
+        self.selector.is_some()
+            && matches!(
+                self.mode,
+                Some(
+                    PatchMode::Inner
+                        | PatchMode::Append
+                        | PatchMode::Prepend
+                        | PatchMode::Before
+                        | PatchMode::After
+                )
+            )
+    }
+
+    fn write_event(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        mode: Option<PatchMode>,
+        elements: &[String],
+    ) -> std::fmt::Result {
         writeln!(f, "event: datastar-patch-elements")?;
 
         if let Some(ref selector) = self.selector {
             writeln!(f, "data: selector {}", selector)?;
         }
 
-        if let Some(mode) = self.mode {
+        if let Some(mode) = mode {
             writeln!(f, "data: mode {}", mode.as_ref())?;
         }
 
@@ -93,8 +146,8 @@ where
             writeln!(f, "data: useViewTransition {}", use_view_transition)?;
         }
 
-        for element in &self.elements {
-            for line in element.to_string().lines() {
+        for element in elements {
+            for line in element.lines() {
                 writeln!(f, "data: elements {}", line)?;
             }
         }
@@ -105,23 +158,193 @@ where
     }
 }
 
+#[cfg(feature = "strict-patches")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchConsistencyError {
+    RemoveWithElements,
+    SelectorMismatch { selector: String, id: String },
+    Unsplittable,
+}
+
+#[cfg(feature = "strict-patches")]
+impl Display for PatchConsistencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchConsistencyError::RemoveWithElements => {
+                write!(f, "remove patch carries elements that will be ignored")
+            }
+            PatchConsistencyError::SelectorMismatch { selector, id } => {
+                write!(
+                    f,
+                    "selector {} does not match root element id {}",
+                    selector, id
+                )
+            }
+            PatchConsistencyError::Unsplittable => {
+                write!(
+                    f,
+                    "split_above has no effect on outer, replace or selector-less patches"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "strict-patches")]
+impl<T: Display> PatchElements<T> {
+    pub fn check(&self) -> Result<(), PatchConsistencyError> {
+        let Some(first) = self.elements.first() else {
+            return Ok(());
+        };
+
+        if self.mode == Some(PatchMode::Remove) {
+            return Err(PatchConsistencyError::RemoveWithElements);
+        }
+
+        if self.split_above.is_some() && !self.splittable() {
+            return Err(PatchConsistencyError::Unsplittable);
+        }
+
+        if !matches!(
+            self.mode,
+            None | Some(PatchMode::Outer) | Some(PatchMode::Replace)
+        ) {
+            return Ok(());
+        }
+
+        if let Some(selector) = &self.selector
+            && let Some(selector_id) = selector.strip_prefix('#')
+            && let Ok(first) = try_display(first)
+            && let Some(id) = crate::diff::root_id(&first)
+            && selector_id != id
+        {
+            return Err(PatchConsistencyError::SelectorMismatch {
+                selector: selector.clone(),
+                id,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "strict-patches")]
+fn report_inconsistency(_error: &PatchConsistencyError) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(error = %_error, "inconsistent datastar patch");
+}
+
+fn split_by_size(elements: &[String], limit: usize) -> Vec<&[String]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = 0;
+
+    for (i, element) in elements.iter().enumerate() {
+        if i > start && size + element.len() > limit {
+            chunks.push(&elements[start..i]);
+            start = i;
+            size = 0;
+        }
+        size += element.len();
+    }
+    chunks.push(&elements[start..]);
+
+    chunks
+}
+
+impl<T> Render for PatchElements<T>
+where
+    T: Display,
+{
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        #[cfg(feature = "strict-patches")]
+        if let Err(error) = self.check() {
+            report_inconsistency(&error);
+        }
+
+        let rendered = self
+            .elements
+            .iter()
+            .map(|element| try_display(element))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(report)?;
+
+        // Outer and replace patches remove the target, so later chunks would
+        // have nothing to anchor to; those are always sent whole.
+        let mut chunks = match self.split_above {
+            Some(limit) if self.splittable() => split_by_size(&rendered, limit),
+            _ => vec![rendered.as_slice()],
+        };
+
+        // Each prepend/after lands directly next to the anchor, so sending
+        // the chunks back to front keeps the original element order.
+        if matches!(self.mode, Some(PatchMode::Prepend | PatchMode::After)) {
+            chunks.reverse();
+        }
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let mode = match self.mode {
+                Some(PatchMode::Inner) if i > 0 => Some(PatchMode::Append),
+                mode => mode,
+            };
+            self.write_event(f, mode, chunk)?;
+        }
+
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        let selector = self
+            .selector
+            .as_ref()
+            .map_or(0, |selector| "data: selector \n".len() + selector.len());
+        "event: datastar-patch-elements\n\n".len()
+            + selector
+            + self.elements.len() * "data: elements \n".len()
+    }
+}
+
+impl<T> Display for PatchElements<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
 #[cfg(feature = "axum")]
 impl<T> IntoResponse for PatchElements<T>
 where
     T: Display,
 {
     fn into_response(self) -> Response {
+        let body = match self.try_render_to_string() {
+            Ok(body) => body,
+            Err(error) => return error.into_response(),
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_response::<T>(body.len());
         (
             StatusCode::OK,
             [(header::CONTENT_TYPE, "text/event-stream")],
-            self.to_string(),
+            body,
         )
             .into_response()
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullSignals {
+    #[default]
+    Delete,
+    Skip,
+}
+
 pub struct PatchSignals<T: SignalEnum> {
     pub only_if_missing: Option<bool>,
+    pub nulls: NullSignals,
+    pub pretty: bool,
     pub signals: Vec<T>,
 }
 
@@ -129,6 +352,8 @@ impl<T: SignalEnum> PatchSignals<T> {
     pub fn new(signals: Vec<T>) -> Self {
         Self {
             only_if_missing: None,
+            nulls: NullSignals::Delete,
+            pretty: false,
             signals,
         }
     }
@@ -137,37 +362,160 @@ impl<T: SignalEnum> PatchSignals<T> {
         self.only_if_missing = Some(value);
         self
     }
+
+    pub fn nulls(mut self, nulls: NullSignals) -> Self {
+        self.nulls = nulls;
+        self
+    }
+
+    pub fn skip_nulls(self) -> Self {
+        self.nulls(NullSignals::Skip)
+    }
+
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
 }
 
-impl<T: SignalEnum> Display for PatchSignals<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: SignalEnum> Render for PatchSignals<T> {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
         writeln!(f, "event: datastar-patch-signals")?;
 
         if let Some(only_if_missing) = self.only_if_missing {
             writeln!(f, "data: onlyIfMissing {}", only_if_missing)?;
         }
 
-        let merged = crate::signal::merge_signals(&self.signals);
-        writeln!(f, "data: signals {}", merged)?;
+        let mut merged = crate::signal::merge_signals(&self.signals);
+        if self.nulls == NullSignals::Skip
+            && let serde_json::Value::Object(map) = &mut merged
+        {
+            map.retain(|_, value| !value.is_null());
+        }
+
+        if self.pretty {
+            let pretty = serde_json::to_string_pretty(&merged).map_err(|_| std::fmt::Error)?;
+            for line in pretty.lines() {
+                writeln!(f, "data: signals {}", line)?;
+            }
+        } else {
+            writeln!(f, "data: signals {}", merged)?;
+        }
 
         writeln!(f)?;
 
         Ok(())
     }
+
+    fn size_hint(&self) -> usize {
+        "event: datastar-patch-signals\ndata: signals {}\n\n".len()
+    }
+}
+
+impl<T: SignalEnum> Display for PatchSignals<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
 }
 
 #[cfg(feature = "axum")]
 impl<T: SignalEnum> IntoResponse for PatchSignals<T> {
     fn into_response(self) -> Response {
-        (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, "text/event-stream")],
-            self.to_string(),
-        )
+        self.try_render_to_string()
+            .map(|body| {
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "text/event-stream")],
+                    body,
+                )
+            })
+            .into_response()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OobPatches {
+    events: Vec<String>,
+    error: Option<RenderError>,
+}
+
+impl OobPatches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, event: impl Display) -> &mut Self {
+        match try_display(&event) {
+            Ok(event) => self.events.push(event),
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn events(&self) -> &[String] {
+        &self.events
+    }
+}
+
+impl Render for OobPatches {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if let Some(error) = &self.error {
+            return Err(report(error.clone()));
+        }
+        for event in &self.events {
+            f.write_str(event)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        self.events.iter().map(String::len).sum()
+    }
+}
+
+impl Display for OobPatches {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for OobPatches {
+    fn into_response(self) -> Response {
+        self.try_render_to_string()
+            .map(|body| {
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "text/event-stream")],
+                    body,
+                )
+            })
             .into_response()
     }
 }
 
+pub trait PatchSink {
+    fn send(&self, event: impl Display) -> usize;
+
+    fn patch_elements<T: Display>(&self, patch: PatchElements<T>) -> usize {
+        self.send(patch)
+    }
+
+    fn patch_signals<T: SignalEnum>(&self, patch: PatchSignals<T>) -> usize {
+        self.send(patch)
+    }
+}
+
 pub struct DatastarInterval {
     duration: Duration,
     leading: bool,
@@ -197,7 +545,7 @@ impl DatastarInterval {
 impl Display for DatastarInterval {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ms = self.duration.as_millis();
-        let duration_str = if ms >= 1000 && ms % 1000 == 0 {
+        let duration_str = if ms >= 1000 && ms.is_multiple_of(1000) {
             format!("{}s", ms / 1000)
         } else {
             format!("{}ms", ms)
@@ -214,6 +562,141 @@ impl Display for DatastarInterval {
     }
 }
 
+#[derive(Default)]
+pub struct DatastarIntersect {
+    once: bool,
+    threshold: Option<u8>,
+}
+
+impl DatastarIntersect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    pub fn threshold(mut self, percent: u8) -> Self {
+        self.threshold = Some(percent.min(100));
+        self
+    }
+}
+
+impl Display for DatastarIntersect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "data-on-intersect")?;
+        if self.once {
+            write!(f, "__once")?;
+        }
+        if let Some(threshold) = self.threshold {
+            write!(f, "__threshold.{}", threshold)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Signal)]
+pub enum ProgressSignals {
+    ProgressPercent(f32),
+    ProgressStatus(String),
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr(class = "progress")]
+pub struct ProgressBar {
+    percent: f32,
+
+    #[element("progress")]
+    #[attr(max = "100", value = "{percent}", "data-attr:value" = ProgressPercent::JS_REF)]
+    bar: (),
+
+    #[element("span")]
+    #[attr("data-text" = ProgressStatus::JS_REF)]
+    status: String,
+}
+
+pub struct Progress;
+
+impl Progress {
+    pub fn bar(percent: f32, status: impl Into<String>) -> ProgressBar {
+        ProgressBar {
+            percent: percent.clamp(0.0, 100.0),
+            bar: (),
+            status: status.into(),
+        }
+    }
+
+    pub fn percent(percent: f32) -> PatchSignals<ProgressSignals> {
+        PatchSignals::new(vec![ProgressSignals::ProgressPercent(
+            percent.clamp(0.0, 100.0),
+        )])
+    }
+
+    pub fn update(percent: f32, status: impl Into<String>) -> PatchSignals<ProgressSignals> {
+        PatchSignals::new(vec![
+            ProgressSignals::ProgressPercent(percent.clamp(0.0, 100.0)),
+            ProgressSignals::ProgressStatus(status.into()),
+        ])
+    }
+
+    #[cfg(feature = "stream")]
+    pub fn throttle<S>(
+        progress: S,
+        interval: Duration,
+    ) -> impl futures_util::Stream<Item = PatchSignals<ProgressSignals>>
+    where
+        S: futures_util::Stream<Item = f32>,
+    {
+        use futures_util::StreamExt;
+        use std::time::Instant;
+
+        let state = (Box::pin(progress.fuse()), None::<Instant>, None::<f32>);
+
+        futures_util::stream::unfold(
+            state,
+            move |(mut inner, mut last, mut pending)| async move {
+                loop {
+                    // A value held back by the window goes out once the window
+                    // elapses, not only when the next value arrives. Without a
+                    // runtime to time it, it waits for the next value instead.
+                    let flush_at = pending
+                        .and(last)
+                        .filter(|_| tokio::runtime::Handle::try_current().is_ok());
+                    let next = match flush_at {
+                        Some(at) => tokio::select! {
+                            next = inner.next() => next,
+                            () = tokio::time::sleep_until((at + interval).into()) => {
+                                let value = pending.take()?;
+                                last = Some(Instant::now());
+                                return Some((Progress::percent(value), (inner, last, None)));
+                            }
+                        },
+                        None => inner.next().await,
+                    };
+                    match next {
+                        Some(value) => {
+                            let due =
+                                last.is_none_or(|at| at.elapsed() >= interval) || value >= 100.0;
+                            if due {
+                                last = Some(Instant::now());
+                                return Some((Progress::percent(value), (inner, last, None)));
+                            }
+                            pending = Some(value);
+                        }
+                        None => {
+                            let value = pending.take()?;
+                            return Some((Progress::percent(value), (inner, last, None)));
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,4 +753,13 @@ mod tests {
         let interval = DatastarInterval::new(Duration::from_millis(1500));
         assert_eq!(interval.to_string(), "data-on-interval__duration.1500ms");
     }
+
+    #[test]
+    fn test_datastar_intersect() {
+        assert_eq!(DatastarIntersect::new().to_string(), "data-on-intersect");
+        assert_eq!(
+            DatastarIntersect::new().once().threshold(25).to_string(),
+            "data-on-intersect__once__threshold.25"
+        );
+    }
 }
@@ -0,0 +1,471 @@
+use std::fmt::Display;
+
+use crate::datastar::{OobPatches, PatchElements, PatchMode};
+use crate::html::is_void_element;
+
+pub(crate) type Attr<'a> = (&'a str, Option<&'a str>);
+
+pub(crate) struct OpenTag<'a> {
+    pub(crate) tag: &'a str,
+    pub(crate) attrs: Vec<Attr<'a>>,
+    pub(crate) end: usize,
+    pub(crate) self_closing: bool,
+}
+
+impl OpenTag<'_> {
+    fn has_content(&self) -> bool {
+        !self.self_closing && !is_void_element(self.tag)
+    }
+}
+
+struct Node<'a> {
+    tag: &'a str,
+    attrs: Vec<Attr<'a>>,
+    inner: &'a str,
+    outer: &'a str,
+}
+
+impl Node<'_> {
+    fn id(&self) -> Option<&str> {
+        attr_value(&self.attrs, "id").flatten()
+    }
+}
+
+fn attr_value<'a>(attrs: &[Attr<'a>], name: &str) -> Option<Option<&'a str>> {
+    attrs.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+}
+
+pub(crate) fn parse_open_tag(html: &str) -> Option<OpenTag<'_>> {
+    let rest = html.strip_prefix('<')?;
+    let name_len = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    if name_len == 0 || !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let tag = &rest[..name_len];
+    let mut attrs = Vec::new();
+    let mut pos = 1 + name_len;
+
+    loop {
+        pos += html[pos..].len() - html[pos..].trim_start().len();
+        let rest = &html[pos..];
+
+        if rest.starts_with("/>") {
+            return Some(OpenTag {
+                tag,
+                attrs,
+                end: pos + 2,
+                self_closing: true,
+            });
+        }
+        if rest.starts_with('>') {
+            return Some(OpenTag {
+                tag,
+                attrs,
+                end: pos + 1,
+                self_closing: false,
+            });
+        }
+
+        let name_len = rest.find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))?;
+        if name_len == 0 {
+            return None;
+        }
+        let name = &rest[..name_len];
+        pos += name_len;
+
+        let Some(rest) = html[pos..].strip_prefix('=') else {
+            attrs.push((name, None));
+            continue;
+        };
+        pos += 1;
+
+        match rest.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let len = rest[1..].find(quote)?;
+                attrs.push((name, Some(&rest[1..1 + len])));
+                pos += len + 2;
+            }
+            _ => {
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                attrs.push((name, Some(&rest[..len])));
+                pos += len;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "strict-patches")]
+pub(crate) fn root_id(html: &str) -> Option<String> {
+    let open = parse_open_tag(html.trim_start())?;
+    attr_value(&open.attrs, "id").flatten().map(unescape)
+}
+
+fn parse_element(html: &str) -> Option<Node<'_>> {
+    let open = parse_open_tag(html)?;
+    if !open.has_content() {
+        return Some(Node {
+            tag: open.tag,
+            inner: "",
+            outer: &html[..open.end],
+            attrs: open.attrs,
+        });
+    }
+
+    let mut depth = 1;
+    let mut pos = open.end;
+    if is_raw_text(open.tag) {
+        pos += html[pos..].find(&format!("</{}", open.tag))?;
+    }
+
+    loop {
+        let start = pos + html[pos..].find('<')?;
+        let rest = &html[start..];
+
+        if rest.starts_with("</") {
+            let end = start + rest.find('>')? + 1;
+            depth -= 1;
+            if depth == 0 {
+                return Some(Node {
+                    tag: open.tag,
+                    inner: &html[open.end..start],
+                    outer: &html[..end],
+                    attrs: open.attrs,
+                });
+            }
+            pos = end;
+        } else if rest.starts_with("<!--") {
+            pos = start + rest.find("-->")? + 3;
+        } else if let Some(tag) = parse_open_tag(rest) {
+            pos = start + tag.end;
+            if tag.has_content() {
+                depth += 1;
+                if is_raw_text(tag.tag) {
+                    pos += html[pos..].find(&format!("</{}", tag.tag))?;
+                }
+            }
+        } else {
+            pos = start + 1;
+        }
+    }
+}
+
+fn is_raw_text(tag: &str) -> bool {
+    tag.eq_ignore_ascii_case("script") || tag.eq_ignore_ascii_case("style")
+}
+
+fn parse_children(inner: &str) -> Option<Vec<Node<'_>>> {
+    let mut children = Vec::new();
+    let mut rest = inner.trim_start();
+    while !rest.is_empty() {
+        let node = parse_element(rest)?;
+        rest = rest[node.outer.len()..].trim_start();
+        children.push(node);
+    }
+    Some(children)
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .replace("</", "<\\/")
+}
+
+fn remove(selector: String) -> PatchElements<&'static str> {
+    PatchElements::new(vec![])
+        .selector(selector)
+        .mode(PatchMode::Remove)
+}
+
+pub fn diff(old: &impl Display, new: &impl Display) -> OobPatches {
+    let old = old.to_string();
+    let new = new.to_string();
+    let mut patches = OobPatches::new();
+
+    if old == new {
+        return patches;
+    }
+
+    let (Some(old_node), Some(new_node)) = (parse_element(old.trim()), parse_element(new.trim()))
+    else {
+        patches.push(PatchElements::new(vec![new.trim()]));
+        return patches;
+    };
+
+    match new_node.id() {
+        Some(id) if old_node.id() == Some(id) => {
+            diff_node(&old_node, &new_node, id, &mut patches);
+        }
+        _ => {
+            patches.push(PatchElements::new(vec![new_node.outer]));
+        }
+    }
+
+    patches
+}
+
+fn diff_node(old: &Node, new: &Node, id: &str, patches: &mut OobPatches) {
+    let selector = format!("#{}", id);
+    let attrs_changed = old.tag != new.tag || old.attrs != new.attrs;
+    let inner_changed = old.inner != new.inner;
+
+    match (attrs_changed, inner_changed) {
+        (false, false) => {}
+        // The attribute patch is an inline script, which a strict CSP would
+        // block, so strict builds morph the whole element instead.
+        (true, false) if old.tag == new.tag && !cfg!(feature = "csp-strict") => {
+            patches.push(attribute_patch(id, &old.attrs, &new.attrs));
+        }
+        (false, true) => diff_inner(old, new, &selector, patches),
+        _ => {
+            patches.push(PatchElements::new(vec![new.outer]).selector(selector));
+        }
+    }
+}
+
+fn attribute_patch(id: &str, old: &[Attr], new: &[Attr]) -> PatchElements<String> {
+    let mut script = format!(
+        "const target = document.getElementById({});",
+        js_string(&unescape(id))
+    );
+
+    for (name, value) in new {
+        if attr_value(old, name) != Some(*value) {
+            script.push_str(&format!(
+                "target.setAttribute({}, {});",
+                js_string(name),
+                js_string(&unescape(value.unwrap_or_default()))
+            ));
+        }
+    }
+    for (name, _) in old {
+        if attr_value(new, name).is_none() {
+            script.push_str(&format!("target.removeAttribute({});", js_string(name)));
+        }
+    }
+
+    PatchElements::new(vec![format!(
+        "<script data-effect=\"el.remove()\">{}</script>",
+        script
+    )])
+    .selector("body")
+    .mode(PatchMode::Append)
+}
+
+fn diff_inner(old: &Node, new: &Node, selector: &str, patches: &mut OobPatches) {
+    let inner = PatchElements::new(vec![new.inner])
+        .selector(selector)
+        .mode(PatchMode::Inner)
+        .to_string();
+
+    let keyed = parse_children(old.inner)
+        .zip(parse_children(new.inner))
+        .and_then(|(old, new)| keyed_patches(selector, &old, &new));
+
+    match keyed {
+        Some(keyed) if keyed.to_string().len() < inner.len() => {
+            for event in keyed.events() {
+                patches.push(event);
+            }
+        }
+        _ => {
+            patches.push(inner);
+        }
+    }
+}
+
+fn keyed_patches(parent: &str, old: &[Node], new: &[Node]) -> Option<OobPatches> {
+    let old_keys = old.iter().map(Node::id).collect::<Option<Vec<_>>>()?;
+    let new_keys = new.iter().map(Node::id).collect::<Option<Vec<_>>>()?;
+
+    let has_duplicates = |keys: &[&str]| {
+        keys.iter()
+            .enumerate()
+            .any(|(i, key)| keys[..i].contains(key))
+    };
+    if has_duplicates(&old_keys) || has_duplicates(&new_keys) {
+        return None;
+    }
+
+    let kept_old: Vec<_> = old_keys.iter().filter(|k| new_keys.contains(k)).collect();
+    let kept_new: Vec<_> = new_keys.iter().filter(|k| old_keys.contains(k)).collect();
+    if kept_old != kept_new {
+        return None;
+    }
+
+    let mut patches = OobPatches::new();
+
+    for key in old_keys.iter().filter(|k| !new_keys.contains(k)) {
+        patches.push(remove(format!("#{}", key)));
+    }
+
+    for (i, node) in new.iter().enumerate() {
+        let key = new_keys[i];
+        match old_keys.iter().position(|k| *k == key) {
+            Some(index) => diff_node(&old[index], node, key, &mut patches),
+            None if i == 0 => {
+                patches.push(
+                    PatchElements::new(vec![node.outer])
+                        .selector(parent)
+                        .mode(PatchMode::Prepend),
+                );
+            }
+            None => {
+                patches.push(
+                    PatchElements::new(vec![node.outer])
+                        .selector(format!("#{}", new_keys[i - 1]))
+                        .mode(PatchMode::After),
+                );
+            }
+        }
+    }
+
+    Some(patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: impl IntoIterator<Item = (u32, &'static str)>) -> String {
+        let items: String = items
+            .into_iter()
+            .map(|(id, text)| format!("<li id=\"item-{id}\" class=\"row\">{text}</li>"))
+            .collect();
+        format!("<ul id=\"list\">{items}</ul>")
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert!(diff(&"<p id=\"a\">hi</p>", &"<p id=\"a\">hi</p>").is_empty());
+    }
+
+    #[cfg(not(feature = "csp-strict"))]
+    #[test]
+    fn test_diff_attribute_change() {
+        let patches = diff(
+            &"<div id=\"a\" class=\"x\" hidden>hi</div>",
+            &"<div id=\"a\" class=\"&quot;y&quot;\">hi</div>",
+        );
+
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            patches.to_string(),
+            concat!(
+                "event: datastar-patch-elements\n",
+                "data: selector body\n",
+                "data: mode append\n",
+                "data: elements <script data-effect=\"el.remove()\">",
+                "const target = document.getElementById(\"a\");",
+                "target.setAttribute(\"class\", \"\\\"y\\\"\");",
+                "target.removeAttribute(\"hidden\");</script>\n",
+                "\n"
+            )
+        );
+    }
+
+    #[cfg(feature = "csp-strict")]
+    #[test]
+    fn test_diff_attribute_change_without_inline_script() {
+        let patches = diff(&"<p id=\"a\" class=\"x\">hi</p>", &"<p id=\"a\">hi</p>");
+
+        assert_eq!(
+            patches.to_string(),
+            "event: datastar-patch-elements\ndata: selector #a\ndata: elements <p id=\"a\">hi</p>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_inner_change() {
+        let patches = diff(&"<p id=\"a\">one</p>", &"<p id=\"a\">two</p>");
+
+        assert_eq!(
+            patches.to_string(),
+            "event: datastar-patch-elements\ndata: selector #a\ndata: mode inner\ndata: elements two\n\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_outer_change() {
+        let patches = diff(&"<p id=\"a\">one</p>", &"<p id=\"a\" class=\"b\">two</p>");
+
+        assert_eq!(
+            patches.to_string(),
+            "event: datastar-patch-elements\ndata: selector #a\ndata: elements <p id=\"a\" class=\"b\">two</p>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_without_id_replaces_element() {
+        let patches = diff(&"<p>one</p>", &"<p>two</p>");
+
+        assert_eq!(
+            patches.to_string(),
+            "event: datastar-patch-elements\ndata: elements <p>two</p>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_keyed_list() {
+        let rows = (1..=20).map(|id| (id, "row"));
+        let old = list(rows.clone());
+        let new = list(
+            rows.filter(|(id, _)| *id != 5)
+                .map(|(id, text)| {
+                    if id == 10 {
+                        (id, "changed")
+                    } else {
+                        (id, text)
+                    }
+                })
+                .chain([(21, "new")]),
+        );
+
+        let patches = diff(&old, &new);
+
+        assert_eq!(
+            patches.events(),
+            [
+                "event: datastar-patch-elements\ndata: selector #item-5\ndata: mode remove\n\n",
+                "event: datastar-patch-elements\ndata: selector #item-10\ndata: mode inner\ndata: elements changed\n\n",
+                "event: datastar-patch-elements\ndata: selector #item-20\ndata: mode after\ndata: elements <li id=\"item-21\" class=\"row\">new</li>\n\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_reordered_list_falls_back_to_inner() {
+        let old = list([(1, "a"), (2, "b")]);
+        let new = list([(2, "b"), (1, "a")]);
+
+        let patches = diff(&old, &new);
+
+        assert_eq!(patches.len(), 1);
+        assert!(
+            patches
+                .to_string()
+                .contains("data: selector #list\ndata: mode inner\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_and_void_elements() {
+        let html = "<div id=\"a\"><div><br><img src=\"x\"/>text</div><!-- </div> --></div>tail";
+        let node = parse_element(html).map(|node| node.outer);
+
+        assert_eq!(
+            node,
+            Some("<div id=\"a\"><div><br><img src=\"x\"/>text</div><!-- </div> --></div>")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::context::{RenderContext, with_value};
+use crate::signal::SignalSelector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Experiment {
+    name: &'static str,
+    variants: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub experiment: &'static str,
+    pub variant: &'static str,
+}
+
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl Experiment {
+    pub const fn new(name: &'static str, variants: &'static [&'static str]) -> Self {
+        assert!(
+            !variants.is_empty(),
+            "experiment needs at least one variant"
+        );
+        Self { name, variants }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn bucket(&self, user_id: impl Display) -> Bucket {
+        let key = format!("{}:{}", self.name, user_id);
+        let index = fnv1a(key.bytes()) % self.variants.len() as u64;
+        Bucket {
+            experiment: self.name,
+            variant: self.variants[index as usize],
+        }
+    }
+
+    pub fn assign(&self, user_id: impl Display) -> Bucket {
+        let bucket = self.bucket(user_id);
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_exposure(bucket.experiment, bucket.variant);
+        bucket
+    }
+}
+
+impl Bucket {
+    pub fn is(&self, variant: &str) -> bool {
+        self.variant == variant
+    }
+
+    pub fn signal<S>(&self) -> S::Enum
+    where
+        S: SignalSelector,
+        S::Value: From<&'static str>,
+    {
+        S::wrap(S::Value::from(self.variant))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Assignments(HashMap<&'static str, &'static str>);
+
+impl RenderContext {
+    pub fn experiment(self, bucket: Bucket) -> Self {
+        let mut assignments = self.get::<Assignments>().cloned().unwrap_or_default();
+        assignments.0.insert(bucket.experiment, bucket.variant);
+        self.with(assignments)
+    }
+}
+
+pub fn variant(experiment: &str) -> Option<&'static str> {
+    with_value(|assignments: Option<&Assignments>| {
+        assignments.and_then(|a| a.0.get(experiment).copied())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUTTON: Experiment = Experiment::new("checkout-button", &["control", "green"]);
+
+    #[test]
+    fn bucketing_is_deterministic() {
+        let bucket = BUTTON.bucket(42);
+        assert_eq!(bucket, BUTTON.bucket(42));
+        assert_eq!(bucket.experiment, "checkout-button");
+
+        let variants: std::collections::HashSet<_> =
+            (0..100).map(|id| BUTTON.bucket(id).variant).collect();
+        assert_eq!(variants.len(), 2);
+    }
+
+    #[derive(crate::Signal)]
+    enum ExperimentSignals {
+        CheckoutVariant(String),
+    }
+
+    #[test]
+    fn bucket_as_signal() {
+        let bucket = BUTTON.bucket(3);
+        let signal = bucket.signal::<CheckoutVariant>();
+
+        assert_eq!(
+            CheckoutVariant::extract(&signal).map(String::as_str),
+            Some(bucket.variant)
+        );
+        assert!(bucket.is(bucket.variant));
+    }
+
+    #[test]
+    fn variant_from_render_context() {
+        let bucket = BUTTON.assign("user-7");
+        let other = Experiment::new("pricing", &["a"]).assign("user-7");
+        let context = RenderContext::new().experiment(bucket).experiment(other);
+
+        assert_eq!(variant("checkout-button"), None);
+        context.render(|| {
+            assert_eq!(variant("checkout-button"), Some(bucket.variant));
+            assert_eq!(variant("pricing"), Some("a"));
+        });
+    }
+}
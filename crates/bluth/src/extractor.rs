@@ -5,6 +5,7 @@ use axum::{
 };
 use std::collections::HashMap;
 
+use crate::form::FieldError;
 use crate::signal::SignalSelector;
 
 pub struct Signal<S: SignalSelector>(pub S::Value);
@@ -16,6 +17,15 @@ pub enum SignalRejection {
     MissingDatastarHeader,
     InvalidJson(String),
     MissingSignal(&'static str),
+    InvalidField(FieldError),
+    Forged(&'static str),
+    SealerMissing,
+}
+
+impl From<FieldError> for SignalRejection {
+    fn from(error: FieldError) -> Self {
+        SignalRejection::InvalidField(error)
+    }
 }
 
 impl IntoResponse for SignalRejection {
@@ -30,6 +40,17 @@ impl IntoResponse for SignalRejection {
             SignalRejection::MissingSignal(signal) => {
                 (StatusCode::BAD_REQUEST, format!("Missing signal: {}", signal))
             }
+            SignalRejection::InvalidField(error) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, error.to_string())
+            }
+            SignalRejection::Forged(signal) => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid sealed signal: {}", signal),
+            ),
+            SignalRejection::SealerMissing => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Signal sealer not configured".to_owned(),
+            ),
         };
         (status, message).into_response()
     }
@@ -59,57 +80,82 @@ where
     }
 }
 
-impl<S1, S2> FromSignalMap for (Signal<S1>, Signal<S2>)
+impl FromSignalMap for HashMap<String, serde_json::Value> {
+    fn from_signal_map(
+        signals: &HashMap<String, serde_json::Value>,
+    ) -> Result<Self, SignalRejection> {
+        Ok(signals.clone())
+    }
+}
+
+impl<A, B> FromSignalMap for (A, B)
 where
-    S1: SignalSelector,
-    S2: SignalSelector,
+    A: FromSignalMap,
+    B: FromSignalMap,
 {
     fn from_signal_map(
         signals: &HashMap<String, serde_json::Value>,
     ) -> Result<Self, SignalRejection> {
-        Ok((
-            Signal::<S1>::from_signal_map(signals)?,
-            Signal::<S2>::from_signal_map(signals)?,
-        ))
+        Ok((A::from_signal_map(signals)?, B::from_signal_map(signals)?))
     }
 }
 
-impl<S1, S2, S3> FromSignalMap for (Signal<S1>, Signal<S2>, Signal<S3>)
+impl<A, B, C> FromSignalMap for (A, B, C)
 where
-    S1: SignalSelector,
-    S2: SignalSelector,
-    S3: SignalSelector,
+    A: FromSignalMap,
+    B: FromSignalMap,
+    C: FromSignalMap,
 {
     fn from_signal_map(
         signals: &HashMap<String, serde_json::Value>,
     ) -> Result<Self, SignalRejection> {
         Ok((
-            Signal::<S1>::from_signal_map(signals)?,
-            Signal::<S2>::from_signal_map(signals)?,
-            Signal::<S3>::from_signal_map(signals)?,
+            A::from_signal_map(signals)?,
+            B::from_signal_map(signals)?,
+            C::from_signal_map(signals)?,
         ))
     }
 }
 
-impl<S1, S2, S3, S4> FromSignalMap for (Signal<S1>, Signal<S2>, Signal<S3>, Signal<S4>)
+impl<A, B, C, D> FromSignalMap for (A, B, C, D)
 where
-    S1: SignalSelector,
-    S2: SignalSelector,
-    S3: SignalSelector,
-    S4: SignalSelector,
+    A: FromSignalMap,
+    B: FromSignalMap,
+    C: FromSignalMap,
+    D: FromSignalMap,
 {
     fn from_signal_map(
         signals: &HashMap<String, serde_json::Value>,
     ) -> Result<Self, SignalRejection> {
         Ok((
-            Signal::<S1>::from_signal_map(signals)?,
-            Signal::<S2>::from_signal_map(signals)?,
-            Signal::<S3>::from_signal_map(signals)?,
-            Signal::<S4>::from_signal_map(signals)?,
+            A::from_signal_map(signals)?,
+            B::from_signal_map(signals)?,
+            C::from_signal_map(signals)?,
+            D::from_signal_map(signals)?,
         ))
     }
 }
 
+#[cfg(feature = "sealed-signals")]
+impl<S> FromSignalMap for crate::sealed::Sealed<S>
+where
+    S: SignalSelector,
+{
+    fn from_signal_map(
+        signals: &HashMap<String, serde_json::Value>,
+    ) -> Result<Self, SignalRejection> {
+        let token = signals
+            .get(S::NAME)
+            .ok_or(SignalRejection::MissingSignal(S::NAME))?;
+
+        let token = token.as_str().ok_or(SignalRejection::Forged(S::NAME))?;
+        crate::sealed::Sealed::<S>::open(token).map_err(|error| match error {
+            crate::sealed::SealError::NotInstalled => SignalRejection::SealerMissing,
+            _ => SignalRejection::Forged(S::NAME),
+        })
+    }
+}
+
 async fn parse_signals_from_request(
     req: Request,
 ) -> Result<HashMap<String, serde_json::Value>, SignalRejection> {
@@ -136,22 +182,36 @@ async fn parse_signals_from_request(
             .await
             .map_err(|e| SignalRejection::InvalidJson(e.to_string()))?;
 
+        #[cfg(feature = "cookie-signals")]
+        if body_bytes.is_empty()
+            && let Some(signals) = crate::cookie::signals_from_cookie(&parts.headers)
+        {
+            return Ok(signals);
+        }
+
         serde_json::from_slice(&body_bytes).map_err(|e| SignalRejection::InvalidJson(e.to_string()))
     } else {
         let query_string = parts.uri.query().unwrap_or("");
         let mut datastar_json = None;
 
         for pair in query_string.split('&') {
-            if let Some((key, value)) = pair.split_once('=') {
-                if key == "datastar" {
-                    let decoded = urlencoding::decode(value)
-                        .map_err(|e| SignalRejection::InvalidJson(e.to_string()))?;
-                    datastar_json = Some(decoded.into_owned());
-                    break;
-                }
+            if let Some((key, value)) = pair.split_once('=')
+                && key == "datastar"
+            {
+                let decoded = urlencoding::decode(value)
+                    .map_err(|e| SignalRejection::InvalidJson(e.to_string()))?;
+                datastar_json = Some(decoded.into_owned());
+                break;
             }
         }
 
+        #[cfg(feature = "cookie-signals")]
+        if datastar_json.is_none()
+            && let Some(signals) = crate::cookie::signals_from_cookie(&parts.headers)
+        {
+            return Ok(signals);
+        }
+
         let json_str = datastar_json.ok_or_else(|| {
             SignalRejection::InvalidJson("Missing datastar query parameter".to_string())
         })?;
@@ -185,3 +245,17 @@ where
         Ok(Signals(T::from_signal_map(&signals)?))
     }
 }
+
+#[cfg(feature = "sealed-signals")]
+impl<S, T> FromRequest<S> for crate::sealed::Sealed<T>
+where
+    S: Send + Sync,
+    T: SignalSelector,
+{
+    type Rejection = SignalRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let signals = parse_signals_from_request(req).await?;
+        Self::from_signal_map(&signals)
+    }
+}
@@ -0,0 +1,250 @@
+#[cfg(feature = "axum")]
+use axum::http::{StatusCode, header};
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use std::fmt::{self, Formatter};
+
+use crate::Element;
+#[cfg(feature = "axum")]
+use crate::Render;
+use crate::html::{Cdata, escape_attr, escape_xml};
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+fn cdata(value: &String, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", Cdata(value))
+}
+
+fn link_href(href: &String, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, r#"<link href="{}"/>"#, escape_attr(href))
+}
+
+fn author_name(name: &String, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "<author><name>{}</name></author>", escape_xml(name))
+}
+
+#[derive(Element)]
+pub struct XmlDocument<T: fmt::Display> {
+    #[element(raw)]
+    declaration: &'static str,
+
+    #[element]
+    pub root: T,
+}
+
+impl<T: fmt::Display> XmlDocument<T> {
+    pub fn new(root: T) -> Self {
+        Self {
+            declaration: XML_DECLARATION,
+            root,
+        }
+    }
+}
+
+#[derive(Element)]
+#[element("rss", xml)]
+#[attr(version = "2.0")]
+pub struct Rss<'a> {
+    #[element]
+    pub channel: &'a Channel,
+}
+
+#[derive(Debug, Clone, Default, Element)]
+#[element("channel", xml)]
+pub struct Channel {
+    #[element("title")]
+    pub title: String,
+
+    #[element("link")]
+    pub link: String,
+
+    #[element("description")]
+    pub description: String,
+
+    #[element("language", render_if = self.language.is_some())]
+    pub language: Option<String>,
+
+    #[element("lastBuildDate", render_if = self.last_build_date.is_some())]
+    pub last_build_date: Option<String>,
+
+    #[element]
+    pub items: Vec<Item>,
+}
+
+impl Channel {
+    pub fn document(&self) -> XmlDocument<Rss<'_>> {
+        XmlDocument::new(Rss { channel: self })
+    }
+}
+
+#[derive(Debug, Clone, Default, Element)]
+#[element("item", xml)]
+pub struct Item {
+    #[element("title")]
+    pub title: String,
+
+    #[element("link")]
+    pub link: String,
+
+    #[element("description", raw, with = cdata, render_if = self.description.is_some())]
+    pub description: Option<String>,
+
+    #[element("guid", render_if = self.guid.is_some())]
+    pub guid: Option<String>,
+
+    #[element("pubDate", render_if = self.pub_date.is_some())]
+    pub pub_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Element)]
+#[element("feed", xml)]
+#[attr(xmlns = "http://www.w3.org/2005/Atom")]
+pub struct AtomFeed {
+    #[element("id")]
+    pub id: String,
+
+    #[element("title")]
+    pub title: String,
+
+    #[element("updated")]
+    pub updated: String,
+
+    #[element(raw, with = link_href)]
+    pub link: String,
+
+    #[element(raw, with = author_name)]
+    pub author: Option<String>,
+
+    #[element]
+    pub entries: Vec<AtomEntry>,
+}
+
+impl AtomFeed {
+    pub fn document(&self) -> XmlDocument<&Self> {
+        XmlDocument::new(self)
+    }
+}
+
+#[derive(Debug, Clone, Default, Element)]
+#[element("entry", xml)]
+pub struct AtomEntry {
+    #[element("id")]
+    pub id: String,
+
+    #[element("title")]
+    pub title: String,
+
+    #[element("updated")]
+    pub updated: String,
+
+    #[element(raw, with = link_href)]
+    pub link: String,
+
+    #[element("summary", render_if = self.summary.is_some())]
+    pub summary: Option<String>,
+
+    #[element("content", raw, with = cdata, render_if = self.content.is_some())]
+    #[attr(type = "html")]
+    pub content: Option<String>,
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for Channel {
+    fn into_response(self) -> Response {
+        self.document()
+            .try_render_to_string()
+            .map(|body| {
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/rss+xml")],
+                    body,
+                )
+            })
+            .into_response()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for AtomFeed {
+    fn into_response(self) -> Response {
+        self.document()
+            .try_render_to_string()
+            .map(|body| {
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/atom+xml")],
+                    body,
+                )
+            })
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rss_channel() {
+        let channel = Channel {
+            title: "News & Views".into(),
+            link: "https://example.com".into(),
+            description: "Latest posts".into(),
+            language: Some("en".into()),
+            items: vec![Item {
+                title: "First <post>".into(),
+                link: "https://example.com/posts/1".into(),
+                description: Some("<p>Hello</p>".into()),
+                pub_date: Some("Mon, 01 Jan 2024 00:00:00 +0000".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            channel.document().to_string(),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0"><channel>"#,
+                "<title>News &amp; Views</title><link>https://example.com</link>",
+                "<description>Latest posts</description><language>en</language>",
+                "<item><title>First &lt;post&gt;</title><link>https://example.com/posts/1</link>",
+                "<description><![CDATA[<p>Hello</p>]]></description>",
+                "<pubDate>Mon, 01 Jan 2024 00:00:00 +0000</pubDate></item>",
+                "</channel></rss>"
+            )
+        );
+    }
+
+    #[test]
+    fn atom_feed() {
+        let feed = AtomFeed {
+            id: "urn:example".into(),
+            title: "Example".into(),
+            updated: "2024-01-01T00:00:00Z".into(),
+            link: "https://example.com/?a=1&b=2".into(),
+            author: Some("Jane".into()),
+            entries: vec![AtomEntry {
+                id: "urn:example:1".into(),
+                title: "One".into(),
+                updated: "2024-01-01T00:00:00Z".into(),
+                link: "https://example.com/1".into(),
+                content: Some("<b>bold</b>".into()),
+                ..Default::default()
+            }],
+        };
+
+        assert_eq!(
+            feed.document().to_string(),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom">"#,
+                "<id>urn:example</id><title>Example</title><updated>2024-01-01T00:00:00Z</updated>",
+                r#"<link href="https://example.com/?a=1&amp;b=2"/>"#,
+                "<author><name>Jane</name></author>",
+                "<entry><id>urn:example:1</id><title>One</title><updated>2024-01-01T00:00:00Z</updated>",
+                r#"<link href="https://example.com/1"/>"#,
+                r#"<content type="html"><![CDATA[<b>bold</b>]]></content></entry>"#,
+                "</feed>"
+            )
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::context::{RenderContext, with_value};
+
+pub trait FeatureFlag {
+    fn is_enabled(&self, flag: &str) -> bool;
+}
+
+impl FeatureFlag for HashSet<String> {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.contains(flag)
+    }
+}
+
+impl FeatureFlag for HashSet<&'static str> {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.contains(flag)
+    }
+}
+
+impl FeatureFlag for Vec<String> {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.iter().any(|f| f == flag)
+    }
+}
+
+impl FeatureFlag for Vec<&'static str> {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.contains(&flag)
+    }
+}
+
+#[derive(Clone)]
+pub struct Flags(Rc<dyn FeatureFlag>);
+
+impl Flags {
+    pub fn new(flags: impl FeatureFlag + 'static) -> Self {
+        Self(Rc::new(flags))
+    }
+}
+
+impl FeatureFlag for Flags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.0.is_enabled(flag)
+    }
+}
+
+impl RenderContext {
+    pub fn flags(self, flags: impl FeatureFlag + 'static) -> Self {
+        self.with(Flags::new(flags))
+    }
+}
+
+pub fn is_enabled(flag: &str) -> bool {
+    with_value(|flags: Option<&Flags>| flags.is_some_and(|flags| flags.is_enabled(flag)))
+}
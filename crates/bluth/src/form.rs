@@ -0,0 +1,221 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::Element;
+use crate::signal::{SignalMap, SignalSelector};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldErrorKind {
+    Missing,
+    Invalid(String),
+    BelowMin(String),
+    AboveMax(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub signal: &'static str,
+    pub kind: FieldErrorKind,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            FieldErrorKind::Missing => write!(f, "{} is required", self.signal),
+            FieldErrorKind::Invalid(value) => write!(f, "{} is not valid: {}", self.signal, value),
+            FieldErrorKind::BelowMin(min) => write!(f, "{} must be at least {}", self.signal, min),
+            FieldErrorKind::AboveMax(max) => write!(f, "{} must be at most {}", self.signal, max),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Values a bounded input can compare against its `min`/`max`.
+pub trait Bounded {
+    /// Whether the value is an ordinary number. NaN compares false against
+    /// both bounds and infinities slip past any unset one, so parsing rejects
+    /// non-finite values up front.
+    fn is_finite(&self) -> bool {
+        true
+    }
+}
+
+macro_rules! bounded_ints {
+    ($($ty:ty),*) => {
+        $(impl Bounded for $ty {})*
+    };
+}
+
+bounded_ints!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl Bounded for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+impl Bounded for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Bounded for chrono::NaiveDate {}
+
+fn parse_bounded<T>(
+    signal: &'static str,
+    signals: &SignalMap,
+    min: Option<&T>,
+    max: Option<&T>,
+) -> Result<T, FieldError>
+where
+    T: FromStr + PartialOrd + Display + Bounded,
+{
+    let error = |kind| FieldError { signal, kind };
+
+    let raw = match signals.get(signal) {
+        None | Some(serde_json::Value::Null) => return Err(error(FieldErrorKind::Missing)),
+        Some(serde_json::Value::String(s)) => s.trim().to_string(),
+        Some(value) => value.to_string(),
+    };
+    if raw.is_empty() {
+        return Err(error(FieldErrorKind::Missing));
+    }
+
+    let value: T = raw
+        .parse()
+        .map_err(|_| error(FieldErrorKind::Invalid(raw.clone())))?;
+
+    if !value.is_finite() {
+        return Err(error(FieldErrorKind::Invalid(raw)));
+    }
+
+    if let Some(min) = min
+        && value < *min
+    {
+        return Err(error(FieldErrorKind::BelowMin(min.to_string())));
+    }
+    if let Some(max) = max
+        && value > *max
+    {
+        return Err(error(FieldErrorKind::AboveMax(max.to_string())));
+    }
+
+    Ok(value)
+}
+
+#[derive(Element)]
+#[element("input")]
+#[attr("type" = "number", name = S::NAME, "data-bind" = S::NAME)]
+pub struct NumberInput<S, T>
+where
+    S: SignalSelector,
+    T: FromStr + PartialOrd + Display + Bounded,
+{
+    #[attr]
+    min: Option<T>,
+
+    #[attr]
+    max: Option<T>,
+
+    #[attr]
+    step: Option<T>,
+
+    #[attr]
+    value: Option<T>,
+
+    selector: PhantomData<S>,
+}
+
+impl<S, T> NumberInput<S, T>
+where
+    S: SignalSelector,
+    T: FromStr + PartialOrd + Display + Bounded,
+{
+    pub fn new(_signal: S) -> Self {
+        Self {
+            min: None,
+            max: None,
+            step: None,
+            value: None,
+            selector: PhantomData,
+        }
+    }
+
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn step(mut self, step: T) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn value(mut self, value: T) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn parse(&self, signals: &SignalMap) -> Result<T, FieldError> {
+        parse_bounded(S::NAME, signals, self.min.as_ref(), self.max.as_ref())
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[derive(Element)]
+#[element("input")]
+#[attr("type" = "date", name = S::NAME, "data-bind" = S::NAME)]
+pub struct DateInput<S: SignalSelector> {
+    #[attr]
+    min: Option<chrono::NaiveDate>,
+
+    #[attr]
+    max: Option<chrono::NaiveDate>,
+
+    #[attr]
+    value: Option<chrono::NaiveDate>,
+
+    selector: PhantomData<S>,
+}
+
+#[cfg(feature = "chrono")]
+impl<S: SignalSelector> DateInput<S> {
+    pub fn new(_signal: S) -> Self {
+        Self {
+            min: None,
+            max: None,
+            value: None,
+            selector: PhantomData,
+        }
+    }
+
+    pub fn min(mut self, min: chrono::NaiveDate) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: chrono::NaiveDate) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn value(mut self, value: chrono::NaiveDate) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn parse(&self, signals: &SignalMap) -> Result<chrono::NaiveDate, FieldError> {
+        parse_bounded(S::NAME, signals, self.min.as_ref(), self.max.as_ref())
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+use crate::context;
+use crate::html::{escape_attr, is_valid_attr_name};
+use crate::render::{RenderError, report};
+
+pub trait RenderHook: 'static {
+    fn on_element_start(&self, _tag: &str, _attrs: &mut HookAttrs<'_>) -> fmt::Result {
+        Ok(())
+    }
+
+    fn on_element_end(&self, _tag: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Hooks(Vec<Rc<dyn RenderHook>>);
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, hook: impl RenderHook) -> Self {
+        self.0.push(Rc::new(hook));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+pub struct HookAttrs<'a> {
+    f: &'a mut dyn fmt::Write,
+    emitted: &'a str,
+    pushed: Vec<String>,
+}
+
+impl HookAttrs<'_> {
+    pub fn contains(&self, name: &str) -> bool {
+        attr_names(self.emitted).any(|emitted| emitted == name)
+            || self.pushed.iter().any(|pushed| pushed == name)
+    }
+
+    pub fn push(&mut self, name: &str, value: impl Display) -> fmt::Result {
+        if !self.claim(name) {
+            return Ok(());
+        }
+        write!(self.f, " {}=\"{}\"", name, escape_attr(value))
+    }
+
+    pub fn push_bare(&mut self, name: &str) -> fmt::Result {
+        if !self.claim(name) {
+            return Ok(());
+        }
+        write!(self.f, " {}", name)
+    }
+
+    // Invalid names are dropped the way flattened attributes are, and a name
+    // the element already has keeps its original value.
+    fn claim(&mut self, name: &str) -> bool {
+        if !is_valid_attr_name(name) || self.contains(name) {
+            return false;
+        }
+        self.pushed.push(name.to_string());
+        true
+    }
+}
+
+fn attr_names(emitted: &str) -> impl Iterator<Item = &str> {
+    let mut rest = emitted;
+    std::iter::from_fn(move || {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        rest = &rest[end..];
+        if let Some(value) = rest.strip_prefix("=\"") {
+            rest = value.find('"').map_or("", |close| &value[close + 1..]);
+        }
+        Some(name)
+    })
+}
+
+pub fn reject(tag: &str, reason: impl Into<String>) -> fmt::Error {
+    report(RenderError::Rejected {
+        tag: tag.to_string(),
+        reason: reason.into(),
+    })
+}
+
+fn with_installed(run: impl FnOnce(&[Rc<dyn RenderHook>]) -> fmt::Result) -> fmt::Result {
+    context::with_value::<Hooks, _>(|hooks| match hooks {
+        Some(hooks) if !hooks.is_empty() => run(&hooks.0),
+        _ => Ok(()),
+    })
+}
+
+#[doc(hidden)]
+pub fn element_start(tag: &str, emitted: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+    with_installed(|hooks| {
+        let mut attrs = HookAttrs {
+            f,
+            emitted,
+            pushed: Vec::new(),
+        };
+        for hook in hooks {
+            hook.on_element_start(tag, &mut attrs)?;
+        }
+        Ok(())
+    })
+}
+
+#[doc(hidden)]
+pub fn element_end(tag: &str) -> fmt::Result {
+    with_installed(|hooks| {
+        for hook in hooks {
+            hook.on_element_end(tag)?;
+        }
+        Ok(())
+    })
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::{self, Display, Write};
 
 pub struct EscapedAttr<T>(pub T);
@@ -22,6 +23,138 @@ pub fn escape_attr<T: Display>(value: T) -> EscapedAttr<T> {
     EscapedAttr(value)
 }
 
+#[doc(hidden)]
+pub struct AttrExpr<'a, T: ?Sized>(pub &'a str, pub &'a T);
+
+#[doc(hidden)]
+pub trait ViaBool {
+    fn write_attr(&self, f: &mut dyn Write) -> fmt::Result;
+}
+
+// These take the literal `"true"`/`"false"` rather than presence/absence.
+fn is_enumerated_attr(name: &str) -> bool {
+    name.starts_with("aria-")
+        || name.starts_with("data-")
+        || matches!(name, "contenteditable" | "draggable" | "spellcheck")
+}
+
+impl ViaBool for &AttrExpr<'_, bool> {
+    fn write_attr(&self, f: &mut dyn Write) -> fmt::Result {
+        if is_enumerated_attr(self.0) {
+            write!(f, " {}=\"{}\"", self.0, self.1)?;
+        } else if *self.1 {
+            write!(f, " {}", self.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display> ViaBool for &AttrExpr<'_, Option<T>> {
+    fn write_attr(&self, f: &mut dyn Write) -> fmt::Result {
+        match self.1 {
+            Some(value) => write!(f, " {}=\"{}\"", self.0, escape_attr(value)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaValue {
+    fn write_attr(&self, f: &mut dyn Write) -> fmt::Result;
+}
+
+impl<T: Display + ?Sized> ViaValue for AttrExpr<'_, T> {
+    fn write_attr(&self, f: &mut dyn Write) -> fmt::Result {
+        write!(f, " {}=\"{}\"", self.0, escape_attr(self.1))
+    }
+}
+
+pub fn is_valid_attr_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|ch| {
+            !ch.is_whitespace()
+                && !ch.is_control()
+                && !matches!(ch, '"' | '\'' | '>' | '<' | '/' | '=' | '&')
+        })
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag)
+}
+
+pub fn is_valid_tag_name(tag: &str) -> bool {
+    let mut chars = tag.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | ':' | '.'))
+}
+
+pub fn tag_name<T: AsRef<str> + ?Sized>(tag: &T) -> Result<&str, fmt::Error> {
+    let tag = tag.as_ref();
+    if is_valid_tag_name(tag) {
+        Ok(tag)
+    } else {
+        Err(crate::render::report(crate::RenderError::InvalidTag(
+            tag.to_string(),
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HeadingLevel {
+    #[default]
+    H1,
+    H2,
+    H3,
+    H4,
+    H5,
+    H6,
+}
+
+impl HeadingLevel {
+    pub fn new(level: u8) -> Self {
+        match level {
+            0 | 1 => HeadingLevel::H1,
+            2 => HeadingLevel::H2,
+            3 => HeadingLevel::H3,
+            4 => HeadingLevel::H4,
+            5 => HeadingLevel::H5,
+            _ => HeadingLevel::H6,
+        }
+    }
+
+    pub fn level(self) -> u8 {
+        self as u8 + 1
+    }
+
+    pub fn nested(self) -> Self {
+        Self::new(self.level() + 1)
+    }
+}
+
+impl AsRef<str> for HeadingLevel {
+    fn as_ref(&self) -> &str {
+        match self {
+            HeadingLevel::H1 => "h1",
+            HeadingLevel::H2 => "h2",
+            HeadingLevel::H3 => "h3",
+            HeadingLevel::H4 => "h4",
+            HeadingLevel::H5 => "h5",
+            HeadingLevel::H6 => "h6",
+        }
+    }
+}
+
+impl Display for HeadingLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
 pub fn escape_attr_str(value: &str) -> String {
     let mut result = String::with_capacity(value.len());
     for ch in value.chars() {
@@ -36,6 +169,379 @@ pub fn escape_attr_str(value: &str) -> String {
     result
 }
 
+pub struct EscapedHtml<T>(pub T);
+
+impl<T: Display> Display for EscapedHtml<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        for ch in value.chars() {
+            match ch {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                _ => f.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_html<T: Display>(value: T) -> EscapedHtml<T> {
+    EscapedHtml(value)
+}
+
+pub struct EscapedXml<T>(pub T);
+
+impl<T: Display> Display for EscapedXml<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        for ch in value.chars() {
+            match ch {
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                _ => f.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_xml<T: Display>(value: T) -> EscapedXml<T> {
+    EscapedXml(value)
+}
+
+pub struct Lazy<F>(pub F);
+
+impl<F> Lazy<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    pub fn new(render: F) -> Self {
+        Self(render)
+    }
+}
+
+impl<F> Display for Lazy<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+pub struct Cdata<T>(pub T);
+
+impl<T: Display> Display for Cdata<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        f.write_str("<![CDATA[")?;
+        f.write_str(&value.replace("]]>", "]]]]><![CDATA[>"))?;
+        f.write_str("]]>")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Raw<T>(pub T);
+
+pub type PreEscaped<T> = Raw<T>;
+
+impl<T: Display> Display for Raw<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrValue {
+    Str(String),
+    Bool(bool),
+    Raw(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attr {
+    name: String,
+    value: AttrValue,
+}
+
+impl Attr {
+    pub fn new(name: impl Into<String>, value: impl Display) -> Self {
+        Self {
+            name: name.into(),
+            value: AttrValue::Str(value.to_string()),
+        }
+    }
+
+    pub fn bool(name: impl Into<String>, value: bool) -> Self {
+        Self {
+            name: name.into(),
+            value: AttrValue::Bool(value),
+        }
+    }
+
+    pub fn raw(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: AttrValue::Raw(value.into()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &AttrValue {
+        &self.value
+    }
+}
+
+impl Display for Attr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !is_valid_attr_name(&self.name) {
+            return Ok(());
+        }
+        match &self.value {
+            AttrValue::Str(value) => write!(f, " {}=\"{}\"", self.name, escape_attr(value)),
+            AttrValue::Bool(true) => write!(f, " {}", self.name),
+            AttrValue::Bool(false) => Ok(()),
+            AttrValue::Raw(value) => write!(f, " {}=\"{}\"", self.name, value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataAttrs(BTreeMap<String, String>);
+
+impl DataAttrs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, suffix: impl Into<String>, value: impl Display) -> Self {
+        self.insert(suffix, value);
+        self
+    }
+
+    pub fn insert(&mut self, suffix: impl Into<String>, value: impl Display) -> &mut Self {
+        self.0.insert(suffix.into(), value.to_string());
+        self
+    }
+
+    pub fn get(&self, suffix: &str) -> Option<&str> {
+        self.0.get(suffix).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl<K: Into<String>, V: Display> FromIterator<(K, V)> for DataAttrs {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut attrs = Self::new();
+        for (suffix, value) in iter {
+            attrs.insert(suffix, value);
+        }
+        attrs
+    }
+}
+
+impl Display for DataAttrs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (suffix, value) in &self.0 {
+            if is_valid_attr_name(suffix) {
+                write!(f, " data-{}=\"{}\"", suffix, escape_attr(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style(Vec<(String, String)>);
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prop(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn prop_if(self, cond: bool, name: impl Into<String>, value: impl Display) -> Self {
+        if cond { self.prop(name, value) } else { self }
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Display) -> &mut Self {
+        let name = name.into();
+        let value = value.to_string();
+        match self.0.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(prop) => prop.1 = value,
+            None => self.0.push((name, value)),
+        }
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn is_valid_css_property(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (name, value) in &self.0 {
+            if !is_valid_css_property(name) || value.contains([';', '{', '}']) {
+                continue;
+            }
+            if !first {
+                f.write_str("; ")?;
+            }
+            first = false;
+            write!(f, "{}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn inject_root_attrs(html: &str, extra: &str) -> String {
+    let Some(start) = html
+        .match_indices('<')
+        .map(|(i, _)| i)
+        .find(|&i| html[i + 1..].starts_with(|c: char| c.is_ascii_alphabetic()))
+    else {
+        return html.to_string();
+    };
+    let Some(open) = crate::diff::parse_open_tag(&html[start..]) else {
+        return html.to_string();
+    };
+    let extra_tag = format!("<x {}>", extra.trim());
+    let Some(extra) = crate::diff::parse_open_tag(&extra_tag) else {
+        return html.to_string();
+    };
+
+    let mut attrs: Vec<(&str, Option<String>)> = open
+        .attrs
+        .iter()
+        .map(|(name, value)| (*name, value.map(str::to_string)))
+        .collect();
+    for (name, value) in extra.attrs {
+        match attrs.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, Some(existing))) if name == "class" && value.is_some() => {
+                existing.push(' ');
+                existing.push_str(value.unwrap_or_default());
+            }
+            Some(slot) => slot.1 = value.map(str::to_string),
+            None => attrs.push((name, value.map(str::to_string))),
+        }
+    }
+
+    let mut result = String::with_capacity(html.len() + extra_tag.len());
+    result.push_str(&html[..start]);
+    result.push('<');
+    result.push_str(open.tag);
+    for (name, value) in attrs {
+        result.push(' ');
+        result.push_str(name);
+        if let Some(value) = value {
+            result.push_str("=\"");
+            result.push_str(&value);
+            result.push('"');
+        }
+    }
+    result.push_str(if open.self_closing { "/>" } else { ">" });
+    result.push_str(&html[start + open.end..]);
+    result
+}
+
+pub trait RootAttrs: Display + Sized {
+    fn with_attr(self, name: &str, value: impl Display) -> WithAttrs<Self> {
+        WithAttrs::new(self).with_attr(name, value)
+    }
+
+    fn with_attrs<K, V>(self, extra: impl IntoIterator<Item = (K, V)>) -> WithAttrs<Self>
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        WithAttrs::new(self).with_attrs(extra)
+    }
+}
+
+pub struct WithAttrs<T> {
+    inner: T,
+    attrs: String,
+}
+
+impl<T: Display> WithAttrs<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            attrs: String::new(),
+        }
+    }
+
+    // A name failing `is_valid_attr_name` could end the tag early, so it is
+    // skipped rather than written.
+    pub fn with_attr(mut self, name: &str, value: impl Display) -> Self {
+        if !is_valid_attr_name(name) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(name, "invalid attribute name dropped");
+            return self;
+        }
+        let _ = write!(self.attrs, " {}=\"{}\"", name, escape_attr(value));
+        self
+    }
+
+    pub fn with_attrs<K, V>(mut self, extra: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        for (name, value) in extra {
+            self = self.with_attr(name.as_ref(), value);
+        }
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Display> Display for WithAttrs<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.attrs.is_empty() {
+            return write!(f, "{}", self.inner);
+        }
+        f.write_str(&inject_root_attrs(&self.inner.to_string(), &self.attrs))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +594,46 @@ mod tests {
         let escaped = format!("{}", escape_attr(value));
         assert_eq!(escaped, "42");
     }
+
+    #[test]
+    fn escaped_xml_apostrophe() {
+        let escaped = format!("{}", escape_xml("it's <b>"));
+        assert_eq!(escaped, "it&apos;s &lt;b&gt;");
+    }
+
+    #[test]
+    fn inject_root_attrs_merges_class_and_appends() {
+        let html = r#"<div class="card" id="a"><span class="x">hi</span></div>"#;
+        let injected = inject_root_attrs(html, r#"class="highlighted" data-on:click="go()""#);
+        assert_eq!(
+            injected,
+            r#"<div class="card highlighted" id="a" data-on:click="go()"><span class="x">hi</span></div>"#
+        );
+    }
+
+    #[test]
+    fn inject_root_attrs_keeps_self_closing() {
+        let injected = inject_root_attrs(r#"<img src="a.png"/>"#, r#"alt="a" hidden"#);
+        assert_eq!(injected, r#"<img src="a.png" alt="a" hidden/>"#);
+    }
+
+    #[test]
+    fn inject_root_attrs_without_element() {
+        assert_eq!(
+            inject_root_attrs("plain text", r#"class="x""#),
+            "plain text"
+        );
+    }
+
+    #[test]
+    fn cdata_wraps_content() {
+        let cdata = Cdata("<p>Hello & welcome</p>");
+        assert_eq!(cdata.to_string(), "<![CDATA[<p>Hello & welcome</p>]]>");
+    }
+
+    #[test]
+    fn cdata_splits_terminator() {
+        let cdata = Cdata("a]]>b");
+        assert_eq!(cdata.to_string(), "<![CDATA[a]]]]><![CDATA[>b]]>");
+    }
 }
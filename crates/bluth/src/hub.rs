@@ -0,0 +1,447 @@
+use axum::body::Body;
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::http::request::Parts;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, TryCurrentError};
+use tokio::sync::broadcast;
+
+use crate::Element;
+use crate::components::{Toast, ToastQueue};
+use crate::datastar::{PatchElements, PatchMode, PatchSignals, PatchSink};
+use crate::id::UniqueId;
+use crate::presence::PresenceGuard;
+use crate::signal::SignalEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LagPolicy {
+    #[default]
+    DropOldest,
+    Disconnect,
+}
+
+type LagHandler = Arc<dyn Fn(u64) -> String + Send + Sync>;
+
+const RETAIN_FOR: Duration = Duration::from_secs(30);
+
+pub const ORIGIN_HEADER: &str = "x-bluth-origin";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OriginToken(pub String);
+
+impl OriginToken {
+    pub fn generate() -> Self {
+        Self(UniqueId::with_prefix("origin").to_string())
+    }
+}
+
+impl Display for OriginToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for OriginToken {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if let Some(token) = parts.extensions.get::<OriginToken>() {
+            return Ok(Some(token.clone()));
+        }
+        Ok(parts
+            .headers
+            .get(ORIGIN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| OriginToken(value.to_string())))
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for OriginToken {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .unwrap_or_default()
+            .ok_or(StatusCode::BAD_REQUEST)
+    }
+}
+
+#[derive(Clone)]
+struct Envelope {
+    origin: Option<Arc<str>>,
+    event: Arc<str>,
+}
+
+impl Envelope {
+    fn new(origin: Option<&OriginToken>, event: impl Display) -> Self {
+        Self {
+            origin: origin.map(|token| Arc::from(token.0.as_str())),
+            event: Arc::from(event.to_string()),
+        }
+    }
+}
+
+type Topics = Arc<Mutex<HashMap<String, broadcast::Sender<Envelope>>>>;
+type Retained = Arc<Mutex<HashMap<String, Vec<(Instant, Envelope)>>>>;
+
+#[derive(Clone)]
+pub struct Hub {
+    sender: broadcast::Sender<Envelope>,
+    topics: Topics,
+    retained: Retained,
+    capacity: usize,
+    lag_policy: LagPolicy,
+    on_lag: Option<LagHandler>,
+}
+
+impl Hub {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            topics: Arc::default(),
+            retained: Arc::default(),
+            capacity,
+            lag_policy: LagPolicy::default(),
+            on_lag: None,
+        }
+    }
+
+    pub fn lag_policy(mut self, policy: LagPolicy) -> Self {
+        self.lag_policy = policy;
+        self
+    }
+
+    pub fn on_lag<F, E>(mut self, handler: F) -> Self
+    where
+        F: Fn(u64) -> E + Send + Sync + 'static,
+        E: Display,
+    {
+        self.on_lag = Some(Arc::new(move |missed| handler(missed).to_string()));
+        self
+    }
+
+    pub fn send(&self, event: impl Display) -> usize {
+        self.sender.send(Envelope::new(None, event)).unwrap_or(0)
+    }
+
+    pub fn patch_elements<T: Display>(&self, patch: PatchElements<T>) -> usize {
+        self.send(patch)
+    }
+
+    pub fn patch_signals<T: SignalEnum>(&self, patch: PatchSignals<T>) -> usize {
+        self.send(patch)
+    }
+
+    pub fn toast(&self, toast: Toast) -> usize {
+        let mut queue = ToastQueue::new();
+        queue.push(toast);
+        self.patch_elements(queue.into_patch())
+    }
+
+    pub fn send_to(&self, topic: &str, event: impl Display) -> usize {
+        self.send_envelope_to(topic, Envelope::new(None, event))
+    }
+
+    fn send_envelope_to(&self, topic: &str, envelope: Envelope) -> usize {
+        let mut topics = self.topics.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(sender) = topics.get(topic) else {
+            return 0;
+        };
+        let sent = sender.send(envelope).unwrap_or(0);
+        if sent == 0 {
+            topics.remove(topic);
+        }
+        sent
+    }
+
+    // Like `send_to`, but an event nobody is subscribed for yet is kept for a
+    // while and handed to the next `subscribe_to(topic)`.
+    pub fn send_or_retain_to(&self, topic: &str, event: impl Display) -> usize {
+        let envelope = Envelope::new(None, event);
+        let topics = self.topics.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sent) = topics
+            .get(topic)
+            .and_then(|sender| sender.send(envelope.clone()).ok())
+        {
+            return sent;
+        }
+
+        let mut retained = self.retained.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        retained.retain(|_, events| {
+            events.retain(|(at, _)| now.duration_since(*at) < RETAIN_FOR);
+            !events.is_empty()
+        });
+        let events = retained.entry(topic.to_string()).or_default();
+        if events.len() < self.capacity {
+            events.push((now, envelope));
+        }
+        0
+    }
+
+    pub fn patch_to<T: Display>(&self, topic: &str, patch: PatchElements<T>) -> usize {
+        self.send_to(topic, patch)
+    }
+
+    pub fn patch_signals_to<T: SignalEnum>(&self, topic: &str, patch: PatchSignals<T>) -> usize {
+        self.send_to(topic, patch)
+    }
+
+    pub fn from_origin<'a>(&'a self, origin: &'a OriginToken) -> FromOrigin<'a> {
+        FromOrigin { hub: self, origin }
+    }
+
+    pub fn subscribe(&self) -> Subscription {
+        self.subscription(self.sender.subscribe())
+    }
+
+    pub fn subscribe_to(&self, topic: impl Into<String>) -> Subscription {
+        let topic = topic.into();
+        let mut topics = self.topics.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = self
+            .retained
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&topic)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(at, _)| at.elapsed() < RETAIN_FOR)
+            .map(|(_, envelope)| envelope)
+            .collect();
+        let receiver = topics
+            .entry(topic.clone())
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe();
+        let mut subscription = self.subscription(receiver);
+        subscription.pending = pending;
+        subscription.topic = Some((self.topics.clone(), topic));
+        subscription
+    }
+
+    fn subscription(&self, receiver: broadcast::Receiver<Envelope>) -> Subscription {
+        Subscription {
+            receiver,
+            lag_policy: self.lag_policy,
+            on_lag: self.on_lag.clone(),
+            pending: VecDeque::new(),
+            closed: false,
+            presence: None,
+            origin: None,
+            topic: None,
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    pub fn topic_subscriber_count(&self, topic: &str) -> usize {
+        let topics = self.topics.lock().unwrap_or_else(|e| e.into_inner());
+        topics
+            .get(topic)
+            .map_or(0, |sender| sender.receiver_count())
+    }
+
+    pub fn topic_count(&self) -> usize {
+        self.topics.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+impl PatchSink for Hub {
+    fn send(&self, event: impl Display) -> usize {
+        Hub::send(self, event)
+    }
+}
+
+pub struct FromOrigin<'a> {
+    hub: &'a Hub,
+    origin: &'a OriginToken,
+}
+
+impl FromOrigin<'_> {
+    pub fn send(&self, event: impl Display) -> usize {
+        self.hub
+            .sender
+            .send(Envelope::new(Some(self.origin), event))
+            .unwrap_or(0)
+    }
+
+    pub fn send_to(&self, topic: &str, event: impl Display) -> usize {
+        self.hub
+            .send_envelope_to(topic, Envelope::new(Some(self.origin), event))
+    }
+
+    pub fn patch_to<T: Display>(&self, topic: &str, patch: PatchElements<T>) -> usize {
+        self.send_to(topic, patch)
+    }
+
+    pub fn patch_signals_to<T: SignalEnum>(&self, topic: &str, patch: PatchSignals<T>) -> usize {
+        self.send_to(topic, patch)
+    }
+}
+
+impl PatchSink for FromOrigin<'_> {
+    fn send(&self, event: impl Display) -> usize {
+        FromOrigin::send(self, event)
+    }
+}
+
+impl Default for Hub {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+pub struct Subscription {
+    receiver: broadcast::Receiver<Envelope>,
+    pending: VecDeque<Envelope>,
+    lag_policy: LagPolicy,
+    on_lag: Option<LagHandler>,
+    closed: bool,
+    presence: Option<PresenceGuard>,
+    origin: Option<OriginToken>,
+    topic: Option<(Topics, String)>,
+}
+
+impl Subscription {
+    pub(crate) fn with_presence(mut self, guard: PresenceGuard) -> Self {
+        self.presence = Some(guard);
+        self
+    }
+
+    pub fn origin(mut self, origin: OriginToken) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    fn is_echo(&self, envelope: &Envelope) -> bool {
+        match (&self.origin, &envelope.origin) {
+            (Some(own), Some(origin)) => own.0 == **origin,
+            _ => false,
+        }
+    }
+
+    pub async fn recv(&mut self) -> Option<Arc<str>> {
+        if self.closed {
+            return None;
+        }
+        if let Some(envelope) = self.pending.pop_front() {
+            return Some(envelope.event);
+        }
+
+        loop {
+            match self.receiver.recv().await {
+                Ok(envelope) if self.is_echo(&envelope) => continue,
+                Ok(envelope) => return Some(envelope.event),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    if self.lag_policy == LagPolicy::Disconnect {
+                        self.closed = true;
+                    }
+                    if let Some(on_lag) = &self.on_lag {
+                        return Some(Arc::from(on_lag(missed)));
+                    }
+                    if self.closed {
+                        return None;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+// The last subscriber to leave a topic removes it, so a map keyed by per-page
+// topics doesn't grow for as long as the hub lives. Later sends to the topic
+// find nothing to broadcast to, which is what makes `send_or_retain_to` retain
+// them for the next `subscribe_to`.
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let Some((topics, topic)) = &self.topic else {
+            return;
+        };
+        let mut topics = topics.lock().unwrap_or_else(|e| e.into_inner());
+        if topics
+            .get(topic)
+            .is_some_and(|sender| sender.receiver_count() <= 1)
+        {
+            topics.remove(topic);
+        }
+    }
+}
+
+impl IntoResponse for Subscription {
+    fn into_response(self) -> Response {
+        let stream = futures_util::stream::unfold(self, |mut subscription| async move {
+            let event = subscription.recv().await?;
+            Some((Ok::<_, Infallible>(event.to_string()), subscription))
+        });
+
+        (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/event-stream"),
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            Body::from_stream(stream),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr(id = "{id}")]
+pub struct Deferred<P: Display> {
+    id: UniqueId,
+
+    #[element]
+    placeholder: P,
+}
+
+impl<P: Display> Deferred<P> {
+    // The content goes only to `topic`, which should identify the requesting
+    // page; it is retained until that page's stream subscribes.
+    pub fn spawn<F, T>(
+        hub: &Hub,
+        topic: impl Into<String>,
+        placeholder: P,
+        future: F,
+    ) -> Result<Self, TryCurrentError>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Display + Send + 'static,
+    {
+        let runtime = Handle::try_current()?;
+        let id = UniqueId::with_prefix("deferred");
+        let selector = id.selector();
+        let topic = topic.into();
+        let hub = hub.clone();
+
+        runtime.spawn(async move {
+            let content = future.await;
+            hub.send_or_retain_to(
+                &topic,
+                PatchElements::new(vec![content])
+                    .selector(selector)
+                    .mode(PatchMode::Inner),
+            );
+        });
+
+        Ok(Self { id, placeholder })
+    }
+
+    pub fn id(&self) -> &UniqueId {
+        &self.id
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UniqueId {
+    prefix: &'static str,
+    value: u64,
+}
+
+impl UniqueId {
+    pub fn new() -> Self {
+        Self::with_prefix("bluth")
+    }
+
+    pub fn with_prefix(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            value: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn selector(&self) -> String {
+        format!("#{}", self)
+    }
+}
+
+impl Default for UniqueId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for UniqueId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.prefix, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_unique() {
+        let a = UniqueId::new();
+        let b = UniqueId::new();
+        assert_ne!(a, b);
+        assert_ne!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn prefix_and_selector() {
+        let id = UniqueId::with_prefix("modal");
+        assert!(id.to_string().starts_with("modal-"));
+        assert_eq!(id.selector(), format!("#{}", id));
+    }
+}
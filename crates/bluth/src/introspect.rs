@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+pub trait Describe {
+    fn describe() -> ComponentSchema;
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentSchema {
+    pub name: &'static str,
+    pub tag: Option<&'static str>,
+    pub attrs: Vec<&'static str>,
+    pub children: Vec<ChildSchema>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChildSchema {
+    pub field: &'static str,
+    pub ty: &'static str,
+    pub tag: Option<&'static str>,
+    pub many: bool,
+    pub describe: Option<fn() -> ComponentSchema>,
+}
+
+impl ChildSchema {
+    pub fn is_component(&self) -> bool {
+        self.describe.is_some()
+    }
+}
+
+#[doc(hidden)]
+pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> Probe<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaDescribe {
+    fn describe_fn(&self) -> Option<fn() -> ComponentSchema>;
+}
+
+impl<T: Describe + ?Sized> ViaDescribe for &Probe<T> {
+    fn describe_fn(&self) -> Option<fn() -> ComponentSchema> {
+        Some(T::describe)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaOpaque {
+    fn describe_fn(&self) -> Option<fn() -> ComponentSchema>;
+}
+
+impl<T: ?Sized> ViaOpaque for Probe<T> {
+    fn describe_fn(&self) -> Option<fn() -> ComponentSchema> {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentTree {
+    pub schema: ComponentSchema,
+    pub children: Vec<ComponentTree>,
+    pub recursive: bool,
+}
+
+pub fn tree<T: Describe>() -> ComponentTree {
+    build(T::describe, &mut Vec::new())
+}
+
+fn build(describe: fn() -> ComponentSchema, path: &mut Vec<&'static str>) -> ComponentTree {
+    let schema = describe();
+    if path.contains(&schema.name) {
+        return ComponentTree {
+            schema,
+            children: Vec::new(),
+            recursive: true,
+        };
+    }
+
+    path.push(schema.name);
+    let children = schema
+        .children
+        .iter()
+        .filter_map(|child| child.describe)
+        .map(|describe| build(describe, path))
+        .collect();
+    path.pop();
+
+    ComponentTree {
+        schema,
+        children,
+        recursive: false,
+    }
+}
+
+impl ComponentTree {
+    pub fn names(&self) -> BTreeSet<&'static str> {
+        let mut names = BTreeSet::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names(&self, names: &mut BTreeSet<&'static str>) {
+        names.insert(self.schema.name);
+        for child in &self.children {
+            child.collect_names(names);
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.schema.name == name || self.children.iter().any(|child| child.contains(name))
+    }
+
+    fn write_outline(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.schema.name, indent = depth * 2)?;
+        if let Some(tag) = self.schema.tag {
+            write!(f, " <{}>", tag)?;
+        }
+        if self.recursive {
+            f.write_str(" (recursive)")?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.write_outline(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ComponentTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_outline(f, 0)
+    }
+}
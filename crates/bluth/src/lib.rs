@@ -1,66 +1,294 @@
-pub use bluth_macros::{Element, Signal};
+pub use bluth_macros::{Element, ElementBuilder, Signal, SignalStore, html};
 
 #[macro_export]
 macro_rules! define_url {
-    ($name:ident, $prefix:literal, $($param:ident: $ty:ty),+ $(,)?) => {
-        #[derive(serde::Deserialize)]
+    (@opts $name:ident, [$($parent:tt)*] [$($label:tt)*] [$($sitemap:tt)*], parent = $new_parent:ident $(($($parent_arg:ident),* $(,)?))?, $($rest:tt)*) => {
+        $crate::define_url!(@opts $name, [$new_parent $(($($parent_arg),*))?] [$($label)*] [$($sitemap)*], $($rest)*);
+    };
+    (@opts $name:ident, [$($parent:tt)*] [$($label:tt)*] [$($sitemap:tt)*], label = $new_label:literal, $($rest:tt)*) => {
+        $crate::define_url!(@opts $name, [$($parent)*] [$new_label] [$($sitemap)*], $($rest)*);
+    };
+    (@opts $name:ident, [$($parent:tt)*] [$($label:tt)*] [$($sitemap:tt)*], sitemap $(($($new_sitemap:tt)*))?, $($rest:tt)*) => {
+        $crate::define_url!(@opts $name, [$($parent)*] [$($label)*] [sitemap $($($new_sitemap)*)?], $($rest)*);
+    };
+    (@opts $name:ident, [$($parent:tt)*] [$($label:literal)?] [$($sitemap:tt)*], $prefix:literal $(, $param:ident: $ty:ty)* $(,)?) => {
         pub struct $name {
-            $(pub $param: $ty),+
+            $(pub $param: $ty),*
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            #[allow(unused_variables)]
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let params = <$crate::url::PathParams as serde::Deserialize>::deserialize(deserializer)?;
+                Ok(Self {
+                    $($param: params.get(stringify!($param)).map_err(<D::Error as serde::de::Error>::custom)?),*
+                })
+            }
         }
 
         impl $name {
-            pub const PATTERN: &'static str = concat!($prefix, $("/{", stringify!($param), "}"),+);
+            pub const PATTERN: &'static str = Self::PATTERN_BRACED;
+            pub const PATTERN_BRACED: &'static str = concat!($prefix, $("/{", stringify!($param), "}"),*);
+            #[allow(dead_code)]
+            pub const PATTERN_COLON: &'static str = concat!($prefix, $("/:", stringify!($param)),*);
 
-            pub fn new($($param: $ty),+) -> Self {
-                Self { $($param),+ }
+            pub fn new($($param: $ty),*) -> Self {
+                Self { $($param),* }
             }
 
+            #[allow(unused_mut)]
             pub fn path(&self) -> String {
                 let mut s = String::from($prefix);
                 $(
                     s.push('/');
-                    s.push_str(&self.$param.to_string());
-                )+
+                    s.push_str(&$crate::url::PathSegment::encode(&self.$param));
+                )*
                 s
             }
         }
+
+        impl $crate::url::UrlPath for $name {
+            fn pattern() -> &'static str {
+                $name::PATTERN
+            }
+
+            fn path(&self) -> String {
+                $name::path(self)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&$name::path(self))
+            }
+        }
+
+        impl $crate::url::UrlNode for $name {
+            fn label(&self) -> &'static str {
+                $crate::__url_label!($name, $($label)?)
+            }
+
+            fn path(&self) -> String {
+                $name::path(self)
+            }
+
+            fn parent(&self) -> Option<Box<dyn $crate::url::UrlNode>> {
+                $crate::__url_parent!(self, $($parent)*)
+            }
+        }
+
+        $crate::__url_sitemap!($name, $($sitemap)*);
+    };
+    ($name:ident, $($rest:tt)*) => {
+        $crate::define_url!(@opts $name, [] [] [], $($rest)*);
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __url_label {
+    ($name:ident,) => {
+        match stringify!($name).strip_suffix("Url") {
+            Some(label) if !label.is_empty() => label,
+            _ => stringify!($name),
+        }
+    };
+    ($name:ident, $label:literal) => {
+        $label
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __url_parent {
+    ($self:ident,) => {
+        None
+    };
+    ($self:ident, $parent:ident) => {
+        Some(Box::new($parent::new()))
+    };
+    ($self:ident, $parent:ident ($($arg:ident),*)) => {
+        Some(Box::new($parent::new($(::core::clone::Clone::clone(&$self.$arg)),*)))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __url_sitemap {
+    ($name:ident,) => {};
+    ($name:ident, sitemap $(priority = $priority:literal)? $(,)? $(changefreq = $changefreq:ident)? $(,)?) => {
+        impl $crate::seo::SitemapUrl for $name {
+            const PRIORITY: Option<f32> = $crate::__url_sitemap!(@opt $($priority)?);
+            const CHANGEFREQ: Option<$crate::seo::ChangeFreq> =
+                $crate::__url_sitemap!(@opt $($crate::seo::ChangeFreq::$changefreq)?);
+        }
+    };
+    (@opt) => {
+        None
+    };
+    (@opt $value:expr) => {
+        Some($value)
+    };
+}
+
+#[cfg(feature = "axum")]
+#[macro_export]
+macro_rules! define_routes {
+    (prefix = $prefix:literal, $($rest:tt)*) => {
+        $crate::__axum::Router::new().nest(
+            $prefix,
+            $crate::define_routes!(@group $prefix, $($rest)*),
+        )
+    };
+    (@group $prefix:literal, layer = $layer:expr, $($rest:tt)*) => {
+        $crate::define_routes!(@group $prefix, $($rest)*).layer($layer)
+    };
+    (@group $prefix:literal, $($url:ty => $handler:expr),+ $(,)?) => {
+        $crate::__axum::Router::new()
+            $(.route(
+                const {
+                    assert!(
+                        $crate::url::is_under_prefix(<$url>::PATTERN, $prefix),
+                        concat!("route `", stringify!($url), "` is not under prefix `", $prefix, "`"),
+                    );
+                    $crate::url::route_pattern(<$url>::PATTERN, $prefix)
+                },
+                $handler,
+            ))+
+    };
+    ($($rest:tt)*) => {
+        $crate::define_routes!(@group "", $($rest)*)
+    };
+}
+
+#[macro_export]
+macro_rules! fragment {
+    ($($child:expr),* $(,)?) => {
+        $crate::render::Fragment(($($child,)*))
+    };
+}
+
+#[cfg(feature = "axum")]
+#[doc(hidden)]
+pub use axum as __axum;
+
+#[cfg(feature = "linkme")]
+#[doc(hidden)]
+pub use linkme as __linkme;
+
 #[cfg(test)]
 mod tests;
 
 use std::fmt::Display;
 
+pub mod builder;
+pub mod cache;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod components;
+pub mod context;
+#[cfg(feature = "csp-strict")]
+pub mod csp;
 pub mod datastar;
+pub mod diff;
+pub mod experiment;
+pub mod feed;
+pub mod flags;
+pub mod form;
+#[cfg(feature = "render-hooks")]
+pub mod hooks;
 pub mod html;
+pub mod id;
+#[cfg(feature = "introspect")]
+pub mod introspect;
+pub mod render;
+pub mod rows;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "sanitize")]
+pub mod sanitize;
+pub mod scaffold;
+pub mod seo;
 pub mod signal;
+#[cfg(feature = "dev-templates")]
+pub mod templates;
+pub mod url;
+pub mod window;
 
 #[cfg(feature = "axum")]
 pub mod extractor;
 
+#[cfg(feature = "axum")]
+pub mod negotiate;
+
+#[cfg(feature = "cookie-signals")]
+pub mod cookie;
+
+#[cfg(feature = "sealed-signals")]
+pub mod sealed;
+
+#[cfg(any(feature = "cookie-signals", feature = "sealed-signals"))]
+mod sign;
+
+#[cfg(feature = "hub")]
+pub mod hub;
+
+#[cfg(feature = "hub")]
+pub mod presence;
+
+#[cfg(any(feature = "hub", feature = "axum-ws"))]
+pub mod throttle;
+
+#[cfg(feature = "axum-ws")]
+pub mod ws;
+
+#[cfg(any(feature = "testing", all(test, feature = "axum")))]
+pub mod testing;
+
+pub use builder::BuilderError;
+pub use render::{AnyElement, Children, Fragment, Render, RenderError};
 pub use signal::{OptDisplay, SignalEnum, SignalSelector, SignalValue};
 
 #[cfg(feature = "axum")]
 pub use extractor::{Signal as SignalExtractor, Signals};
 
-#[derive(Element)]
-pub struct Document<T>
+pub struct Document<'a, T>
 where
     T: Display,
 {
-    #[element]
     doctype: &'static str,
+    html: Html<'a, T>,
+}
 
-    #[element]
-    html: Html<T>,
+impl<T> Render for Document<'_, T>
+where
+    T: Display,
+{
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        signal::unique_names(|| write!(f, "{}{}", self.doctype, self.html))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.doctype.len() + self.html.size_hint()
+    }
+}
+
+impl<T> Display for Document<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
 }
 
-impl<T> Document<T>
+impl<T> html::RootAttrs for Document<'_, T> where T: Display {}
+
+impl<'a, T> Document<'a, T>
 where
     T: Display,
 {
-    pub fn new(html: Html<T>) -> Self {
+    pub fn new(html: Html<'a, T>) -> Self {
         Self {
             doctype: "<!doctype html>",
             html,
@@ -70,28 +298,28 @@ where
 
 #[derive(Element)]
 #[element("html")]
-pub struct Html<T>
+#[attr(lang = context::lang_or(self.lang), dir = context::dir_or(self.lang))]
+pub struct Html<'a, T>
 where
     T: Display,
 {
-    #[attr]
-    pub lang: &'static str,
+    pub lang: &'a str,
 
     #[element]
-    pub head: Head,
+    pub head: Head<'a>,
 
     #[element]
-    pub body: Body<T>,
+    pub body: Body<'a, T>,
 }
 
 #[derive(Element)]
 #[element("body")]
-pub struct Body<T>
+pub struct Body<'a, T>
 where
     T: Display,
 {
     #[attr]
-    pub class: &'static str,
+    pub class: &'a str,
 
     #[element]
     pub children: Vec<T>,
@@ -99,34 +327,34 @@ where
 
 #[derive(Element)]
 #[element("head")]
-pub struct Head {
+pub struct Head<'a> {
     #[element]
-    pub link: Vec<Link>,
+    pub link: Vec<Link<'a>>,
 
     #[element]
-    pub script: Vec<Script>,
+    pub script: Vec<Script<'a>>,
 }
 
 #[derive(Element)]
 #[element("link")]
 #[attr(rel = "stylesheet")]
-pub struct Link {
+pub struct Link<'a> {
     #[attr]
-    pub id: Option<&'static str>,
+    pub id: Option<&'a str>,
 
     #[attr]
-    pub href: &'static str,
+    pub href: &'a str,
 }
 
 #[derive(Element)]
 #[element("script")]
-pub struct Script {
+pub struct Script<'a> {
     #[attr]
-    pub src: &'static str,
+    pub src: &'a str,
 
     #[attr(name = "async")]
     pub async_: bool,
 
     #[attr(name = "type")]
-    pub type_: &'static str,
+    pub type_: &'a str,
 }
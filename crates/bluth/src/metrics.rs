@@ -0,0 +1,451 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseSize {
+    pub responses: u64,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+}
+
+impl ResponseSize {
+    pub fn average_bytes(&self) -> u64 {
+        self.total_bytes.checked_div(self.responses).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderTime {
+    pub renders: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl RenderTime {
+    pub fn average(&self) -> Duration {
+        u32::try_from(self.renders)
+            .ok()
+            .and_then(|renders| self.total.checked_div(renders))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConditionalStats {
+    pub requests: u64,
+    pub not_modified: u64,
+}
+
+impl ConditionalStats {
+    pub fn not_modified_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.not_modified as f64 / self.requests as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricEvent {
+    ResponseRendered {
+        component: &'static str,
+        bytes: usize,
+    },
+    ExperimentExposure {
+        experiment: &'static str,
+        variant: &'static str,
+    },
+    RenderBudgetExceeded {
+        component: &'static str,
+        elapsed: Duration,
+        budget: Duration,
+    },
+    CacheLookup {
+        component: &'static str,
+        hit: bool,
+    },
+    ConditionalResponse {
+        component: &'static str,
+        not_modified: bool,
+    },
+}
+
+type Observer = Box<dyn Fn(&MetricEvent) + Send + Sync>;
+
+static SIZES: Mutex<Option<HashMap<&'static str, ResponseSize>>> = Mutex::new(None);
+static CACHE: Mutex<Option<HashMap<&'static str, CacheStats>>> = Mutex::new(None);
+static CONDITIONAL: Mutex<Option<HashMap<&'static str, ConditionalStats>>> = Mutex::new(None);
+static EXPOSURES: Mutex<Option<HashMap<(&'static str, &'static str), u64>>> = Mutex::new(None);
+static OBSERVER: OnceLock<Observer> = OnceLock::new();
+static RENDER_TIMES: Mutex<Option<HashMap<&'static str, &'static RenderCounters>>> =
+    Mutex::new(None);
+static RENDER_BUDGET_NANOS: AtomicU64 = AtomicU64::new(NO_BUDGET);
+
+const NO_BUDGET: u64 = u64::MAX;
+
+thread_local! {
+    static RENDER_PATH: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static LOCAL_COUNTERS: RefCell<HashMap<&'static str, &'static RenderCounters>> =
+        RefCell::new(HashMap::new());
+}
+
+#[derive(Default)]
+struct RenderCounters {
+    renders: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl RenderCounters {
+    // Each component's counters are registered once and leaked, so the
+    // per-render path only touches a thread-local cache and atomics.
+    fn of(component: &'static str) -> &'static RenderCounters {
+        LOCAL_COUNTERS.with(|local| {
+            *local.borrow_mut().entry(component).or_insert_with(|| {
+                *RENDER_TIMES
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get_or_insert_with(HashMap::new)
+                    .entry(component)
+                    .or_insert_with(|| Box::leak(Box::default()))
+            })
+        })
+    }
+
+    fn record(&self, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.renders.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> RenderTime {
+        RenderTime {
+            renders: self.renders.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+pub fn on_event(observer: impl Fn(&MetricEvent) + Send + Sync + 'static) -> Result<(), String> {
+    OBSERVER
+        .set(Box::new(observer))
+        .map_err(|_| "metrics observer already set".to_string())
+}
+
+pub fn record_response<T: ?Sized>(bytes: usize) {
+    let component = type_name::<T>();
+    {
+        let mut sizes = SIZES.lock().unwrap_or_else(|e| e.into_inner());
+        let size = sizes
+            .get_or_insert_with(HashMap::new)
+            .entry(component)
+            .or_default();
+        size.responses += 1;
+        size.total_bytes += bytes as u64;
+        size.max_bytes = size.max_bytes.max(bytes as u64);
+    }
+
+    if let Some(observer) = OBSERVER.get() {
+        observer(&MetricEvent::ResponseRendered { component, bytes });
+    }
+}
+
+// Called by `cache::Cached<T>` once per render, with whether it replayed stored output.
+pub fn record_cache_lookup<T: ?Sized>(hit: bool) {
+    let component = type_name::<T>();
+    {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = cache
+            .get_or_insert_with(HashMap::new)
+            .entry(component)
+            .or_default();
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+    }
+
+    if let Some(observer) = OBSERVER.get() {
+        observer(&MetricEvent::CacheLookup { component, hit });
+    }
+}
+
+// Called by `cache::Conditional<T>` once per request carrying `If-None-Match`,
+// with whether the ETag matched and a 304 was sent instead of a body.
+pub fn record_conditional<T: ?Sized>(not_modified: bool) {
+    let component = type_name::<T>();
+    {
+        let mut conditional = CONDITIONAL.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = conditional
+            .get_or_insert_with(HashMap::new)
+            .entry(component)
+            .or_default();
+        stats.requests += 1;
+        if not_modified {
+            stats.not_modified += 1;
+        }
+    }
+
+    if let Some(observer) = OBSERVER.get() {
+        observer(&MetricEvent::ConditionalResponse {
+            component,
+            not_modified,
+        });
+    }
+}
+
+pub fn cache_stats<T: ?Sized>() -> CacheStats {
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(type_name::<T>()).copied())
+        .unwrap_or_default()
+}
+
+pub fn conditional_stats<T: ?Sized>() -> ConditionalStats {
+    CONDITIONAL
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|conditional| conditional.get(type_name::<T>()).copied())
+        .unwrap_or_default()
+}
+
+pub fn record_exposure(experiment: &'static str, variant: &'static str) {
+    *EXPOSURES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .entry((experiment, variant))
+        .or_default() += 1;
+
+    if let Some(observer) = OBSERVER.get() {
+        observer(&MetricEvent::ExperimentExposure {
+            experiment,
+            variant,
+        });
+    }
+}
+
+pub fn exposures(experiment: &str, variant: &str) -> u64 {
+    EXPOSURES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|exposures| exposures.get(&(experiment, variant)).copied())
+        .unwrap_or(0)
+}
+
+pub fn response_size<T: ?Sized>() -> ResponseSize {
+    SIZES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|sizes| sizes.get(type_name::<T>()).copied())
+        .unwrap_or_default()
+}
+
+pub fn response_sizes() -> Vec<(&'static str, ResponseSize)> {
+    let mut sizes: Vec<_> = SIZES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .flatten()
+        .map(|(component, size)| (*component, *size))
+        .collect();
+    sizes.sort_by_key(|(component, _)| *component);
+    sizes
+}
+
+pub fn set_render_budget(budget: Option<Duration>) {
+    let nanos = budget.map_or(NO_BUDGET, |budget| {
+        u64::try_from(budget.as_nanos()).unwrap_or(NO_BUDGET - 1)
+    });
+    RENDER_BUDGET_NANOS.store(nanos, Ordering::Relaxed);
+}
+
+pub fn render_budget() -> Option<Duration> {
+    match RENDER_BUDGET_NANOS.load(Ordering::Relaxed) {
+        NO_BUDGET => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+pub fn render_time<T: ?Sized>() -> RenderTime {
+    RENDER_TIMES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|times| times.get(type_name::<T>()))
+        .map(|counters| counters.snapshot())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "tracing")]
+fn short_name(component: &str) -> &str {
+    let name = component.split('<').next().unwrap_or(component);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(feature = "tracing")]
+fn render_path() -> String {
+    RENDER_PATH.with(|path| {
+        path.borrow()
+            .iter()
+            .map(|component| short_name(component))
+            .collect::<Vec<_>>()
+            .join(" > ")
+    })
+}
+
+#[doc(hidden)]
+pub struct RenderTimer {
+    component: &'static str,
+    start: Instant,
+}
+
+impl RenderTimer {
+    pub fn start<T: ?Sized>() -> Self {
+        let component = type_name::<T>();
+        RENDER_PATH.with(|path| path.borrow_mut().push(component));
+        Self {
+            component,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for RenderTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        RenderCounters::of(self.component).record(elapsed);
+
+        let budget = render_budget().filter(|budget| elapsed > *budget);
+        #[cfg(feature = "tracing")]
+        if let Some(budget) = budget {
+            tracing::warn!(path = %render_path(), ?elapsed, ?budget, "render budget exceeded");
+        }
+        RENDER_PATH.with(|path| path.borrow_mut().pop());
+
+        if let (Some(budget), Some(observer)) = (budget, OBSERVER.get()) {
+            observer(&MetricEvent::RenderBudgetExceeded {
+                component: self.component,
+                elapsed,
+                budget,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Widget;
+
+    #[test]
+    fn records_sizes_per_component() {
+        record_response::<Widget>(100);
+        record_response::<Widget>(300);
+
+        let size = response_size::<Widget>();
+        assert_eq!(size.responses, 2);
+        assert_eq!(size.total_bytes, 400);
+        assert_eq!(size.max_bytes, 300);
+        assert_eq!(size.average_bytes(), 200);
+        assert!(
+            response_sizes()
+                .iter()
+                .any(|(component, _)| component.ends_with("Widget"))
+        );
+        assert_eq!(response_size::<str>(), ResponseSize::default());
+    }
+
+    #[test]
+    fn tracks_render_time_per_component() {
+        struct Slow;
+
+        {
+            let _outer = RenderTimer::start::<Widget>();
+            let _inner = RenderTimer::start::<Slow>();
+            std::thread::sleep(Duration::from_millis(2));
+            RENDER_PATH.with(|path| assert_eq!(path.borrow().len(), 2));
+        }
+        RENDER_PATH.with(|path| assert!(path.borrow().is_empty()));
+
+        let slow = render_time::<Slow>();
+        assert_eq!(slow.renders, 1);
+        assert!(slow.max >= Duration::from_millis(2));
+        assert_eq!(slow.average(), slow.total);
+        assert!(render_time::<Widget>().total >= slow.total);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn shortens_render_path_names() {
+        assert_eq!(short_name("app::views::Row<app::Item>"), "Row");
+    }
+
+    #[test]
+    fn stores_render_budget() {
+        set_render_budget(Some(Duration::from_millis(5)));
+        assert_eq!(render_budget(), Some(Duration::from_millis(5)));
+        set_render_budget(None);
+        assert_eq!(render_budget(), None);
+    }
+
+    #[test]
+    fn records_cache_and_conditional_rates() {
+        struct Cached;
+
+        record_cache_lookup::<Cached>(false);
+        record_cache_lookup::<Cached>(true);
+        record_cache_lookup::<Cached>(true);
+        record_conditional::<Cached>(true);
+        record_conditional::<Cached>(false);
+
+        let cache = cache_stats::<Cached>();
+        assert_eq!((cache.hits, cache.misses), (2, 1));
+        assert!((cache.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        let conditional = conditional_stats::<Cached>();
+        assert_eq!(conditional.requests, 2);
+        assert_eq!(conditional.not_modified_rate(), 0.5);
+        assert_eq!(cache_stats::<Widget>().hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn records_experiment_exposures() {
+        record_exposure("metrics-test", "b");
+        record_exposure("metrics-test", "b");
+
+        assert_eq!(exposures("metrics-test", "b"), 2);
+        assert_eq!(exposures("metrics-test", "a"), 0);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::Render;
+use axum::{
+    extract::FromRequestParts,
+    http::{StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use std::convert::Infallible;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Html,
+    Json,
+}
+
+impl ResponseFormat {
+    pub fn from_accept(accept: &str) -> Self {
+        let mut html: Option<f32> = None;
+        let mut json: Option<f32> = None;
+
+        for entry in accept.split(',') {
+            let mut params = entry.split(';');
+            let media = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+
+            let slot = match media.as_str() {
+                "text/html" | "application/xhtml+xml" => &mut html,
+                "application/json" => &mut json,
+                media if media.ends_with("+json") => &mut json,
+                _ => continue,
+            };
+            *slot = Some(slot.map_or(quality, |q| q.max(quality)));
+        }
+
+        match (html, json) {
+            (None, Some(json)) if json > 0.0 => ResponseFormat::Json,
+            (Some(html), Some(json)) if json > html => ResponseFormat::Json,
+            _ => ResponseFormat::Html,
+        }
+    }
+
+    pub fn respond<T>(self, data: T) -> HtmlOrJson<T> {
+        HtmlOrJson { format: self, data }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ResponseFormat {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if parts
+            .headers
+            .get("Datastar-Request")
+            .is_some_and(|v| v == "true")
+        {
+            return Ok(ResponseFormat::Html);
+        }
+
+        Ok(parts
+            .headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .map_or(ResponseFormat::Html, ResponseFormat::from_accept))
+    }
+}
+
+pub struct HtmlOrJson<T> {
+    pub format: ResponseFormat,
+    pub data: T,
+}
+
+impl<T> HtmlOrJson<T> {
+    pub fn new(format: ResponseFormat, data: T) -> Self {
+        Self { format, data }
+    }
+}
+
+impl<T: Render + Serialize> IntoResponse for HtmlOrJson<T> {
+    fn into_response(self) -> Response {
+        let (content_type, body) = match self.format {
+            ResponseFormat::Html => match self.data.try_render_to_string() {
+                Ok(body) => ("text/html; charset=utf-8", body),
+                Err(error) => return error.into_response(),
+            },
+            ResponseFormat::Json => match serde_json::to_string(&self.data) {
+                Ok(body) => ("application/json", body),
+                Err(err) => {
+                    return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+                }
+            },
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_response::<T>(body.len());
+
+        (StatusCode::OK, [(header::CONTENT_TYPE, content_type)], body).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_header_preference() {
+        assert_eq!(
+            ResponseFormat::from_accept("application/json"),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept("application/problem+json"),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept("text/html,application/xhtml+xml,*/*;q=0.8"),
+            ResponseFormat::Html
+        );
+        assert_eq!(
+            ResponseFormat::from_accept("text/html;q=0.5, application/json"),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept("application/json;q=0"),
+            ResponseFormat::Html
+        );
+        assert_eq!(ResponseFormat::from_accept("*/*"), ResponseFormat::Html);
+    }
+}
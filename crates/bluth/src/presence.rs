@@ -0,0 +1,178 @@
+use axum::http::Extensions;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::datastar::{PatchElements, PatchMode, PatchSignals};
+use crate::hub::{Hub, Subscription};
+use crate::id::UniqueId;
+use crate::{Element, Signal};
+
+#[derive(Signal)]
+pub enum PresenceSignals {
+    OnlineCount(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresenceUser(pub String);
+
+#[derive(Debug, Clone)]
+pub struct PresenceEntry {
+    pub id: UniqueId,
+    pub user_id: Option<String>,
+    pub joined_at: SystemTime,
+}
+
+#[derive(Element)]
+#[element("li")]
+#[attr(id = "{id}")]
+pub struct PresenceMember {
+    id: UniqueId,
+
+    #[element]
+    name: String,
+}
+
+#[derive(Default)]
+struct PresenceState {
+    members: HashMap<String, Vec<PresenceEntry>>,
+    last_seen: HashMap<String, SystemTime>,
+}
+
+#[derive(Clone)]
+pub struct Presence {
+    hub: Hub,
+    list_id: &'static str,
+    state: Arc<Mutex<PresenceState>>,
+}
+
+impl Presence {
+    pub fn new(hub: Hub) -> Self {
+        Self {
+            hub,
+            list_id: "presence",
+            state: Arc::default(),
+        }
+    }
+
+    pub fn list_id(mut self, id: &'static str) -> Self {
+        self.list_id = id;
+        self
+    }
+
+    pub fn join(&self, topic: impl Into<String>, user_id: Option<String>) -> PresenceGuard {
+        let topic = topic.into();
+        let entry = PresenceEntry {
+            id: UniqueId::with_prefix("presence"),
+            user_id,
+            joined_at: SystemTime::now(),
+        };
+        let member = PresenceMember {
+            id: entry.id.clone(),
+            name: entry.user_id.clone().unwrap_or_else(|| "Guest".to_string()),
+        };
+        let guard = PresenceGuard {
+            presence: self.clone(),
+            topic: topic.clone(),
+            id: entry.id.clone(),
+        };
+
+        let count = {
+            let mut state = self.lock();
+            let members = state.members.entry(topic.clone()).or_default();
+            members.push(entry);
+            members.len()
+        };
+
+        self.hub.patch_to(
+            &topic,
+            PatchElements::new(vec![member])
+                .selector(format!("#{}", self.list_id))
+                .mode(PatchMode::Append),
+        );
+        self.broadcast_count(&topic, count);
+
+        guard
+    }
+
+    pub fn join_from(&self, topic: impl Into<String>, extensions: &Extensions) -> PresenceGuard {
+        let user_id = extensions.get::<PresenceUser>().map(|user| user.0.clone());
+        self.join(topic, user_id)
+    }
+
+    pub fn subscribe(&self, topic: impl Into<String>, user_id: Option<String>) -> Subscription {
+        let topic = topic.into();
+        let subscription = self.hub.subscribe_to(topic.clone());
+        subscription.with_presence(self.join(topic, user_id))
+    }
+
+    pub fn count(&self, topic: &str) -> usize {
+        self.lock().members.get(topic).map_or(0, Vec::len)
+    }
+
+    pub fn members(&self, topic: &str) -> Vec<PresenceEntry> {
+        self.lock().members.get(topic).cloned().unwrap_or_default()
+    }
+
+    pub fn last_seen(&self, user_id: &str) -> Option<SystemTime> {
+        self.lock().last_seen.get(user_id).copied()
+    }
+
+    fn leave(&self, topic: &str, id: &UniqueId) {
+        let count = {
+            let mut state = self.lock();
+            let Some(members) = state.members.get_mut(topic) else {
+                return;
+            };
+            let Some(index) = members.iter().position(|entry| &entry.id == id) else {
+                return;
+            };
+            let entry = members.remove(index);
+            let count = members.len();
+            if count == 0 {
+                state.members.remove(topic);
+            }
+            if let Some(user_id) = entry.user_id {
+                state.last_seen.insert(user_id, SystemTime::now());
+            }
+            count
+        };
+
+        self.hub.patch_to(
+            topic,
+            PatchElements::<&str>::new(vec![])
+                .selector(id.selector())
+                .mode(PatchMode::Remove),
+        );
+        self.broadcast_count(topic, count);
+    }
+
+    fn broadcast_count(&self, topic: &str, count: usize) {
+        self.hub.patch_signals_to(
+            topic,
+            PatchSignals::new(vec![PresenceSignals::OnlineCount(count)]),
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PresenceState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub struct PresenceGuard {
+    presence: Presence,
+    topic: String,
+    id: UniqueId,
+}
+
+impl PresenceGuard {
+    pub fn id(&self) -> &UniqueId {
+        &self.id
+    }
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        self.presence.leave(&self.topic, &self.id);
+    }
+}
@@ -0,0 +1,597 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::io;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    Fmt,
+    TooLarge {
+        max_bytes: usize,
+    },
+    TooDeep {
+        max_depth: usize,
+    },
+    InvalidTag(String),
+    SignalNameCollision(crate::signal::SignalNameCollision),
+    #[cfg(feature = "csp-strict")]
+    Csp(crate::csp::CspViolation),
+    #[cfg(feature = "render-hooks")]
+    Rejected {
+        tag: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Fmt => write!(f, "formatter error"),
+            RenderError::TooLarge { max_bytes } => {
+                write!(f, "rendered output exceeded {} bytes", max_bytes)
+            }
+            RenderError::TooDeep { max_depth } => {
+                write!(f, "element nesting exceeded a depth of {}", max_depth)
+            }
+            RenderError::InvalidTag(tag) => write!(f, "invalid tag name: {:?}", tag),
+            RenderError::SignalNameCollision(collision) => write!(f, "{}", collision),
+            #[cfg(feature = "csp-strict")]
+            RenderError::Csp(violation) => write!(f, "CSP violation: {}", violation),
+            #[cfg(feature = "render-hooks")]
+            RenderError::Rejected { tag, reason } => {
+                write!(f, "<{}> rejected by render hook: {}", tag, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for RenderError {
+    fn into_response(self) -> axum::response::Response {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %self, "render failed");
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to render response",
+        )
+            .into_response()
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<RenderError>> = const { RefCell::new(None) };
+    static DEPTH: RefCell<Option<(usize, usize)>> = const { RefCell::new(None) };
+    static BUDGET: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+pub fn report(error: RenderError) -> fmt::Error {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+    fmt::Error
+}
+
+fn take_error() -> RenderError {
+    LAST_ERROR
+        .with(|last| last.borrow_mut().take())
+        .unwrap_or(RenderError::Fmt)
+}
+
+pub(crate) fn try_display(value: &dyn fmt::Display) -> Result<String, RenderError> {
+    use fmt::Write as _;
+
+    let mut output = Buffer::default();
+    LAST_ERROR.with(|last| last.borrow_mut().take());
+    match write!(output, "{}", value) {
+        Ok(()) => Ok(output.into_string()),
+        Err(fmt::Error) => Err(take_error()),
+    }
+}
+
+pub trait Render {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result;
+
+    fn size_hint(&self) -> usize {
+        0
+    }
+
+    fn render_to_string(&self) -> String {
+        let mut output = String::with_capacity(self.size_hint());
+        self.render(&mut output)
+            .expect("a Render implementation returned an error unexpectedly");
+        output
+    }
+
+    fn try_render_to_string(&self) -> Result<String, RenderError> {
+        let mut output = Buffer::with_capacity(self.size_hint());
+        LAST_ERROR.with(|last| last.borrow_mut().take());
+        match self.render(&mut output) {
+            Ok(()) => Ok(output.into_string()),
+            Err(fmt::Error) => Err(take_error()),
+        }
+    }
+
+    fn render_to_writer(&self, writer: &mut impl io::Write) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        let mut adapter = IoAdapter {
+            writer,
+            error: None,
+        };
+        match self.render(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => Err(adapter
+                .error
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+}
+
+struct IoAdapter<'a, W: io::Write> {
+    writer: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+impl Render for str {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", crate::html::escape_html(self))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Render for String {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_str().render(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Render for std::borrow::Cow<'_, str> {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).render(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+macro_rules! impl_render_display {
+    ($($ty:ty),*) => {
+        $(
+            impl Render for $ty {
+                fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                    write!(f, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_render_display!(
+    bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl Render for char {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.encode_utf8(&mut [0; 4]).render(f)
+    }
+}
+
+impl<T: Render> Render for Option<T> {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Some(value) => value.render(f),
+            None => Ok(()),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, Render::size_hint)
+    }
+}
+
+impl Render for () {
+    fn render(&self, _f: &mut dyn fmt::Write) -> fmt::Result {
+        Ok(())
+    }
+}
+
+macro_rules! impl_render_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Render),+> Render for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                let ($($name,)+) = self;
+                $($name.render(f)?;)+
+                Ok(())
+            }
+
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.size_hint())+
+            }
+        }
+    };
+}
+
+impl_render_tuple!(A);
+impl_render_tuple!(A, B);
+impl_render_tuple!(A, B, C);
+impl_render_tuple!(A, B, C, D);
+impl_render_tuple!(A, B, C, D, E);
+impl_render_tuple!(A, B, C, D, E, F);
+impl_render_tuple!(A, B, C, D, E, F, G);
+impl_render_tuple!(A, B, C, D, E, F, G, H);
+impl_render_tuple!(A, B, C, D, E, F, G, H, I);
+impl_render_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_render_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_render_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+pub struct DisplayWith<'a, T: ?Sized, F>(pub &'a T, pub F);
+
+impl<T: ?Sized, F> fmt::Display for DisplayWith<'_, T, F>
+where
+    F: Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.1)(self.0, f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fragment<T>(pub T);
+
+impl<T: Render> Render for Fragment<T> {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.0.render(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Render> fmt::Display for Fragment<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+impl<T: Render> crate::html::RootAttrs for Fragment<T> {}
+
+pub type AnyElement = Box<dyn Render + Send + Sync>;
+
+pub fn boxed<T: Render + Send + Sync + 'static>(element: T) -> AnyElement {
+    Box::new(element)
+}
+
+impl<T: Render + ?Sized> Render for Box<T> {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).render(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        (**self).size_hint()
+    }
+}
+
+macro_rules! display_dyn_render {
+    ($($ty:ty),*) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.render(f)
+                }
+            }
+        )*
+    };
+}
+
+display_dyn_render!(
+    dyn Render + '_,
+    dyn Render + Send + '_,
+    dyn Render + Send + Sync + '_
+);
+
+enum Child {
+    Static(&'static str),
+    Owned(String),
+    Element(AnyElement),
+}
+
+#[derive(Default)]
+pub struct Children(Vec<Child>);
+
+impl Children {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    pub fn push_static(&mut self, html: &'static str) -> &mut Self {
+        self.0.push(Child::Static(html));
+        self
+    }
+
+    pub fn push_text(&mut self, text: impl fmt::Display) -> &mut Self {
+        self.0
+            .push(Child::Owned(crate::html::escape_html(text).to_string()));
+        self
+    }
+
+    pub fn push_raw(&mut self, html: impl Into<String>) -> &mut Self {
+        self.0.push(Child::Owned(html.into()));
+        self
+    }
+
+    pub fn push<T: Render + Send + Sync + 'static>(&mut self, element: T) -> &mut Self {
+        self.0.push(Child::Element(Box::new(element)));
+        self
+    }
+
+    pub fn extend<T, I>(&mut self, elements: I) -> &mut Self
+    where
+        T: Render + Send + Sync + 'static,
+        I: IntoIterator<Item = T>,
+    {
+        for element in elements {
+            self.push(element);
+        }
+        self
+    }
+
+    pub fn append(&mut self, other: Children) -> &mut Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Render for Children {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        for child in &self.0 {
+            match child {
+                Child::Static(html) => f.write_str(html)?,
+                Child::Owned(html) => f.write_str(html)?,
+                Child::Element(element) => element.render(f)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0
+            .iter()
+            .map(|child| match child {
+                Child::Static(html) => html.len(),
+                Child::Owned(html) => html.len(),
+                Child::Element(element) => element.size_hint(),
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for Children {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+impl crate::html::RootAttrs for Children {}
+
+pub struct Markup<F> {
+    size_hint: usize,
+    render: F,
+}
+
+impl<F> Markup<F>
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+{
+    pub fn new(size_hint: usize, render: F) -> Self {
+        Self { size_hint, render }
+    }
+}
+
+impl<F> Render for Markup<F>
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+{
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        (self.render)(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
+
+impl<F> fmt::Display for Markup<F>
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+impl<F> crate::html::RootAttrs for Markup<F> where F: Fn(&mut dyn fmt::Write) -> fmt::Result {}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).render(f)
+    }
+
+    fn size_hint(&self) -> usize {
+        (**self).size_hint()
+    }
+}
+
+#[doc(hidden)]
+pub struct DepthGuard(bool);
+
+impl DepthGuard {
+    pub fn enter() -> Result<Self, fmt::Error> {
+        DEPTH.with(|depth| match &mut *depth.borrow_mut() {
+            Some((current, max_depth)) if *current >= *max_depth => {
+                Err(report(RenderError::TooDeep {
+                    max_depth: *max_depth,
+                }))
+            }
+            Some((current, _)) => {
+                *current += 1;
+                Ok(DepthGuard(true))
+            }
+            None => Ok(DepthGuard(false)),
+        })
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        if self.0 {
+            DEPTH.with(|depth| {
+                if let Some((current, _)) = &mut *depth.borrow_mut() {
+                    *current -= 1;
+                }
+            });
+        }
+    }
+}
+
+fn charge(bytes: usize) -> fmt::Result {
+    BUDGET.with(|budget| match budget.get() {
+        Some((remaining, max_bytes)) if bytes > remaining => {
+            Err(report(RenderError::TooLarge { max_bytes }))
+        }
+        Some((remaining, max_bytes)) => {
+            budget.set(Some((remaining - bytes, max_bytes)));
+            Ok(())
+        }
+        None => Ok(()),
+    })
+}
+
+fn refund(bytes: usize) {
+    BUDGET.with(|budget| {
+        if let Some((remaining, max_bytes)) = budget.get() {
+            budget.set(Some((remaining + bytes, max_bytes)));
+        }
+    });
+}
+
+/// A scratch `String` for output that is rendered before it is written on,
+/// such as an error boundary's child. Its bytes count against the budget of
+/// an enclosing [`render_with_limits`] until the buffer is taken or dropped.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Buffer {
+    output: String,
+    charged: usize,
+}
+
+impl Buffer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            output: String::with_capacity(capacity),
+            charged: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.output.is_empty()
+    }
+
+    pub fn into_string(mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+}
+
+impl fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        charge(s.len())?;
+        self.charged += s.len();
+        self.output.push_str(s);
+        Ok(())
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        refund(self.charged);
+    }
+}
+
+struct LimitedWriter {
+    output: String,
+}
+
+impl fmt::Write for LimitedWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        charge(s.len())?;
+        self.output.push_str(s);
+        Ok(())
+    }
+}
+
+struct RestoreLimits {
+    depth: Option<(usize, usize)>,
+    budget: Option<(usize, usize)>,
+}
+
+impl Drop for RestoreLimits {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| *depth.borrow_mut() = self.depth.take());
+        BUDGET.with(|budget| budget.set(self.budget));
+    }
+}
+
+pub fn render_with_limits<R: Render + ?Sized>(
+    element: &R,
+    max_bytes: usize,
+    max_depth: usize,
+) -> Result<String, RenderError> {
+    let _restore = RestoreLimits {
+        depth: DEPTH.with(|depth| depth.borrow_mut().replace((0, max_depth))),
+        budget: BUDGET.with(|budget| budget.replace(Some((max_bytes, max_bytes)))),
+    };
+    let mut writer = LimitedWriter {
+        output: String::with_capacity(element.size_hint().min(max_bytes)),
+    };
+    LAST_ERROR.with(|last| last.borrow_mut().take());
+    match element.render(&mut writer) {
+        Ok(()) => Ok(writer.output),
+        Err(fmt::Error) => Err(take_error()),
+    }
+}
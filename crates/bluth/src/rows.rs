@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+
+use crate::Element;
+
+#[derive(Element)]
+#[element("div")]
+#[attr(class = "bluth-row-error", role = "alert")]
+pub struct RowError {
+    #[element]
+    pub message: String,
+}
+
+impl RowError {
+    pub fn new(error: impl Display) -> Self {
+        Self {
+            message: error.to_string(),
+        }
+    }
+}
+
+pub struct Rows<I, F> {
+    rows: RefCell<Option<I>>,
+    on_error: F,
+}
+
+impl<I, R, E> Rows<I, fn(E) -> RowError>
+where
+    I: Iterator<Item = Result<R, E>>,
+    E: Display,
+{
+    pub fn new(rows: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            rows: RefCell::new(Some(rows.into_iter())),
+            on_error: |error| RowError::new(error),
+        }
+    }
+}
+
+impl<I, F> Rows<I, F> {
+    pub fn on_error<G>(self, on_error: G) -> Rows<I, G> {
+        Rows {
+            rows: self.rows,
+            on_error,
+        }
+    }
+}
+
+impl<I, R, E, F, D> Display for Rows<I, F>
+where
+    I: Iterator<Item = Result<R, E>>,
+    R: Display,
+    F: Fn(E) -> D,
+    D: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(rows) = self.rows.borrow_mut().take() else {
+            return Ok(());
+        };
+        for row in rows {
+            match row {
+                Ok(row) => write!(f, "{row}")?,
+                Err(error) => return write!(f, "{}", (self.on_error)(error)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "stream")]
+pub struct RowStream<S, F> {
+    head: String,
+    tail: String,
+    rows: S,
+    on_error: F,
+}
+
+#[cfg(feature = "stream")]
+impl<S, R, E> RowStream<S, fn(E) -> RowError>
+where
+    S: futures_util::Stream<Item = Result<R, E>>,
+    E: Display,
+{
+    pub fn new(rows: S) -> Self {
+        Self {
+            head: String::new(),
+            tail: String::new(),
+            rows,
+            on_error: |error| RowError::new(error),
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<S, F> RowStream<S, F> {
+    pub fn head(mut self, head: impl Display) -> Self {
+        self.head = head.to_string();
+        self
+    }
+
+    pub fn tail(mut self, tail: impl Display) -> Self {
+        self.tail = tail.to_string();
+        self
+    }
+
+    pub fn on_error<G>(self, on_error: G) -> RowStream<S, G> {
+        RowStream {
+            head: self.head,
+            tail: self.tail,
+            rows: self.rows,
+            on_error,
+        }
+    }
+
+    pub fn into_stream<R, E, D>(self) -> impl futures_util::Stream<Item = String>
+    where
+        S: futures_util::Stream<Item = Result<R, E>>,
+        R: Display,
+        F: Fn(E) -> D,
+        D: Display,
+    {
+        use futures_util::StreamExt;
+
+        let Self {
+            head,
+            tail,
+            rows,
+            on_error,
+        } = self;
+        let rows = rows
+            .scan(false, move |failed, row| {
+                let chunk = match row {
+                    _ if *failed => None,
+                    Ok(row) => Some(row.to_string()),
+                    Err(error) => {
+                        *failed = true;
+                        Some(on_error(error).to_string())
+                    }
+                };
+                async move { chunk }
+            })
+            .filter(|chunk| std::future::ready(!chunk.is_empty()));
+
+        futures_util::stream::once(std::future::ready(head))
+            .filter(|chunk| std::future::ready(!chunk.is_empty()))
+            .chain(rows)
+            .chain(
+                futures_util::stream::once(std::future::ready(tail))
+                    .filter(|chunk| std::future::ready(!chunk.is_empty())),
+            )
+    }
+}
+
+#[cfg(all(feature = "stream", feature = "axum"))]
+impl<S, R, E, F, D> axum::response::IntoResponse for RowStream<S, F>
+where
+    S: futures_util::Stream<Item = Result<R, E>> + Send + 'static,
+    R: Display + 'static,
+    E: 'static,
+    F: Fn(E) -> D + Send + 'static,
+    D: Display + 'static,
+{
+    fn into_response(self) -> axum::response::Response {
+        use futures_util::StreamExt;
+
+        let body = axum::body::Body::from_stream(
+            self.into_stream().map(Ok::<_, std::convert::Infallible>),
+        );
+        (
+            [(axum::http::header::CONTENT_TYPE, "text/html; charset=utf-8")],
+            body,
+        )
+            .into_response()
+    }
+}
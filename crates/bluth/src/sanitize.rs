@@ -0,0 +1,72 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Clean(pub String);
+
+impl Clean {
+    pub fn new(html: impl Into<String>) -> Self {
+        Self(html.into())
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Clean {
+    fn from(html: String) -> Self {
+        Self(html)
+    }
+}
+
+impl From<&str> for Clean {
+    fn from(html: &str) -> Self {
+        Self(html.to_string())
+    }
+}
+
+impl fmt::Display for Clean {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&ammonia::clean(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Element;
+
+    #[test]
+    fn strips_disallowed_markup() {
+        assert_eq!(
+            Clean::new("<b>bold</b><script>alert(1)</script>").to_string(),
+            "<b>bold</b>"
+        );
+        assert_eq!(
+            Clean::from(r#"<a href="/x" onclick="steal()">link</a>"#).to_string(),
+            r#"<a href="/x" rel="noopener noreferrer">link</a>"#
+        );
+    }
+
+    #[test]
+    fn embeds_as_child_field() {
+        #[derive(Element)]
+        #[element("article")]
+        struct Post {
+            #[element("h1")]
+            title: String,
+
+            #[element]
+            body: Clean,
+        }
+
+        let post = Post {
+            title: "<Hi>".into(),
+            body: Clean::new("<p>Hello <img src=x onerror=alert(1)></p>"),
+        };
+        assert_eq!(
+            post.to_string(),
+            r#"<article><h1>&lt;Hi&gt;</h1><p>Hello <img src="x"></p></article>"#
+        );
+    }
+}
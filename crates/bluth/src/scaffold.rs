@@ -0,0 +1,62 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::Element;
+use crate::datastar::{PatchElements, PatchMode};
+use crate::signal::SignalSelector;
+use crate::url::UrlPath;
+
+#[derive(Element)]
+#[element("input")]
+#[attr(
+    "type" = "search",
+    "data-bind" = Self::bind(),
+    "data-on:input__debounce.300ms" = "@get('{path}')",
+    "aria-controls" = "{results_id}"
+)]
+pub struct LiveSearch<S: SignalSelector> {
+    path: String,
+    results_id: String,
+    selector: PhantomData<S>,
+}
+
+pub fn live_search<S: SignalSelector>(
+    _term: S,
+    url: &dyn UrlPath,
+    results_id: impl Into<String>,
+) -> LiveSearch<S> {
+    LiveSearch {
+        path: url.path(),
+        results_id: results_id.into(),
+        selector: PhantomData,
+    }
+}
+
+impl<S: SignalSelector> LiveSearch<S> {
+    fn bind() -> &'static str {
+        S::NAME
+    }
+
+    pub fn results_id(&self) -> &str {
+        &self.results_id
+    }
+
+    pub fn container(&self) -> SearchResults {
+        SearchResults {
+            id: self.results_id.clone(),
+        }
+    }
+
+    pub fn results<T: Display>(&self, results: Vec<T>) -> PatchElements<T> {
+        PatchElements::new(results)
+            .selector(format!("#{}", self.results_id))
+            .mode(PatchMode::Inner)
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr(id = "{id}", "aria-live" = "polite")]
+pub struct SearchResults {
+    id: String,
+}
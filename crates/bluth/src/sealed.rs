@@ -0,0 +1,216 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use crate::sign::{decode, encode, sign, verify};
+use crate::signal::{SignalEnum, SignalSelector};
+
+static SEALER: OnceLock<Sealer> = OnceLock::new();
+
+const NONCE_BYTES: usize = 24;
+
+#[derive(Debug)]
+pub enum SealError {
+    NotInstalled,
+    Serialize(serde_json::Error),
+    Encrypt,
+    Invalid,
+    AlreadyInstalled,
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SealError::NotInstalled => write!(f, "no Sealer installed"),
+            SealError::Serialize(error) => write!(f, "sealed value failed to serialize: {}", error),
+            SealError::Encrypt => write!(f, "sealed value failed to encrypt"),
+            SealError::Invalid => write!(f, "sealed value is invalid or was tampered with"),
+            SealError::AlreadyInstalled => write!(f, "signal sealer already installed"),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+#[derive(Clone)]
+pub struct Sealer {
+    secret: Vec<u8>,
+    encrypt: bool,
+}
+
+impl std::fmt::Debug for Sealer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sealer")
+            .field("encrypt", &self.encrypt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Sealer {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            encrypt: false,
+        }
+    }
+
+    pub fn encrypted(mut self) -> Self {
+        self.encrypt = true;
+        self
+    }
+
+    pub fn install(self) -> Result<(), SealError> {
+        SEALER.set(self).map_err(|_| SealError::AlreadyInstalled)
+    }
+
+    fn key(&self, purpose: &str) -> Vec<u8> {
+        sign(&self.secret, purpose.as_bytes())
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new_from_slice(&self.key("enc")).expect("derived key is 32 bytes")
+    }
+
+    pub fn seal<T: serde::Serialize>(&self, name: &str, value: &T) -> Result<String, SealError> {
+        let json = serde_json::to_vec(value).map_err(SealError::Serialize)?;
+        if !self.encrypt {
+            let payload = encode(&json);
+            let mac = sign(&self.key("mac"), format!("{}:{}", name, payload).as_bytes());
+            return Ok(format!("{}.{}", payload, encode(mac)));
+        }
+
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &json,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| SealError::Encrypt)?;
+        let mut token = nonce.to_vec();
+        token.extend_from_slice(&ciphertext);
+        Ok(encode(token))
+    }
+
+    pub fn open<T: for<'de> serde::Deserialize<'de>>(&self, name: &str, token: &str) -> Option<T> {
+        let json = if self.encrypt {
+            let bytes = decode(token)?;
+            if bytes.len() < NONCE_BYTES {
+                return None;
+            }
+            let (nonce, ciphertext) = bytes.split_at(NONCE_BYTES);
+            self.cipher()
+                .decrypt(
+                    XNonce::from_slice(nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: name.as_bytes(),
+                    },
+                )
+                .ok()?
+        } else {
+            let (payload, mac) = token.split_once('.')?;
+            let message = format!("{}:{}", name, payload);
+            if !verify(&self.key("mac"), message.as_bytes(), &decode(mac)?) {
+                return None;
+            }
+            decode(payload)?
+        };
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+pub fn installed() -> Option<&'static Sealer> {
+    SEALER.get()
+}
+
+fn sealer() -> Result<&'static Sealer, SealError> {
+    installed().ok_or(SealError::NotInstalled)
+}
+
+pub struct Sealed<S: SignalSelector>(pub S::Value);
+
+impl<S: SignalSelector> Sealed<S> {
+    pub fn new(value: S::Value) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> S::Value {
+        self.0
+    }
+
+    pub fn open(token: &str) -> Result<Self, SealError> {
+        sealer()?
+            .open(S::NAME, token)
+            .map(Self)
+            .ok_or(SealError::Invalid)
+    }
+}
+
+impl<S: SignalSelector> Sealed<S>
+where
+    S::Value: serde::Serialize,
+{
+    pub fn token(&self) -> Result<String, SealError> {
+        sealer()?.seal(S::NAME, &self.0)
+    }
+
+    pub fn to_signal(&self) -> Result<SealedSignal<S>, SealError> {
+        Ok(SealedSignal {
+            token: self.token()?,
+            selector: PhantomData,
+        })
+    }
+}
+
+impl<S: SignalSelector> std::ops::Deref for Sealed<S> {
+    type Target = S::Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: SignalSelector> std::fmt::Debug for Sealed<S>
+where
+    S::Value: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Sealed").field(&self.0).finish()
+    }
+}
+
+pub struct SealedSignal<S> {
+    token: String,
+    selector: PhantomData<S>,
+}
+
+impl<S> SealedSignal<S> {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl<S: SignalSelector> serde::Serialize for SealedSignal<S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(S::NAME, &self.token)?;
+        map.end()
+    }
+}
+
+impl<S: SignalSelector> SignalEnum for SealedSignal<S> {
+    fn signal_name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn to_json_value(&self) -> serde_json::Value {
+        serde_json::Value::String(self.token.clone())
+    }
+}
@@ -0,0 +1,384 @@
+#[cfg(feature = "axum")]
+use axum::http::{StatusCode, header};
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock};
+use strum::AsRefStr;
+
+use crate::html::escape_attr_str;
+use crate::url::{UrlNode, UrlPath};
+use crate::{Element, Render};
+
+static BASE_URL: OnceLock<String> = OnceLock::new();
+
+pub fn set_base_url(base_url: impl Into<String>) -> Result<(), String> {
+    BASE_URL.set(base_url.into().trim_end_matches('/').to_string())
+}
+
+pub fn base_url() -> &'static str {
+    BASE_URL.get().map(String::as_str).unwrap_or("")
+}
+
+pub fn absolute_url(url: &dyn UrlPath) -> String {
+    format!("{}{}", base_url(), url.path())
+}
+
+#[derive(Element)]
+#[element("link")]
+#[attr(rel = "canonical", href = absolute_url(&self.0))]
+pub struct Canonical<U: UrlPath>(pub U);
+
+#[derive(Element)]
+#[element("link")]
+#[attr(rel = "alternate", href = absolute_url(&self.url))]
+pub struct Alternate<U: UrlPath> {
+    #[attr]
+    pub hreflang: &'static str,
+
+    pub url: U,
+}
+
+impl<U: UrlPath> Alternate<U> {
+    pub fn new(hreflang: &'static str, url: U) -> Self {
+        Self { hreflang, url }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+pub trait SitemapUrl: UrlNode {
+    const PRIORITY: Option<f32>;
+    const CHANGEFREQ: Option<ChangeFreq>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub path: String,
+    pub priority: Option<f32>,
+    pub changefreq: Option<ChangeFreq>,
+}
+
+impl SitemapEntry {
+    pub fn for_url<U: SitemapUrl>(url: &U) -> Self {
+        Self {
+            path: url.path(),
+            priority: U::PRIORITY,
+            changefreq: U::CHANGEFREQ,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SitemapRegistry {
+    entries: Mutex<Vec<SitemapEntry>>,
+}
+
+impl SitemapRegistry {
+    pub const fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn register<U: SitemapUrl>(&self, url: &U) -> &Self {
+        let entry = SitemapEntry::for_url(url);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if !entries.iter().any(|e| e.path == entry.path) {
+            entries.push(entry);
+        }
+        self
+    }
+
+    pub fn entries(&self) -> Vec<SitemapEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn sitemap_xml(&self, base_url: impl Into<String>) -> SitemapXml {
+        SitemapXml {
+            base_url: base_url.into(),
+            entries: self.entries(),
+        }
+    }
+}
+
+static REGISTRY: SitemapRegistry = SitemapRegistry::new();
+
+pub fn register<U: SitemapUrl>(url: &U) {
+    REGISTRY.register(url);
+}
+
+pub fn sitemap_xml(base_url: impl Into<String>) -> SitemapXml {
+    REGISTRY.sitemap_xml(base_url)
+}
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+const URLSET_OPEN: &str = r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#;
+
+pub struct SitemapXml {
+    pub base_url: String,
+    pub entries: Vec<SitemapEntry>,
+}
+
+impl Render for SitemapXml {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let base = self.base_url.trim_end_matches('/');
+
+        f.write_str(XML_DECLARATION)?;
+        f.write_str(URLSET_OPEN)?;
+        for entry in &self.entries {
+            write!(
+                f,
+                "<url><loc>{}{}</loc>",
+                escape_attr_str(base),
+                escape_attr_str(&entry.path)
+            )?;
+            if let Some(changefreq) = entry.changefreq {
+                write!(f, "<changefreq>{}</changefreq>", changefreq.as_ref())?;
+            }
+            if let Some(priority) = entry.priority {
+                write!(f, "<priority>{:.1}</priority>", priority.clamp(0.0, 1.0))?;
+            }
+            write!(f, "</url>")?;
+        }
+        write!(f, "</urlset>")
+    }
+
+    fn size_hint(&self) -> usize {
+        let base = self.base_url.trim_end_matches('/').len();
+        let entries: usize = self
+            .entries
+            .iter()
+            .map(|entry| "<url><loc></loc></url>".len() + base + entry.path.len())
+            .sum();
+        XML_DECLARATION.len() + URLSET_OPEN.len() + "</urlset>".len() + entries
+    }
+}
+
+impl Display for SitemapXml {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for SitemapXml {
+    fn into_response(self) -> Response {
+        self.try_render_to_string()
+            .map(|body| {
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/xml")],
+                    body,
+                )
+            })
+            .into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RobotsRule {
+    UserAgent(String),
+    Allow(String),
+    Disallow(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Robots {
+    rules: Vec<RobotsRule>,
+    sitemaps: Vec<String>,
+}
+
+impl Robots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn user_agent(mut self, agent: impl Into<String>) -> Self {
+        self.rules.push(RobotsRule::UserAgent(agent.into()));
+        self
+    }
+
+    pub fn allow(mut self, path: impl Into<String>) -> Self {
+        self.rules.push(RobotsRule::Allow(path.into()));
+        self
+    }
+
+    pub fn disallow(mut self, path: impl Into<String>) -> Self {
+        self.rules.push(RobotsRule::Disallow(path.into()));
+        self
+    }
+
+    pub fn sitemap(mut self, url: impl Into<String>) -> Self {
+        self.sitemaps.push(url.into());
+        self
+    }
+}
+
+impl Render for Robots {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if !matches!(self.rules.first(), Some(RobotsRule::UserAgent(_))) {
+            writeln!(f, "User-agent: *")?;
+        }
+        for rule in &self.rules {
+            match rule {
+                RobotsRule::UserAgent(agent) => writeln!(f, "User-agent: {}", agent)?,
+                RobotsRule::Allow(path) => writeln!(f, "Allow: {}", path)?,
+                RobotsRule::Disallow(path) => writeln!(f, "Disallow: {}", path)?,
+            }
+        }
+        for sitemap in &self.sitemaps {
+            writeln!(f, "Sitemap: {}", sitemap)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        let rules: usize = self
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                RobotsRule::UserAgent(agent) => "User-agent: \n".len() + agent.len(),
+                RobotsRule::Allow(path) => "Allow: \n".len() + path.len(),
+                RobotsRule::Disallow(path) => "Disallow: \n".len() + path.len(),
+            })
+            .sum();
+        let sitemaps: usize = self
+            .sitemaps
+            .iter()
+            .map(|sitemap| "Sitemap: \n".len() + sitemap.len())
+            .sum();
+        rules + sitemaps
+    }
+}
+
+impl Display for Robots {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Render::render(self, f)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for Robots {
+    fn into_response(self) -> Response {
+        self.try_render_to_string()
+            .map(|body| (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain")], body))
+            .into_response()
+    }
+}
+
+#[derive(Element)]
+#[element("link")]
+#[attr(rel = "preload")]
+pub struct Preload {
+    #[attr]
+    pub href: String,
+
+    #[attr(name = "as")]
+    pub as_: &'static str,
+
+    #[attr(name = "type")]
+    pub type_: Option<&'static str>,
+
+    #[attr]
+    pub crossorigin: bool,
+}
+
+impl Preload {
+    pub fn new(href: impl Into<String>, as_: &'static str) -> Self {
+        Self {
+            href: href.into(),
+            as_,
+            type_: None,
+            crossorigin: false,
+        }
+    }
+
+    pub fn font(href: impl Into<String>, type_: &'static str) -> Self {
+        Self {
+            type_: Some(type_),
+            crossorigin: true,
+            ..Self::new(href, "font")
+        }
+    }
+
+    pub fn type_(mut self, type_: &'static str) -> Self {
+        self.type_ = Some(type_);
+        self
+    }
+}
+
+#[derive(Element, Debug, Clone, PartialEq)]
+#[element("link")]
+#[attr(rel = "prefetch")]
+pub struct Prefetch {
+    #[attr]
+    pub href: String,
+}
+
+impl Prefetch {
+    pub fn new(url: &dyn UrlPath) -> Self {
+        Self { href: url.path() }
+    }
+}
+
+thread_local! {
+    static PREFETCHES: RefCell<Option<Vec<Prefetch>>> = const { RefCell::new(None) };
+}
+
+pub fn collect_prefetches<R>(render: impl FnOnce() -> R) -> (R, Vec<Prefetch>) {
+    let outer = PREFETCHES.with(|p| p.borrow_mut().replace(Vec::new()));
+    let result = render();
+    let collected = PREFETCHES.with(|p| std::mem::replace(&mut *p.borrow_mut(), outer));
+    (result, collected.unwrap_or_default())
+}
+
+fn register_prefetch(href: &str) {
+    PREFETCHES.with(|p| {
+        if let Some(prefetches) = p.borrow_mut().as_mut()
+            && !prefetches.iter().any(|p| p.href == href)
+        {
+            prefetches.push(Prefetch {
+                href: href.to_string(),
+            });
+        }
+    });
+}
+
+#[derive(Element)]
+#[element("a")]
+#[attr(href = self.register())]
+pub struct PrefetchLink<T: Display> {
+    href: String,
+
+    #[element]
+    pub content: T,
+}
+
+impl<T: Display> PrefetchLink<T> {
+    pub fn new(url: &dyn UrlPath, content: T) -> Self {
+        Self {
+            href: url.path(),
+            content,
+        }
+    }
+
+    fn register(&self) -> &str {
+        register_prefetch(&self.href);
+        &self.href
+    }
+}
@@ -1,8 +1,16 @@
 use std::collections::HashMap;
 
+use crate::render::{RenderError, report};
+
+pub type SignalMap = HashMap<String, serde_json::Value>;
+
 pub trait SignalEnum: Sized + serde::Serialize {
     fn signal_name(&self) -> &'static str;
     fn to_json_value(&self) -> serde_json::Value;
+
+    fn selectors() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
 }
 
 pub trait SignalSelector: Sized {
@@ -10,6 +18,9 @@ pub trait SignalSelector: Sized {
     type Enum: SignalEnum;
 
     const NAME: &'static str;
+    const DATA_BIND: &'static str;
+    const JSON_KEY: &'static str;
+    const JS_REF: &'static str;
 
     fn extract(value: &Self::Enum) -> Option<&Self::Value>;
     fn into_inner(value: Self::Enum) -> Option<Self::Value>;
@@ -94,6 +105,42 @@ where
 
 impl<S: SignalSelector> Eq for SignalValue<S> where S::Value: Eq {}
 
+pub trait BindValue {
+    fn fmt_bind(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result;
+}
+
+impl BindValue for bool {
+    fn fmt_bind(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if *self { write!(f, " checked") } else { Ok(()) }
+    }
+}
+
+impl<T: BindValue> BindValue for Option<T> {
+    fn fmt_bind(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if let Some(v) = self {
+            v.fmt_bind(f)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+macro_rules! impl_bind_value {
+    ($($ty:ty),*) => {
+        $(
+            impl BindValue for $ty {
+                fn fmt_bind(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                    write!(f, " value=\"{}\"", crate::html::escape_attr(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_bind_value!(
+    String, &str, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
 pub fn merge_signals<T: SignalEnum>(signals: &[T]) -> serde_json::Value {
     let mut map = serde_json::Map::new();
     for signal in signals {
@@ -102,10 +149,121 @@ pub fn merge_signals<T: SignalEnum>(signals: &[T]) -> serde_json::Value {
     serde_json::Value::Object(map)
 }
 
-pub fn signals_from_map<S: SignalSelector>(
-    signals: &HashMap<String, serde_json::Value>,
-) -> Option<S::Value> {
+pub fn signals_from_map<S: SignalSelector>(signals: &SignalMap) -> Option<S::Value> {
     signals
         .get(S::NAME)
         .and_then(|v| serde_json::from_value(v.clone()).ok())
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalNameCollision {
+    pub name: &'static str,
+    pub first: &'static str,
+    pub second: &'static str,
+}
+
+impl std::fmt::Display for SignalNameCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "signal name `{}` is used by both `{}` and `{}`",
+            self.name, self.first, self.second
+        )
+    }
+}
+
+impl std::error::Error for SignalNameCollision {}
+
+#[derive(Default)]
+struct UsedNames {
+    names: HashMap<&'static str, &'static str>,
+    pending: Option<SignalNameCollision>,
+}
+
+thread_local! {
+    static USED_NAMES: std::cell::RefCell<Option<UsedNames>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+struct RestoreNames(Option<UsedNames>);
+
+impl Drop for RestoreNames {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        USED_NAMES.with(|used| *used.borrow_mut() = previous);
+    }
+}
+
+/// Runs `render` with signal-name collision tracking enabled (debug builds
+/// only). A collision seen during the render fails it with
+/// [`RenderError::SignalNameCollision`](crate::RenderError::SignalNameCollision).
+pub fn unique_names(render: impl FnOnce() -> std::fmt::Result) -> std::fmt::Result {
+    if !cfg!(debug_assertions) || USED_NAMES.with(|used| used.borrow().is_some()) {
+        return render();
+    }
+    let _restore =
+        RestoreNames(USED_NAMES.with(|used| used.borrow_mut().replace(UsedNames::default())));
+    render()?;
+    match USED_NAMES.with(|used| used.borrow_mut().as_mut()?.pending.take()) {
+        Some(collision) => Err(report(RenderError::SignalNameCollision(collision))),
+        None => Ok(()),
+    }
+}
+
+fn collision<S: SignalSelector>() -> Option<SignalNameCollision> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    let selector = std::any::type_name::<S>();
+    USED_NAMES.with(|used| {
+        let mut used = used.borrow_mut();
+        let first = *used.as_mut()?.names.entry(S::NAME).or_insert(selector);
+        (first != selector).then_some(SignalNameCollision {
+            name: S::NAME,
+            first,
+            second: selector,
+        })
+    })
+}
+
+pub fn track<S: SignalSelector>() -> std::fmt::Result {
+    match collision::<S>() {
+        Some(collision) => Err(report(RenderError::SignalNameCollision(collision))),
+        None => Ok(()),
+    }
+}
+
+/// Like [`track`], for callers that cannot fail (such as `AsRef<str>`): the
+/// collision is held until the enclosing [`unique_names`] scope ends.
+#[doc(hidden)]
+pub fn track_name<S: SignalSelector>() -> &'static str {
+    if let Some(collision) = collision::<S>() {
+        USED_NAMES.with(|used| {
+            if let Some(used) = used.borrow_mut().as_mut() {
+                used.pending.get_or_insert(collision);
+            }
+        });
+    }
+    S::NAME
+}
+
+#[cfg(feature = "linkme")]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static SIGNAL_NAMES: [(&'static str, &'static str)];
+
+#[cfg(feature = "linkme")]
+pub fn check_signal_names() -> Result<(), SignalNameCollision> {
+    let mut seen = HashMap::new();
+    for &(name, selector) in SIGNAL_NAMES.iter() {
+        let first = *seen.entry(name).or_insert(selector);
+        if first != selector {
+            return Err(SignalNameCollision {
+                name,
+                first,
+                second: selector,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::html::escape_attr_str;
+
+#[derive(Default)]
+struct Templates {
+    dir: Option<PathBuf>,
+    inline: HashMap<String, Arc<str>>,
+    cache: HashMap<String, (SystemTime, Arc<str>)>,
+}
+
+fn templates() -> &'static Mutex<Templates> {
+    static TEMPLATES: OnceLock<Mutex<Templates>> = OnceLock::new();
+    TEMPLATES.get_or_init(Mutex::default)
+}
+
+pub fn set_dir(dir: impl Into<PathBuf>) {
+    let mut templates = templates().lock().unwrap_or_else(|e| e.into_inner());
+    templates.dir = Some(dir.into());
+    templates.cache.clear();
+}
+
+pub fn register(component: &str, template: &str) {
+    let mut templates = templates().lock().unwrap_or_else(|e| e.into_inner());
+    templates
+        .inline
+        .insert(component.to_string(), Arc::from(template));
+}
+
+pub fn unregister(component: &str) {
+    let mut templates = templates().lock().unwrap_or_else(|e| e.into_inner());
+    templates.inline.remove(component);
+}
+
+pub fn lookup(component: &str) -> Option<Arc<str>> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    let mut templates = templates().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(template) = templates.inline.get(component) {
+        return Some(Arc::clone(template));
+    }
+
+    let path = templates.dir.as_ref()?.join(format!("{}.html", component));
+    let Ok(modified) = std::fs::metadata(&path).and_then(|meta| meta.modified()) else {
+        templates.cache.remove(component);
+        return None;
+    };
+    if let Some((cached_at, template)) = templates.cache.get(component)
+        && *cached_at == modified
+    {
+        return Some(Arc::clone(template));
+    }
+    let template: Arc<str> = Arc::from(std::fs::read_to_string(&path).ok()?);
+    templates
+        .cache
+        .insert(component.to_string(), (modified, Arc::clone(&template)));
+    Some(template)
+}
+
+pub fn render(template: &str, field: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let name_end = tail.find('}').filter(|_| tail.starts_with('{'));
+        match name_end.and_then(|end| field(tail[1..end].trim()).map(|value| (end, value))) {
+            Some((end, value)) => {
+                out.push_str(&value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[doc(hidden)]
+pub struct FieldValue<'a, T: ?Sized>(pub &'a T, pub bool);
+
+#[doc(hidden)]
+pub trait ViaDisplay {
+    fn template_value(&self) -> Option<String>;
+}
+
+impl<T: Display + ?Sized> ViaDisplay for &FieldValue<'_, T> {
+    fn template_value(&self) -> Option<String> {
+        let value = self.0.to_string();
+        Some(if self.1 {
+            escape_attr_str(&value)
+        } else {
+            value
+        })
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaFallback {
+    fn template_value(&self) -> Option<String>;
+}
+
+impl<T: ?Sized> ViaFallback for FieldValue<'_, T> {
+    fn template_value(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_known_fields() {
+        let field = |name: &str| (name == "title").then(|| "Hi".to_string());
+        assert_eq!(
+            render("<h1>{title}</h1>{{title}} {missing}", field),
+            "<h1>Hi</h1>{title} {missing}"
+        );
+        assert_eq!(render("a } b { c", field), "a } b { c");
+    }
+
+    #[test]
+    fn reloads_changed_template_files() {
+        let dir = std::env::temp_dir().join(format!("bluth-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        set_dir(&dir);
+
+        assert_eq!(lookup("ReloadedCard"), None);
+        std::fs::write(dir.join("ReloadedCard.html"), "<p>{a}</p>").unwrap();
+        assert_eq!(lookup("ReloadedCard").as_deref(), Some("<p>{a}</p>"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(lookup("ReloadedCard"), None);
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode, header};
+use axum::response::Response;
+use futures_util::StreamExt;
+use tower::ServiceExt;
+
+use crate::SignalEnum;
+
+#[derive(Clone)]
+pub struct TestApp {
+    router: Router,
+}
+
+impl TestApp {
+    pub fn new(router: Router) -> Self {
+        Self { router }
+    }
+
+    pub fn get(&self, uri: impl Into<String>) -> TestRequest<'_> {
+        self.request(Method::GET, uri)
+    }
+
+    pub fn post(&self, uri: impl Into<String>) -> TestRequest<'_> {
+        self.request(Method::POST, uri)
+    }
+
+    pub fn put(&self, uri: impl Into<String>) -> TestRequest<'_> {
+        self.request(Method::PUT, uri)
+    }
+
+    pub fn delete(&self, uri: impl Into<String>) -> TestRequest<'_> {
+        self.request(Method::DELETE, uri)
+    }
+
+    pub fn request(&self, method: Method, uri: impl Into<String>) -> TestRequest<'_> {
+        TestRequest {
+            app: self,
+            method,
+            uri: uri.into(),
+            headers: HeaderMap::new(),
+            signals: None,
+            body: None,
+        }
+    }
+}
+
+pub struct TestRequest<'a> {
+    app: &'a TestApp,
+    method: Method,
+    uri: String,
+    headers: HeaderMap,
+    signals: Option<serde_json::Value>,
+    body: Option<Body>,
+}
+
+impl TestRequest<'_> {
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::try_from(name).expect("invalid header name");
+        let value = HeaderValue::try_from(value).expect("invalid header value");
+        self.headers.insert(name, value);
+        self
+    }
+
+    pub fn datastar(self) -> Self {
+        self.header("Datastar-Request", "true")
+    }
+
+    pub fn signals<T: SignalEnum>(self, signals: &[T]) -> Self {
+        self.signals_json(crate::signal::merge_signals(signals))
+    }
+
+    pub fn signals_json(mut self, signals: serde_json::Value) -> Self {
+        self.signals = Some(signals);
+        self.datastar()
+    }
+
+    pub fn body(mut self, content_type: &str, body: impl Into<Body>) -> Self {
+        self.body = Some(body.into());
+        self.header(header::CONTENT_TYPE.as_str(), content_type)
+    }
+
+    pub fn form(self, body: impl Into<String>) -> Self {
+        self.body("application/x-www-form-urlencoded", body.into())
+    }
+
+    pub async fn send(self) -> TestResponse {
+        let response = self.dispatch().await;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+
+        TestResponse {
+            status,
+            headers,
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+        }
+    }
+
+    // SSE bodies may never end, so this stops reading once `count` complete
+    // events have arrived or `timeout` passes, whichever comes first.
+    pub async fn send_events(self, count: usize, timeout: Duration) -> TestResponse {
+        let response = self.dispatch().await;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let mut stream = response.into_body().into_data_stream();
+        let mut body = String::new();
+
+        let read = async {
+            while parse_events(complete_events(&body)).len() < count {
+                match stream.next().await {
+                    Some(Ok(chunk)) => body.push_str(&String::from_utf8_lossy(&chunk)),
+                    Some(Err(error)) => panic!("failed to read response body: {error}"),
+                    None => break,
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, read).await;
+
+        TestResponse {
+            status,
+            headers,
+            body: complete_events(&body).to_string(),
+        }
+    }
+
+    async fn dispatch(self) -> Response {
+        let mut uri = self.uri;
+        let mut headers = self.headers;
+        let mut body = self.body;
+
+        if let Some(signals) = self.signals {
+            let json = signals.to_string();
+            if self.method == Method::GET || self.method == Method::DELETE {
+                let separator = if uri.contains('?') { '&' } else { '?' };
+                uri = format!("{uri}{separator}datastar={}", urlencoding::encode(&json));
+            } else {
+                headers.insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                );
+                body = Some(Body::from(json));
+            }
+        }
+
+        let mut request = Request::builder()
+            .method(self.method)
+            .uri(uri)
+            .body(body.unwrap_or_else(Body::empty))
+            .expect("invalid test request");
+        request.headers_mut().extend(headers);
+
+        self.app
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl TestResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.header(header::CONTENT_TYPE.as_str())
+    }
+
+    pub fn html(&self) -> Option<&str> {
+        self.content_type()
+            .is_some_and(|value| value.starts_with("text/html"))
+            .then_some(self.body.as_str())
+    }
+
+    pub fn events(&self) -> Vec<SseEvent> {
+        parse_events(&self.body)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: Vec<String>,
+}
+
+impl SseEvent {
+    pub fn data(&self, key: &str) -> Option<String> {
+        let lines: Vec<&str> = self
+            .data
+            .iter()
+            .filter_map(|line| {
+                line.strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix(' ').or(rest.is_empty().then_some("")))
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    pub fn elements(&self) -> Option<String> {
+        self.data("elements")
+    }
+
+    pub fn signals(&self) -> Option<serde_json::Value> {
+        self.data("signals")
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+}
+
+fn complete_events(body: &str) -> &str {
+    &body[..body.rfind("\n\n").map_or(0, |end| end + 2)]
+}
+
+pub fn parse_events(body: &str) -> Vec<SseEvent> {
+    let mut events = Vec::new();
+    let mut current = SseEvent::default();
+    let mut started = false;
+
+    for line in body.lines() {
+        if line.is_empty() {
+            if started {
+                events.push(std::mem::take(&mut current));
+                started = false;
+            }
+            continue;
+        }
+        if line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value).to_string();
+        match field {
+            "event" => current.event = Some(value),
+            "id" => current.id = Some(value),
+            "data" => current.data.push(value),
+            _ => continue,
+        }
+        started = true;
+    }
+    if started {
+        events.push(current);
+    }
+    events
+}
@@ -1,4 +1,5 @@
 use crate::Element;
+use crate::tests::voids;
 
 #[test]
 fn attrs() {
@@ -79,7 +80,7 @@ fn field_attr() {
 
     let html = hello.to_string();
 
-    assert_eq!(html, "<input value=\"World\" disabled/>");
+    assert_eq!(html, voids("<input value=\"World\" disabled/>"));
 }
 
 #[test]
@@ -141,3 +142,503 @@ fn attr_only_double_braces_no_interpolation() {
 
     assert_eq!(html, "<div data-config=\"{key: 'value'}\"></div>");
 }
+
+#[test]
+fn attr_bool_expression_controls_bare_attr() {
+    #[derive(Element)]
+    #[element("button")]
+    #[attr(disabled = self.count == 0, hidden = !self.visible, title = self.count + 1)]
+    struct Counter {
+        count: u32,
+        visible: bool,
+    }
+
+    assert_eq!(
+        Counter {
+            count: 0,
+            visible: true
+        }
+        .to_string(),
+        "<button disabled title=\"1\"></button>"
+    );
+    assert_eq!(
+        Counter {
+            count: 2,
+            visible: false
+        }
+        .to_string(),
+        "<button hidden title=\"3\"></button>"
+    );
+}
+
+#[test]
+fn attr_bool_expression_keeps_enumerated_values() {
+    #[derive(Element)]
+    #[element("div")]
+    #[attr(
+        aria_expanded = self.open,
+        data_open = self.open,
+        draggable = self.open,
+        spellcheck = !self.open,
+        contenteditable = self.open,
+        hidden = !self.open
+    )]
+    struct Panel {
+        open: bool,
+    }
+
+    assert_eq!(
+        Panel { open: true }.to_string(),
+        r#"<div aria-expanded="true" data-open="true" draggable="true" spellcheck="false" contenteditable="true"></div>"#
+    );
+    assert_eq!(
+        Panel { open: false }.to_string(),
+        r#"<div aria-expanded="false" data-open="false" draggable="false" spellcheck="true" contenteditable="false" hidden></div>"#
+    );
+}
+
+#[test]
+fn attr_conditional_if() {
+    #[derive(Element)]
+    #[element("option")]
+    #[attr(value = "{value}", selected if self.current == self.value)]
+    struct Choice {
+        value: &'static str,
+        current: &'static str,
+    }
+
+    #[derive(Element)]
+    struct Choices {
+        #[element("button")]
+        #[attr(disabled if self.choices.is_empty())]
+        submit: (),
+        #[element]
+        choices: Vec<Choice>,
+    }
+
+    let choices = Choices {
+        submit: (),
+        choices: vec![
+            Choice {
+                value: "a",
+                current: "b",
+            },
+            Choice {
+                value: "b",
+                current: "b",
+            },
+        ],
+    };
+
+    assert_eq!(
+        choices.to_string(),
+        "<button></button><option value=\"a\"></option><option value=\"b\" selected></option>"
+    );
+}
+
+#[test]
+fn field_attr_keyword_names() {
+    #[derive(Element)]
+    #[element("label")]
+    #[attr(class_ = "field")]
+    struct Label {
+        #[attr]
+        for_: &'static str,
+
+        #[attr]
+        r#loop: bool,
+
+        #[attr(name = "type_")]
+        kept: &'static str,
+    }
+
+    let label = Label {
+        for_: "email",
+        r#loop: true,
+        kept: "verbatim",
+    };
+
+    assert_eq!(
+        label.to_string(),
+        "<label class=\"field\" for=\"email\" loop type_=\"verbatim\"></label>"
+    );
+}
+
+#[test]
+fn attr_raw_and_framework_style_keys() {
+    #[derive(Element)]
+    #[element("button")]
+    #[attr(
+        r#type = "button",
+        r#async = true,
+        @click = "open = true",
+        x_on:click.prevent = "go()",
+        data_on:keydown__window.debounce.500ms = "@get('/x')"
+    )]
+    struct Button {
+        #[element("span")]
+        #[attr(for = "a", @focus = "select()", data_attr:aria_label = "$label")]
+        label: &'static str,
+    }
+
+    let button = Button { label: "Go" };
+
+    assert_eq!(
+        button.to_string(),
+        concat!(
+            "<button type=\"button\" async @click=\"open = true\" x-on:click.prevent=\"go()\"",
+            " data-on:keydown__window.debounce.500ms=\"@get('/x')\">",
+            "<span for=\"a\" @focus=\"select()\" data-attr:aria-label=\"$label\">Go</span></button>"
+        )
+    );
+}
+
+#[test]
+fn raw_attr_field_is_not_escaped() {
+    use crate::html::Raw;
+
+    #[derive(Element)]
+    #[element("div")]
+    struct Widget {
+        #[attr(name = "data-on:click")]
+        on_click: Raw<&'static str>,
+
+        #[attr]
+        title: &'static str,
+
+        #[attr(name = "data-json")]
+        json: Option<Raw<String>>,
+    }
+
+    let widget = Widget {
+        on_click: Raw("a &amp;&amp; b()"),
+        title: "a & b",
+        json: Some(Raw("{&quot;a&quot;:1}".to_string())),
+    };
+
+    assert_eq!(
+        widget.to_string(),
+        "<div data-on:click=\"a &amp;&amp; b()\" title=\"a &amp; b\" data-json=\"{&quot;a&quot;:1}\"></div>"
+    );
+}
+
+#[test]
+fn flattened_attr_map() {
+    use std::collections::BTreeMap;
+
+    #[derive(Element)]
+    #[element("div")]
+    struct Widget {
+        #[attr]
+        id: &'static str,
+
+        #[attr(flatten)]
+        extra: BTreeMap<String, String>,
+
+        #[element]
+        label: &'static str,
+    }
+
+    let widget = Widget {
+        id: "w",
+        extra: BTreeMap::from([
+            ("data-user".to_string(), "a \"b\" & c".to_string()),
+            ("aria-label".to_string(), "Widget".to_string()),
+            ("bad name".to_string(), "x".to_string()),
+            ("x\"onclick".to_string(), "y".to_string()),
+        ]),
+        label: "hi",
+    };
+    assert_eq!(
+        widget.to_string(),
+        r#"<div id="w" aria-label="Widget" data-user="a &quot;b&quot; &amp; c">hi</div>"#
+    );
+}
+
+#[test]
+fn spread_attr_list() {
+    use crate::html::Attr;
+
+    #[derive(Element)]
+    #[element("button")]
+    struct Button {
+        #[attr(spread)]
+        attrs: Vec<Attr>,
+
+        #[element]
+        label: &'static str,
+    }
+
+    let button = Button {
+        attrs: vec![
+            Attr::new("title", "Save \"draft\""),
+            Attr::bool("disabled", true),
+            Attr::bool("hidden", false),
+            Attr::raw("data-on:click", "@post('/save')"),
+            Attr::new("x onclick", "steal()"),
+        ],
+        label: "Save",
+    };
+    assert_eq!(
+        button.to_string(),
+        r#"<button title="Save &quot;draft&quot;" disabled data-on:click="@post('/save')">Save</button>"#
+    );
+}
+
+#[test]
+fn data_attrs_bag() {
+    use crate::html::DataAttrs;
+
+    #[derive(Element)]
+    #[element("tr")]
+    struct Row {
+        #[attr]
+        data: DataAttrs,
+
+        #[attr]
+        id: &'static str,
+    }
+
+    let row = Row {
+        data: DataAttrs::new()
+            .with("user-id", 42)
+            .with("note", "say \"hi\" & <bye>")
+            .with("bad key", "x"),
+        id: "r1",
+    };
+    assert_eq!(
+        row.to_string(),
+        r#"<tr data-note="say &quot;hi&quot; &amp; &lt;bye&gt;" data-user-id="42" id="r1"></tr>"#
+    );
+
+    let empty = Row {
+        data: [("k", "v")].into_iter().collect(),
+        id: "r2",
+    };
+    assert_eq!(empty.data.get("k"), Some("v"));
+    assert_eq!(empty.to_string(), r#"<tr data-k="v" id="r2"></tr>"#);
+}
+
+#[test]
+fn conditional_class_list() {
+    #[derive(Element)]
+    #[element("button")]
+    #[attr(class = ["btn", "btn-primary", active => self.is_active, "is-loading" => self.loading])]
+    struct Button {
+        is_active: bool,
+        loading: bool,
+
+        #[element]
+        label: &'static str,
+    }
+
+    let button = Button {
+        is_active: true,
+        loading: false,
+        label: "Save",
+    };
+    assert_eq!(
+        button.to_string(),
+        r#"<button class="btn btn-primary active">Save</button>"#
+    );
+
+    #[derive(Element)]
+    #[element("ul")]
+    struct Tabs {
+        #[element]
+        tabs: Vec<Tab>,
+    }
+
+    #[derive(Element)]
+    #[element("li")]
+    #[attr(class = [selected => self.selected, disabled => self.disabled])]
+    struct Tab {
+        selected: bool,
+        disabled: bool,
+    }
+
+    let tabs = Tabs {
+        tabs: vec![
+            Tab {
+                selected: true,
+                disabled: true,
+            },
+            Tab {
+                selected: false,
+                disabled: false,
+            },
+        ],
+    };
+    assert_eq!(
+        tabs.to_string(),
+        r#"<ul><li class="selected disabled"></li><li></li></ul>"#
+    );
+}
+
+#[test]
+fn style_builder() {
+    use crate::html::Style;
+
+    #[derive(Element)]
+    #[element("div")]
+    #[attr(style = Style::new().prop("width", format!("{}%", self.percent)).prop_if(self.hidden, "display", "none"))]
+    struct Bar {
+        percent: u8,
+        hidden: bool,
+    }
+
+    assert_eq!(
+        Bar {
+            percent: 50,
+            hidden: true
+        }
+        .to_string(),
+        r#"<div style="width: 50%; display: none"></div>"#
+    );
+
+    #[derive(Element)]
+    #[element("span")]
+    struct Badge {
+        #[attr]
+        style: Style,
+    }
+
+    let badge = Badge {
+        style: Style::new()
+            .prop("font-family", "\"Fira Sans\"")
+            .prop("color", "red")
+            .prop("color", "blue")
+            .prop("background", "red; position: fixed")
+            .prop("bad name", "x"),
+    };
+    assert_eq!(
+        badge.to_string(),
+        r#"<span style="font-family: &quot;Fira Sans&quot;; color: blue"></span>"#
+    );
+
+    let empty = Badge {
+        style: Style::new().prop_if(false, "color", "red"),
+    };
+    assert_eq!(empty.to_string(), "<span></span>");
+}
+
+#[test]
+fn optional_bool_attrs() {
+    #[derive(Element)]
+    #[element("button")]
+    struct Toggle {
+        #[attr]
+        disabled: Option<bool>,
+
+        #[attr(name = "aria-pressed", explicit)]
+        pressed: Option<bool>,
+
+        #[attr(explicit)]
+        aria_expanded: bool,
+    }
+
+    let toggle = |disabled, pressed, aria_expanded| {
+        Toggle {
+            disabled,
+            pressed,
+            aria_expanded,
+        }
+        .to_string()
+    };
+
+    assert_eq!(
+        toggle(Some(true), Some(true), true),
+        r#"<button disabled aria-pressed="true" aria-expanded="true"></button>"#
+    );
+    assert_eq!(
+        toggle(Some(false), Some(false), false),
+        r#"<button aria-pressed="false" aria-expanded="false"></button>"#
+    );
+    assert_eq!(
+        toggle(None, None, false),
+        r#"<button aria-expanded="false"></button>"#
+    );
+}
+
+#[test]
+fn skip_if_empty_attrs() {
+    #[derive(Element)]
+    #[element("div")]
+    struct Panel {
+        #[attr(skip_if_empty)]
+        class: String,
+
+        #[attr(name = "title", skip_if_empty)]
+        tooltip: Option<&'static str>,
+
+        #[attr]
+        id: &'static str,
+    }
+
+    let empty = Panel {
+        class: String::new(),
+        tooltip: Some(""),
+        id: "",
+    };
+    assert_eq!(empty.to_string(), r#"<div id=""></div>"#);
+
+    let filled = Panel {
+        class: "card".to_string(),
+        tooltip: Some("Details"),
+        id: "p1",
+    };
+    assert_eq!(
+        filled.to_string(),
+        r#"<div class="card" title="Details" id="p1"></div>"#
+    );
+}
+
+#[test]
+fn attr_field_path_interpolation() {
+    struct User {
+        id: u32,
+        role: (String, u8),
+    }
+
+    #[derive(Element)]
+    #[element("a")]
+    #[attr(href = "/users/{user.id}", "data-{user.role.0}" = "{user.role.1}")]
+    struct UserLink {
+        user: User,
+    }
+
+    let link = UserLink {
+        user: User {
+            id: 7,
+            role: ("admin".to_string(), 3),
+        },
+    };
+
+    assert_eq!(
+        link.to_string(),
+        r#"<a href="/users/7" data-admin="3"></a>"#
+    );
+}
+
+#[test]
+fn attr_interpolation_format_spec() {
+    #[derive(Element)]
+    #[element("div")]
+    #[attr("data-total" = "{total:.2}", "data-code" = "{code:>04}", title = "#{id:x}")]
+    struct Order {
+        total: f64,
+        code: u32,
+        id: u32,
+    }
+
+    let order = Order {
+        total: 19.999,
+        code: 7,
+        id: 255,
+    };
+
+    assert_eq!(
+        order.to_string(),
+        r##"<div data-total="20.00" data-code="0007" title="#ff"></div>"##
+    );
+}
@@ -1,8 +1,10 @@
+use crate::testing::TestApp;
 use crate::{Signal, SignalExtractor, SignalSelector, Signals};
 use axum::{
-    extract::FromRequest,
+    Router,
     http::{StatusCode, header},
     response::IntoResponse,
+    routing::{get, post},
 };
 use bluth_macros::Element;
 
@@ -13,122 +15,93 @@ pub enum TestSignals {
     UserEmail(String),
 }
 
-#[tokio::test]
-async fn signal_extractor_post_json() -> Result<(), anyhow::Error> {
-    use axum::{
-        body::Body,
-        extract::Request,
-        http::{Method, header},
-    };
-
-    let json_body = r#"{"searchTerm":"test query"}"#;
-
-    let request = Request::builder()
-        .method(Method::POST)
-        .uri("/search")
-        .header(header::CONTENT_TYPE, "application/json")
-        .header("Datastar-Request", "true")
-        .body(Body::from(json_body))?;
-
-    let result: Result<SignalExtractor<SearchTerm>, _> =
-        SignalExtractor::<SearchTerm>::from_request(request, &()).await;
-
-    let SignalExtractor(search_term) = result.expect("Failed to extract signal");
+fn extractor_app() -> TestApp {
+    TestApp::new(
+        Router::new()
+            .route(
+                "/search",
+                get(|SignalExtractor(term): SignalExtractor<SearchTerm>| async move { term })
+                    .post(|SignalExtractor(term): SignalExtractor<SearchTerm>| async move { term }),
+            )
+            .route(
+                "/register",
+                post(
+                    |Signals((SignalExtractor(name), SignalExtractor(email))): Signals<(
+                        SignalExtractor<UserName>,
+                        SignalExtractor<UserEmail>,
+                    )>| async move { format!("{name} <{email}>") },
+                ),
+            ),
+    )
+}
 
-    assert_eq!(search_term, "test query");
+#[tokio::test]
+async fn signal_extractor_post_json() {
+    let response = extractor_app()
+        .post("/search")
+        .body("application/json", r#"{"searchTerm":"test query"}"#)
+        .datastar()
+        .send()
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, "test query");
     assert_eq!(SearchTerm::NAME, "searchTerm");
-
-    Ok(())
 }
 
 #[tokio::test]
-async fn signal_extractor_get_query() -> Result<(), anyhow::Error> {
-    use axum::{body::Body, extract::Request, http::Method};
-
+async fn signal_extractor_get_query() {
     let query_string = "datastar=%7B%22searchTerm%22%3A%22test%20query%22%7D";
 
-    let request = Request::builder()
-        .method(Method::GET)
-        .uri(format!("/search?{}", query_string))
-        .header("Datastar-Request", "true")
-        .body(Body::empty())?;
+    let response = extractor_app()
+        .get(format!("/search?{}", query_string))
+        .datastar()
+        .send()
+        .await;
 
-    let result: Result<SignalExtractor<SearchTerm>, _> =
-        SignalExtractor::<SearchTerm>::from_request(request, &()).await;
-
-    let SignalExtractor(search_term) = result.expect("Failed to extract signal");
-
-    assert_eq!(search_term, "test query");
-
-    Ok(())
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, "test query");
 }
 
 #[tokio::test]
-async fn signal_extractor_multiple_signals() -> Result<(), anyhow::Error> {
-    use axum::{body::Body, extract::Request, http::Method};
-
-    let json_body = r#"{"userName":"John Doe","userEmail":"john@example.com"}"#;
-
-    let request = Request::builder()
-        .method(Method::POST)
-        .uri("/register")
-        .header(header::CONTENT_TYPE, "application/json")
-        .header("Datastar-Request", "true")
-        .body(Body::from(json_body))?;
-
-    let result: Result<Signals<(SignalExtractor<UserName>, SignalExtractor<UserEmail>)>, _> =
-        Signals::from_request(request, &()).await;
-
-    let Signals((SignalExtractor(user_name), SignalExtractor(user_email))) =
-        result.expect("Failed to extract signals");
-
-    assert_eq!(user_name, "John Doe");
-    assert_eq!(user_email, "john@example.com");
+async fn signal_extractor_multiple_signals() {
+    let response = extractor_app()
+        .post("/register")
+        .body(
+            "application/json",
+            r#"{"userName":"John Doe","userEmail":"john@example.com"}"#,
+        )
+        .datastar()
+        .send()
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, "John Doe <john@example.com>");
     assert_eq!(UserName::NAME, "userName");
     assert_eq!(UserEmail::NAME, "userEmail");
-
-    Ok(())
 }
 
 #[tokio::test]
-async fn signal_extractor_missing_header() -> Result<(), anyhow::Error> {
-    use axum::{body::Body, extract::Request, http::Method};
-
-    let json_body = r#"{"searchTerm":"test"}"#;
-
-    let request = Request::builder()
-        .method(Method::POST)
-        .uri("/search")
-        .header(header::CONTENT_TYPE, "application/json")
-        .body(Body::from(json_body))?;
-
-    let result: Result<SignalExtractor<SearchTerm>, _> =
-        SignalExtractor::<SearchTerm>::from_request(request, &()).await;
-
-    assert!(result.is_err());
-
-    Ok(())
+async fn signal_extractor_missing_header() {
+    let response = extractor_app()
+        .post("/search")
+        .body("application/json", r#"{"searchTerm":"test"}"#)
+        .send()
+        .await;
+
+    assert!(response.status.is_client_error());
 }
 
 #[tokio::test]
-async fn signal_extractor_missing_signal() -> Result<(), anyhow::Error> {
-    use axum::{body::Body, extract::Request, http::Method};
-
-    let json_body = r#"{"otherSignal":"value"}"#;
-
-    let request = Request::builder()
-        .method(Method::POST)
-        .uri("/search")
-        .header(header::CONTENT_TYPE, "application/json")
-        .header("Datastar-Request", "true")
-        .body(Body::from(json_body))?;
-
-    let result: Result<SignalExtractor<SearchTerm>, _> =
-        SignalExtractor::<SearchTerm>::from_request(request, &()).await;
-
-    assert!(result.is_err());
-
-    Ok(())
+async fn signal_extractor_missing_signal() {
+    let response = extractor_app()
+        .post("/search")
+        .body("application/json", r#"{"otherSignal":"value"}"#)
+        .datastar()
+        .send()
+        .await;
+
+    assert!(response.status.is_client_error());
 }
 
 #[tokio::test]
@@ -163,3 +136,108 @@ async fn enum_to_html() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+crate::define_url!(AdminUrl, "/admin");
+crate::define_url!(AdminUserUrl, "/admin/users", user_id: u64);
+crate::define_url!(StatusUrl, "/status");
+
+#[tokio::test]
+async fn define_routes_nests_under_prefix() {
+    use axum::{
+        extract::{Path, Request},
+        middleware::{self, Next},
+        response::Response,
+    };
+
+    async fn tag(request: Request, next: Next) -> Response {
+        let mut response = next.run(request).await;
+        response
+            .headers_mut()
+            .insert("x-admin", header::HeaderValue::from_static("1"));
+        response
+    }
+
+    let app = axum::Router::new()
+        .merge(crate::define_routes!(
+            prefix = "/admin",
+            layer = middleware::from_fn(tag),
+            AdminUrl => get(|| async { "dashboard" }),
+            AdminUserUrl => get(|Path(url): Path<AdminUserUrl>| async move {
+                format!("user {}", url.user_id)
+            }),
+        ))
+        .merge(crate::define_routes!(StatusUrl => get(|| async { "ok" })));
+
+    let app = TestApp::new(app);
+
+    let response = app.get(AdminUserUrl::new(7).path()).send().await;
+    assert_eq!(response.header("x-admin"), Some("1"));
+    assert_eq!(response.body, "user 7");
+
+    let response = app.get(AdminUrl::new().path()).send().await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app.get(StatusUrl::new().path()).send().await;
+    assert!(response.header("x-admin").is_none());
+    assert_eq!(response.body, "ok");
+}
+
+#[tokio::test]
+async fn html_or_json_negotiates_on_accept() {
+    use crate::negotiate::ResponseFormat;
+
+    #[derive(Element, serde::Serialize)]
+    #[element("p")]
+    struct Greeting {
+        #[element]
+        name: &'static str,
+    }
+
+    let app = axum::Router::new().route(
+        "/greeting",
+        get(|format: ResponseFormat| async move { format.respond(Greeting { name: "Ann" }) }),
+    );
+
+    let app = TestApp::new(app);
+
+    let response = app
+        .get("/greeting")
+        .header(header::ACCEPT.as_str(), "application/json")
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.content_type(), Some("application/json"));
+    assert_eq!(response.body, r#"{"name":"Ann"}"#);
+
+    let response = app
+        .get("/greeting")
+        .header(header::ACCEPT.as_str(), "application/json")
+        .datastar()
+        .send()
+        .await;
+    assert_eq!(response.body, "<p>Ann</p>");
+}
+
+#[tokio::test]
+async fn render_errors_become_server_errors() {
+    use crate::datastar::PatchElements;
+
+    #[derive(Element)]
+    #[element(tag = tag)]
+    struct Dynamic {
+        tag: String,
+    }
+
+    let app = TestApp::new(Router::new().route(
+        "/patch",
+        get(|| async {
+            PatchElements::new(vec![Dynamic {
+                tag: "div onclick=alert(1)".to_string(),
+            }])
+        }),
+    ));
+
+    let response = app.get("/patch").send().await;
+    assert_eq!(response.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.body, "Failed to render response");
+}
@@ -1,4 +1,5 @@
 use crate::Element;
+use crate::tests::voids;
 
 #[test]
 fn struct_to_html() {
@@ -41,3 +42,341 @@ fn enum_to_html() {
 
     assert_eq!(html, "<div><span>world</span></div>");
 }
+
+#[test]
+fn enum_named_field_variants() {
+    #[derive(Element)]
+    #[element("div")]
+    enum Card {
+        #[element("header")]
+        #[attr(class = "card-header")]
+        Header {
+            #[element("h2")]
+            title: String,
+
+            #[element("p")]
+            #[attr(class = "subtitle")]
+            subtitle: Option<String>,
+        },
+
+        Body {
+            #[attr(name = "data-id")]
+            id: u32,
+
+            #[element]
+            items: Vec<String>,
+        },
+    }
+
+    let header = Card::Header {
+        title: "Hello".into(),
+        subtitle: Some("World".into()),
+    };
+    assert_eq!(
+        header.to_string(),
+        "<div><header class=\"card-header\"><h2>Hello</h2><p class=\"subtitle\">World</p></header></div>"
+    );
+
+    let header = Card::Header {
+        title: "Hello".into(),
+        subtitle: None,
+    };
+    assert_eq!(
+        header.to_string(),
+        "<div><header class=\"card-header\"><h2>Hello</h2><p class=\"subtitle\"></p></header></div>"
+    );
+
+    let body = Card::Body {
+        id: 7,
+        items: vec!["a".into(), "b".into()],
+    };
+    assert_eq!(body.to_string(), "<div data-id=\"7\">ab</div>");
+}
+
+#[test]
+fn enum_named_field_variant_interpolation() {
+    #[derive(Element)]
+    #[element("ul")]
+    enum Link {
+        #[element("li")]
+        #[attr(id = "link-{f}", hidden if !self.visible)]
+        Item {
+            f: u32,
+            visible: bool,
+
+            #[element("a")]
+            label: &'static str,
+        },
+    }
+
+    let link = Link::Item {
+        f: 3,
+        visible: false,
+        label: "Go",
+    };
+    assert_eq!(
+        link.to_string(),
+        "<ul><li id=\"link-3\" hidden><a>Go</a></li></ul>"
+    );
+}
+
+#[test]
+fn render_if_condition() {
+    #[derive(Element)]
+    #[element("article")]
+    struct Product {
+        show_details: bool,
+
+        #[element("h1")]
+        name: &'static str,
+
+        #[element("p", render_if = self.show_details)]
+        details: &'static str,
+
+        #[element("span", render_if_fn = Self::on_sale)]
+        discount: u8,
+    }
+
+    impl Product {
+        fn on_sale(&self) -> bool {
+            self.discount > 0
+        }
+    }
+
+    let product = Product {
+        show_details: true,
+        name: "Lamp",
+        details: "Brass",
+        discount: 0,
+    };
+    assert_eq!(
+        product.to_string(),
+        "<article><h1>Lamp</h1><p>Brass</p></article>"
+    );
+
+    let product = Product {
+        show_details: false,
+        name: "Lamp",
+        details: "Brass",
+        discount: 20,
+    };
+    assert_eq!(
+        product.to_string(),
+        "<article><h1>Lamp</h1><span>20</span></article>"
+    );
+
+    mod flags {
+        pub const BETA: bool = false;
+    }
+
+    #[derive(Element)]
+    #[element("nav")]
+    struct Menu {
+        #[element("a", render_if = flags::BETA)]
+        beta: &'static str,
+    }
+
+    assert_eq!(Menu { beta: "Beta" }.to_string(), "<nav></nav>");
+}
+
+#[test]
+fn text_content_is_escaped() {
+    #[derive(Element)]
+    #[element("p")]
+    struct Comment {
+        #[element("b")]
+        author: String,
+
+        #[element]
+        body: String,
+
+        #[element(raw)]
+        signature: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("span")]
+    struct Label(String);
+
+    #[derive(Element)]
+    #[element("li")]
+    enum Item {
+        Text(String),
+    }
+
+    let comment = Comment {
+        author: "<script>".into(),
+        body: "Tom & Jerry".into(),
+        signature: "<em>cheers</em>",
+    };
+    assert_eq!(
+        comment.to_string(),
+        "<p><b>&lt;script&gt;</b>Tom &amp; Jerry<em>cheers</em></p>"
+    );
+    assert_eq!(Label("a < b".into()).to_string(), "<span>a &lt; b</span>");
+    assert_eq!(Item::Text("1 > 0".into()).to_string(), "<li>1 &gt; 0</li>");
+}
+
+#[test]
+fn enum_variant_attrs() {
+    #[derive(Element)]
+    #[element("ul")]
+    enum Alert {
+        #[element("li")]
+        #[attr(class = "warning", data_code = "{1}")]
+        Warning(&'static str, u16),
+
+        #[attr(class = "info", hidden if self.0.is_empty())]
+        Info(&'static str),
+
+        #[element("hr")]
+        #[attr(class = "divider")]
+        Divider,
+    }
+
+    assert_eq!(
+        Alert::Warning("Disk <low>", 507).to_string(),
+        r#"<ul><li class="warning" data-code="507">Disk &lt;low&gt;507</li></ul>"#
+    );
+    assert_eq!(
+        Alert::Info("").to_string(),
+        r#"<ul class="info" hidden></ul>"#
+    );
+    assert_eq!(Alert::Info("ok").to_string(), r#"<ul class="info">ok</ul>"#);
+    assert_eq!(
+        Alert::Divider.to_string(),
+        voids(r#"<ul><hr class="divider"/></ul>"#)
+    );
+}
+
+#[test]
+fn dynamic_tag_from_field() {
+    use crate::html::HeadingLevel;
+    use crate::{Render, RenderError};
+
+    #[derive(Element)]
+    #[element(tag = level)]
+    #[attr(class = "title")]
+    struct Heading {
+        level: HeadingLevel,
+
+        #[element]
+        text: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element(tag = tag)]
+    struct Dynamic {
+        tag: &'static str,
+
+        #[attr]
+        id: &'static str,
+    }
+
+    assert_eq!(
+        Heading {
+            level: HeadingLevel::new(3),
+            text: "Intro",
+        }
+        .to_string(),
+        r#"<h3 class="title">Intro</h3>"#
+    );
+    assert_eq!(HeadingLevel::H2.nested(), HeadingLevel::H3);
+    assert_eq!(
+        Dynamic {
+            tag: "my-card",
+            id: "a"
+        }
+        .to_string(),
+        r#"<my-card id="a"></my-card>"#
+    );
+    assert_eq!(
+        Dynamic { tag: "br", id: "b" }.to_string(),
+        voids(r#"<br id="b"/>"#)
+    );
+    assert_eq!(
+        Dynamic {
+            tag: "div onclick=x",
+            id: "c"
+        }
+        .try_render_to_string(),
+        Err(RenderError::InvalidTag("div onclick=x".to_string()))
+    );
+}
+
+#[test]
+fn macro_void_elements_match_runtime() {
+    #[derive(Element)]
+    #[element(tag = tag)]
+    struct Dynamic {
+        tag: &'static str,
+    }
+
+    macro_rules! check {
+        ($($tag:ident)*) => {$(
+            assert_eq!(
+                crate::html! { $tag; }.to_string(),
+                Dynamic { tag: stringify!($tag) }.to_string(),
+                "<{}> is void in only one of bluth and bluth_macros",
+                stringify!($tag)
+            );
+        )*};
+    }
+
+    check!(area base br col embed hr img input link meta param source track wbr);
+    check!(div p span script template textarea);
+}
+
+#[test]
+fn result_fields() {
+    #[derive(Element)]
+    #[element("p")]
+    #[attr(class = "error")]
+    struct ErrorBox {
+        #[element]
+        message: String,
+    }
+
+    impl ErrorBox {
+        fn new(error: &impl std::fmt::Display) -> Self {
+            Self {
+                message: error.to_string(),
+            }
+        }
+    }
+
+    #[derive(Element)]
+    #[element("div")]
+    struct Dashboard {
+        #[element("span")]
+        total: Result<u32, String>,
+        #[element("span")]
+        #[map_or("n/a")]
+        average: Result<f32, String>,
+        #[element("section", on_err = ErrorBox::new(error))]
+        feed: Result<String, std::fmt::Error>,
+    }
+
+    let ok = Dashboard {
+        total: Ok(3),
+        average: Ok(1.5),
+        feed: Ok("<news>".into()),
+    };
+    assert_eq!(
+        ok.to_string(),
+        "<div><span>3</span><span>1.5</span><section>&lt;news&gt;</section></div>"
+    );
+
+    let failed = Dashboard {
+        total: Err("db <down>".into()),
+        average: Err("timeout".into()),
+        feed: Err(std::fmt::Error),
+    };
+    assert_eq!(
+        failed.to_string(),
+        concat!(
+            "<div><span>db &lt;down&gt;</span><span>n/a</span>",
+            r#"<section><p class="error">an error occurred when formatting an argument</p></section></div>"#
+        )
+    );
+}
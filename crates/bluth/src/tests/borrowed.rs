@@ -0,0 +1,100 @@
+use crate::tests::voids;
+use std::borrow::Cow;
+
+use crate::{Body, Document, Element, Head, Html, Link, Script};
+
+#[derive(Element)]
+#[element("a")]
+#[attr(class = "user-{kind}")]
+struct UserLink<'a> {
+    kind: &'a str,
+
+    #[attr]
+    href: Cow<'a, str>,
+
+    #[attr]
+    title: Option<&'a str>,
+
+    #[element]
+    name: Cow<'a, str>,
+}
+
+#[derive(Element)]
+#[element("ul")]
+struct UserList<'a, 'b: 'a> {
+    #[element]
+    users: &'a [UserLink<'b>],
+
+    #[element("li")]
+    tags: Vec<&'b str>,
+}
+
+#[test]
+fn borrowed_fields_render_without_cloning() {
+    let request_name = String::from("Ann & <Bob>");
+    let request_path = format!("/users/{}", 7);
+
+    let users = [
+        UserLink {
+            kind: "admin",
+            href: Cow::Borrowed(&request_path),
+            title: Some(&request_name),
+            name: Cow::Borrowed(&request_name),
+        },
+        UserLink {
+            kind: "guest",
+            href: Cow::Owned("/users/8".to_string()),
+            title: None,
+            name: "Cy".into(),
+        },
+    ];
+    let list = UserList {
+        users: &users,
+        tags: request_path.split('/').filter(|s| !s.is_empty()).collect(),
+    };
+
+    assert_eq!(
+        list.to_string(),
+        concat!(
+            r#"<ul><a class="user-admin" href="/users/7" title="Ann &amp; &lt;Bob&gt;">Ann &amp; &lt;Bob&gt;</a>"#,
+            r#"<a class="user-guest" href="/users/8">Cy</a><li>users7</li></ul>"#,
+        )
+    );
+}
+
+#[test]
+fn built_in_document_accepts_borrowed_strings() {
+    let lang = String::from("en");
+    let stylesheet = format!("/static/{}.css", "app");
+    let script = String::from("/static/app.js");
+    let class = String::from("page");
+    let children = ["<p>hi</p>"];
+
+    let document = Document::new(Html {
+        lang: &lang,
+        head: Head {
+            link: vec![Link {
+                id: None,
+                href: &stylesheet,
+            }],
+            script: vec![Script {
+                src: &script,
+                async_: true,
+                type_: "module",
+            }],
+        },
+        body: Body {
+            class: &class,
+            children: children.to_vec(),
+        },
+    });
+
+    assert_eq!(
+        document.to_string(),
+        voids(concat!(
+            r#"<!doctype html><html lang="en"><head><link rel="stylesheet" href="/static/app.css"/>"#,
+            r#"<script src="/static/app.js" async type="module"></script></head>"#,
+            r#"<body class="page"><p>hi</p></body></html>"#,
+        ))
+    );
+}
@@ -0,0 +1,68 @@
+use crate::{BuilderError, Element, ElementBuilder};
+
+#[derive(Element, ElementBuilder)]
+#[element("li")]
+struct Item {
+    #[element]
+    label: String,
+}
+
+#[derive(Element, ElementBuilder)]
+#[element("article")]
+struct Card {
+    #[element("h2")]
+    title: String,
+    #[element("p")]
+    subtitle: Option<String>,
+    #[element("ul")]
+    items: Vec<Item>,
+    #[attr]
+    #[builder(default)]
+    data_rank: u32,
+}
+
+#[test]
+fn builder_sets_required_optional_and_list_fields() {
+    let card = CardBuilder::new()
+        .title("Hello")
+        .subtitle("World")
+        .items(vec![Item::builder().label("one").build().unwrap()])
+        .push_items(Item::builder().label("two").build().unwrap())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        card.to_string(),
+        r#"<article data-rank="0"><h2>Hello</h2><p>World</p><ul><li>one</li><li>two</li></ul></article>"#
+    );
+}
+
+#[test]
+fn builder_leaves_optional_fields_unset() {
+    let card = Card::builder()
+        .title("Bare")
+        .data_rank(3u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(card.subtitle, None);
+    assert!(card.items.is_empty());
+    assert_eq!(card.data_rank, 3);
+}
+
+#[test]
+fn builder_reports_missing_required_field() {
+    let error = Card::builder().build().err().unwrap();
+
+    assert_eq!(
+        error,
+        BuilderError {
+            builder: "CardBuilder",
+            field: "title",
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "CardBuilder: missing required field `title`"
+    );
+}
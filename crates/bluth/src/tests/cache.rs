@@ -0,0 +1,102 @@
+use crate::cache::{Cached, etag, etag_matches};
+use crate::{Element, Render};
+use std::cell::Cell;
+use std::fmt::Display;
+
+thread_local! {
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct Counted;
+
+impl Display for Counted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        f.write_str("<p>counted</p>")
+    }
+}
+
+#[derive(Element)]
+#[element("section")]
+struct Panel {
+    #[element]
+    body: Counted,
+}
+
+#[test]
+fn cached_renders_once_and_replays() {
+    let mut cached = Cached::new(Panel { body: Counted });
+
+    assert_eq!(cached.to_string(), "<section><p>counted</p></section>");
+    assert_eq!(cached.to_string(), "<section><p>counted</p></section>");
+    assert_eq!(RENDERS.with(Cell::get), 1);
+    assert_eq!(
+        cached.size_hint(),
+        "<section><p>counted</p></section>".len()
+    );
+
+    cached.invalidate();
+    assert_eq!(cached.to_string(), "<section><p>counted</p></section>");
+    assert_eq!(RENDERS.with(Cell::get), 2);
+
+    #[cfg(feature = "metrics")]
+    {
+        let stats = crate::metrics::cache_stats::<Panel>();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+    }
+}
+
+#[test]
+fn etags_match_weak_and_listed_tags() {
+    let tag = etag("<p>hi</p>");
+    assert_eq!(tag, etag("<p>hi</p>"));
+    assert_ne!(tag, etag("<p>bye</p>"));
+    assert!(tag.starts_with('"') && tag.ends_with('"'));
+
+    assert!(etag_matches(&tag, &tag));
+    assert!(etag_matches(&format!("W/{tag}"), &tag));
+    assert!(etag_matches(&format!("\"other\", {tag}"), &tag));
+    assert!(etag_matches("*", &tag));
+    assert!(!etag_matches("\"other\"", &tag));
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn conditional_answers_not_modified_for_a_matching_etag() {
+    use crate::cache::IfNoneMatch;
+    use crate::testing::TestApp;
+    use axum::{Router, http::StatusCode, routing::get};
+
+    #[derive(Element)]
+    #[element("main")]
+    struct Home {}
+
+    let app = TestApp::new(Router::new().route(
+        "/",
+        get(|if_none_match: IfNoneMatch| async move { if_none_match.respond(Home {}) }),
+    ));
+
+    let fresh = app.get("/").send().await;
+    assert_eq!(fresh.status, StatusCode::OK);
+    assert_eq!(fresh.body, "<main></main>");
+    let tag = fresh.header("etag").unwrap().to_string();
+
+    let cached = app.get("/").header("if-none-match", &tag).send().await;
+    assert_eq!(cached.status, StatusCode::NOT_MODIFIED);
+    assert!(cached.body.is_empty());
+    assert_eq!(cached.header("etag"), Some(tag.as_str()));
+
+    let stale = app
+        .get("/")
+        .header("if-none-match", "\"stale\"")
+        .send()
+        .await;
+    assert_eq!(stale.status, StatusCode::OK);
+
+    #[cfg(feature = "metrics")]
+    {
+        let stats = crate::metrics::conditional_stats::<Home>();
+        assert_eq!((stats.requests, stats.not_modified), (2, 1));
+        assert_eq!(crate::metrics::response_size::<Home>().responses, 2);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::SignalEnum;
+use crate::codegen::ConstModule;
+
+#[derive(crate::Signal)]
+pub enum ExportSignals {
+    UserName(String),
+    #[signal(name = "pageNum")]
+    PageNumber(i32),
+}
+
+crate::define_url!(ExportUserUrl, "/users", user_id: u64);
+
+#[test]
+fn typescript_module() {
+    let module = ConstModule::typescript()
+        .signals::<ExportSignals>()
+        .url::<ExportUserUrl>()
+        .to_string();
+
+    assert_eq!(
+        module,
+        concat!(
+            "// Generated by bluth. Do not edit.\n",
+            "\n",
+            "export const ExportSignals = {\n",
+            "  UserName: \"userName\",\n",
+            "  PageNumber: \"pageNum\",\n",
+            "} as const;\n",
+            "\n",
+            "export const ExportUserUrl = \"/users/{user_id}\";\n",
+        )
+    );
+    assert_eq!(
+        ExportSignals::UserName(String::new()).signal_name(),
+        "userName"
+    );
+    assert_eq!(ExportSignals::PageNumber(2).signal_name(), "pageNum");
+    assert_eq!(ExportUserUrl::new(7).path(), "/users/7");
+}
+
+#[test]
+fn javascript_module_written_to_disk() {
+    let path = std::env::temp_dir().join(format!("bluth-codegen-{}.js", std::process::id()));
+    let module = ConstModule::javascript().signals::<ExportSignals>();
+    module.write_to(&path).unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(written.contains("  PageNumber: \"pageNum\",\n};\n"));
+}
@@ -45,3 +45,272 @@ fn component_list() {
 
     assert_eq!(html, "<div><ul><li>World</li><li>Hello</li></ul></div>");
 }
+
+#[test]
+fn slices_arrays_and_deques() {
+    use std::collections::{BTreeSet, VecDeque};
+
+    #[derive(Element)]
+    #[element("li")]
+    struct Item(&'static str);
+
+    #[derive(Element)]
+    #[element("div")]
+    struct Lists<'a> {
+        #[element("ul")]
+        slice: &'a [Item],
+
+        #[element("ol")]
+        array: [Item; 2],
+
+        #[element("p")]
+        queue: VecDeque<String>,
+
+        #[element(iter)]
+        tags: BTreeSet<&'static str>,
+    }
+
+    let items = [Item("a"), Item("b")];
+    let lists = Lists {
+        slice: &items,
+        array: [Item("c"), Item("d")],
+        queue: VecDeque::from(["x & y".to_string()]),
+        tags: BTreeSet::from(["z", "y"]),
+    };
+
+    assert_eq!(
+        lists.to_string(),
+        concat!(
+            "<div><ul><li>a</li><li>b</li></ul>",
+            "<ol><li>c</li><li>d</li></ol>",
+            "<p>x &amp; y</p>",
+            "yz</div>"
+        )
+    );
+}
+
+#[test]
+fn nested_option_and_vec() {
+    #[derive(Element)]
+    #[element("div")]
+    struct Tags {
+        #[element("ul")]
+        tags: Option<Vec<String>>,
+
+        #[element("ol")]
+        #[map_or("none")]
+        extra: Option<Vec<&'static str>>,
+
+        #[element("p")]
+        maybe: Vec<Option<String>>,
+    }
+
+    let tags = Tags {
+        tags: Some(vec!["a".into(), "<b>".into()]),
+        extra: None,
+        maybe: vec![Some("x".into()), None, Some("y".into())],
+    };
+    assert_eq!(
+        tags.to_string(),
+        "<div><ul>a&lt;b&gt;</ul><ol>none</ol><p>xy</p></div>"
+    );
+
+    let tags = Tags {
+        tags: None,
+        extra: Some(vec!["c"]),
+        maybe: vec![None],
+    };
+    assert_eq!(tags.to_string(), "<div><ul></ul><ol>c</ol><p></p></div>");
+}
+
+#[test]
+fn keyed_items_emit_morph_keys() {
+    #[derive(Element)]
+    #[element("li", key = id)]
+    #[attr(class = "todo")]
+    struct Todo {
+        id: String,
+
+        #[element]
+        title: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("tr", data_key = self.row.0)]
+    struct Row {
+        row: (u32, &'static str),
+
+        #[element("td", key = cell_id)]
+        cell: &'static str,
+
+        cell_id: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("ul")]
+    struct Todos {
+        #[element]
+        items: Vec<Todo>,
+    }
+
+    let todos = Todos {
+        items: vec![
+            Todo {
+                id: "todo-1".into(),
+                title: "Write",
+            },
+            Todo {
+                id: "todo-\"2\"".into(),
+                title: "Ship",
+            },
+        ],
+    };
+    assert_eq!(
+        todos.to_string(),
+        r#"<ul><li id="todo-1" class="todo">Write</li><li id="todo-&quot;2&quot;" class="todo">Ship</li></ul>"#
+    );
+
+    let row = Row {
+        row: (7, "x"),
+        cell: "a",
+        cell_id: "cell-7",
+    };
+    assert_eq!(
+        row.to_string(),
+        r#"<tr data-key="7"><td id="cell-7">a</td></tr>"#
+    );
+}
+
+#[test]
+fn empty_collection_placeholder() {
+    #[derive(Element)]
+    #[element("tr")]
+    #[attr(class = "empty")]
+    struct EmptyRow;
+
+    #[derive(Element)]
+    #[element("tr")]
+    struct Row {
+        #[element("td")]
+        name: String,
+    }
+
+    #[derive(Element)]
+    #[element("table")]
+    struct Table {
+        #[element("tbody", empty = EmptyRow)]
+        rows: Vec<Row>,
+        #[element("tfoot", empty = "none")]
+        notes: Option<Vec<String>>,
+    }
+
+    let empty = Table {
+        rows: vec![],
+        notes: None,
+    };
+    assert_eq!(
+        empty.to_string(),
+        r#"<table><tbody><tr class="empty"></tr></tbody><tfoot>none</tfoot></table>"#
+    );
+
+    let full = Table {
+        rows: vec![Row { name: "Ada".into() }],
+        notes: Some(vec![]),
+    };
+    assert_eq!(
+        full.to_string(),
+        "<table><tbody><tr><td>Ada</td></tr></tbody><tfoot>none</tfoot></table>"
+    );
+}
+
+#[test]
+fn enumerate_exposes_index() {
+    #[derive(Element)]
+    #[element("ol")]
+    struct Ranking {
+        #[element("li", enumerate)]
+        #[attr(class = "row-{index_parity}", "data-rank" = "{index}", "data-list" = "{name}")]
+        entries: Vec<String>,
+        #[element("p", enumerate)]
+        #[format("{}. {item}", index + 1)]
+        notes: Vec<&'static str>,
+        name: String,
+    }
+
+    let ranking = Ranking {
+        entries: vec!["Ada".into(), "<Bob>".into()],
+        notes: vec!["first", "second"],
+        name: "top".into(),
+    };
+
+    assert_eq!(
+        ranking.to_string(),
+        concat!(
+            r#"<ol><li class="row-even" data-rank="0" data-list="top">Ada</li>"#,
+            r#"<li class="row-odd" data-rank="1" data-list="top">&lt;Bob&gt;</li>"#,
+            "<p>1. first</p><p>2. second</p></ol>"
+        )
+    );
+}
+
+#[test]
+fn enumerate_exposes_boundaries() {
+    #[derive(Element)]
+    #[element("nav")]
+    struct Crumbs {
+        #[element("a", enumerate)]
+        #[attr(rounded_l if is_first, rounded_r if is_last, aria_current if is_last)]
+        #[attr("data-only" = "{is_only}")]
+        links: Vec<&'static str>,
+    }
+
+    let crumbs = Crumbs {
+        links: vec!["Home", "Docs", "Page"],
+    };
+    assert_eq!(
+        crumbs.to_string(),
+        concat!(
+            r#"<nav><a rounded-l data-only="false">Home</a><a data-only="false">Docs</a>"#,
+            r#"<a rounded-r aria-current data-only="false">Page</a></nav>"#
+        )
+    );
+
+    let single = Crumbs {
+        links: vec!["Home"],
+    };
+    assert_eq!(
+        single.to_string(),
+        r#"<nav><a rounded-l rounded-r aria-current data-only="true">Home</a></nav>"#
+    );
+}
+
+#[test]
+fn chunk_groups_items_into_rows() {
+    #[derive(Element)]
+    #[element("div")]
+    struct Card {
+        #[element]
+        title: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("section")]
+    struct Grid {
+        #[element("div", chunk = 2)]
+        #[attr(class = "row", "data-row" = "{index}", last if is_last)]
+        cards: Vec<Card>,
+    }
+
+    let grid = Grid {
+        cards: ["a", "b", "c"].map(|title| Card { title }).into(),
+    };
+
+    assert_eq!(
+        grid.to_string(),
+        concat!(
+            r#"<section><div class="row" data-row="0"><div>a</div><div>b</div></div>"#,
+            r#"<div class="row" data-row="1" last><div>c</div></div></section>"#
+        )
+    );
+    assert_eq!(Grid { cards: vec![] }.to_string(), "<section></section>");
+}
@@ -0,0 +1,208 @@
+use crate::components::{Toast, ToastQueue, ToastRegion};
+use crate::tests::voids;
+use std::time::Duration;
+
+#[test]
+fn toast_levels() {
+    assert_eq!(
+        Toast::info("Saved").to_string(),
+        "<div class=\"toast toast-info\" role=\"status\" data-init=\"setTimeout(() =&gt; el.remove(), 5000)\">Saved</div>"
+    );
+    assert_eq!(
+        Toast::error("Failed")
+            .dismiss_after(Duration::from_secs(10))
+            .to_string(),
+        "<div class=\"toast toast-error\" role=\"alert\" data-init=\"setTimeout(() =&gt; el.remove(), 10000)\">Failed</div>"
+    );
+}
+
+#[test]
+fn toast_region() {
+    assert_eq!(
+        ToastRegion {}.to_string(),
+        "<div id=\"toasts\" aria-live=\"polite\"></div>"
+    );
+}
+
+#[test]
+fn toast_queue_appends_into_region() {
+    let mut queue = ToastQueue::new();
+    assert!(queue.is_empty());
+    queue
+        .push(Toast::success("One"))
+        .push(Toast::warning("Two"));
+
+    let patch = queue.into_patch().to_string();
+
+    assert!(patch.starts_with(
+        "event: datastar-patch-elements\ndata: selector #toasts\ndata: mode append\n"
+    ));
+    assert!(patch.contains("data: elements <div class=\"toast toast-success\""));
+    assert!(patch.contains("data: elements <div class=\"toast toast-warning\""));
+}
+
+#[derive(crate::Signal)]
+pub enum ModalSignals {
+    ConfirmOpen(bool),
+}
+
+#[test]
+fn modal_binds_open_state_to_signal() {
+    use crate::components::Modal;
+
+    let modal = Modal::<ConfirmOpen, _>::new("Are you sure?");
+    let id = modal.id().to_string();
+
+    assert_eq!(Modal::<ConfirmOpen, &str>::open(), "$confirmOpen = true");
+    assert_eq!(
+        modal.to_string(),
+        format!(
+            concat!(
+                "<dialog id=\"{}\" aria-modal=\"true\"",
+                " data-effect=\"$confirmOpen ? (el.open || el.showModal()) : (el.open &amp;&amp; el.close())\"",
+                " data-on:close=\"$confirmOpen = false\"",
+                " data-on:keydown__window=\"evt.key === 'Escape' &amp;&amp; ($confirmOpen = false)\"",
+                " data-on:click=\"evt.target === el &amp;&amp; ($confirmOpen = false)\">",
+                "Are you sure?</dialog>"
+            ),
+            id
+        )
+    );
+}
+
+crate::define_url!(ItemsPageUrl, "/items", page: u32);
+
+#[test]
+fn load_more_sentinel() {
+    use crate::components::LoadMore;
+
+    assert_eq!(ItemsPageUrl::PATTERN, "/items/{page}");
+    assert_eq!(
+        LoadMore::new(&ItemsPageUrl::new(2)).to_string(),
+        "<div id=\"load-more\" data-on-intersect__once=\"@get('/items/2')\"></div>"
+    );
+    assert_eq!(
+        LoadMore::new(&ItemsPageUrl::new(3))
+            .repeat()
+            .threshold(50)
+            .to_string(),
+        "<div id=\"load-more\" data-on-intersect__threshold.50=\"@get('/items/3')\"></div>"
+    );
+}
+
+#[test]
+fn load_more_page_appends_and_advances() {
+    use crate::components::{LoadMore, LoadMorePage};
+
+    let page = LoadMorePage::new("#items", vec!["<li>a</li>", "<li>b</li>"])
+        .next(LoadMore::new(&ItemsPageUrl::new(2)))
+        .to_string();
+
+    assert_eq!(
+        page,
+        concat!(
+            "event: datastar-patch-elements\n",
+            "data: selector #items\n",
+            "data: mode append\n",
+            "data: elements <li>a</li>\n",
+            "data: elements <li>b</li>\n",
+            "\n",
+            "event: datastar-patch-elements\n",
+            "data: selector #load-more\n",
+            "data: mode outer\n",
+            "data: elements <div id=\"load-more\" data-on-intersect__once=\"@get('/items/2')\"></div>\n",
+            "\n",
+        )
+    );
+
+    let last = LoadMorePage::new("#items", vec!["<li>z</li>"]).to_string();
+    assert!(last.ends_with("data: selector #load-more\ndata: mode remove\n\n"));
+}
+
+#[derive(crate::Signal)]
+pub enum FormControlSignals {
+    Color(String),
+    Agreed(bool),
+}
+
+#[test]
+fn form_controls_checked_state() {
+    use crate::components::{Checkbox, Radio, SelectOption};
+
+    let current = "green".to_string();
+    let options: String = ["red", "green"]
+        .into_iter()
+        .map(|value| SelectOption::new(value.to_string(), &current, value).to_string())
+        .collect();
+    assert_eq!(
+        options,
+        "<option value=\"red\">red</option><option value=\"green\" selected>green</option>"
+    );
+
+    assert_eq!(
+        Radio::new(Color, "green".to_string(), &current).to_string(),
+        voids("<input type=\"radio\" name=\"color\" value=\"green\" data-bind=\"color\" checked/>")
+    );
+    assert_eq!(
+        Radio::new(Color, "red".to_string(), &current).to_string(),
+        voids("<input type=\"radio\" name=\"color\" value=\"red\" data-bind=\"color\"/>")
+    );
+    assert_eq!(
+        Checkbox::new(Agreed, false).to_string(),
+        voids("<input type=\"checkbox\" name=\"agreed\" data-bind=\"agreed\"/>")
+    );
+}
+
+#[cfg(feature = "strum")]
+#[test]
+fn select_from_enum() {
+    use crate::components::Select;
+
+    #[derive(PartialEq, strum::EnumIter, strum::AsRefStr)]
+    #[strum(serialize_all = "kebab-case")]
+    enum Shipping {
+        Standard,
+        NextDay,
+    }
+
+    impl std::fmt::Display for Shipping {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Shipping::Standard => write!(f, "Standard (5 days)"),
+                Shipping::NextDay => write!(f, "Next day"),
+            }
+        }
+    }
+
+    assert_eq!(
+        Select::from_enum(&Shipping::NextDay)
+            .bind(Color)
+            .to_string(),
+        concat!(
+            "<select name=\"color\" data-bind=\"color\">",
+            "<option value=\"standard\">Standard (5 days)</option>",
+            "<option value=\"next-day\" selected>Next day</option>",
+            "</select>"
+        )
+    );
+}
+
+#[test]
+fn labeled_pairs_label_and_input_ids() {
+    use crate::components::{Checkbox, Labeled};
+
+    let labeled = Labeled::new("I agree", Checkbox::new(Agreed, true));
+    let id = labeled.id().to_string();
+    assert!(id.starts_with("field-"));
+
+    assert_eq!(
+        labeled.to_string(),
+        voids(&format!(
+            "<label for=\"{id}\">I agree</label><input type=\"checkbox\" name=\"agreed\" data-bind=\"agreed\" checked id=\"{id}\"/>"
+        ))
+    );
+    assert_ne!(
+        Labeled::new("Other", Checkbox::new(Agreed, false)).id(),
+        labeled.id()
+    );
+}
@@ -0,0 +1,131 @@
+use crate::cookie::{CookieError, SignalCookie, mirror};
+use crate::testing::TestApp;
+use crate::{Signal, SignalExtractor, SignalSelector};
+use axum::{Router, http::header, response::IntoResponse, routing::get};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Signal)]
+pub enum CookieSignals {
+    Theme(String),
+    Draft(String),
+}
+
+fn cookie() -> SignalCookie {
+    SignalCookie::new("test-secret").allow::<Theme>()
+}
+
+#[test]
+fn encodes_only_allowed_signals_and_rejects_tampering() {
+    let cookie = cookie();
+    let header = cookie
+        .set_cookie(&[
+            CookieSignals::Theme("dark".to_string()),
+            CookieSignals::Draft("secret draft".to_string()),
+        ])
+        .unwrap();
+    assert!(header.starts_with("bluth-signals="));
+    assert!(header.ends_with("; Path=/; Max-Age=604800; HttpOnly; SameSite=Lax; Secure"));
+    let local = cookie
+        .clone()
+        .secure(false)
+        .set_cookie(&[CookieSignals::Theme("dark".to_string())])
+        .unwrap();
+    assert!(local.ends_with("; HttpOnly; SameSite=Lax"));
+
+    let value = header
+        .trim_start_matches("bluth-signals=")
+        .split(';')
+        .next()
+        .unwrap();
+    let signals = cookie.decode(value).unwrap();
+    assert_eq!(signals.get(Theme::NAME), Some(&serde_json::json!("dark")));
+    assert!(!signals.contains_key(Draft::NAME));
+
+    let (payload, signature) = value.split_once('.').unwrap();
+    assert!(
+        cookie
+            .decode(&format!("{}x.{}", payload, signature))
+            .is_none()
+    );
+    assert!(
+        SignalCookie::new("other")
+            .allow::<Theme>()
+            .decode(value)
+            .is_none()
+    );
+
+    let small = cookie.clone().max_bytes(16);
+    assert!(matches!(
+        small.set_cookie(&[CookieSignals::Theme("dark".to_string())]),
+        Err(CookieError::TooLarge { max_bytes: 16, .. })
+    ));
+}
+
+#[test]
+fn rejects_expired_cookies() {
+    let cookie = cookie().max_age(Duration::from_secs(60));
+    let signals = serde_json::json!({ "theme": "dark" });
+
+    let fresh = cookie.encode(&signals).unwrap();
+    assert!(cookie.decode(&fresh).is_some());
+
+    let issued_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 120;
+    let replayed = cookie.encode_at(&signals, issued_at).unwrap();
+    assert!(cookie.decode(&replayed).is_none());
+    assert!(
+        cookie
+            .clone()
+            .max_age(Duration::from_secs(600))
+            .decode(&replayed)
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn extractor_falls_back_to_cookie() -> Result<(), anyhow::Error> {
+    let _ = cookie().install();
+    assert!(matches!(
+        cookie().install(),
+        Err(CookieError::AlreadyInstalled)
+    ));
+
+    let response = mirror("ok", &[CookieSignals::Theme("light".to_string())]).into_response();
+    let set_cookie = response
+        .headers()
+        .get(header::SET_COOKIE)
+        .expect("cookie mirrored")
+        .to_str()?
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string();
+
+    let app = TestApp::new(Router::new().route(
+        "/settings",
+        get(|SignalExtractor(theme): SignalExtractor<Theme>| async move { theme }),
+    ));
+
+    let theme = app
+        .get("/settings")
+        .datastar()
+        .header(header::COOKIE.as_str(), &format!("other=1; {}", set_cookie))
+        .send()
+        .await
+        .body;
+    assert_eq!(theme, "light");
+
+    let theme = app
+        .get("/settings?datastar=%7B%22theme%22%3A%22blue%22%7D")
+        .datastar()
+        .header(header::COOKIE.as_str(), &set_cookie)
+        .send()
+        .await
+        .body;
+    assert_eq!(theme, "blue");
+
+    Ok(())
+}
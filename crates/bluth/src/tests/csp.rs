@@ -0,0 +1,126 @@
+use crate::csp::CspViolation;
+use crate::{Element, Render, RenderError};
+
+#[test]
+fn inline_handler_attr_is_rejected() {
+    #[derive(Element)]
+    #[element("button")]
+    #[attr(onclick = "pay()", "data-on:click" = "@post('/pay')")]
+    struct PayButton {}
+
+    assert_eq!(
+        PayButton {}.try_render_to_string(),
+        Err(RenderError::Csp(CspViolation::InlineHandler(
+            "onclick".to_string()
+        )))
+    );
+}
+
+#[test]
+fn inline_handler_field_is_rejected() {
+    #[derive(Element)]
+    #[element("a")]
+    struct Link {
+        #[attr]
+        href: &'static str,
+
+        #[attr(name = "onmouseover")]
+        hover: &'static str,
+    }
+
+    let link = Link {
+        href: "/",
+        hover: "track()",
+    };
+    assert!(matches!(
+        link.try_render_to_string(),
+        Err(RenderError::Csp(CspViolation::InlineHandler(_)))
+    ));
+}
+
+#[test]
+fn scripts_require_nonce() {
+    #[derive(Element)]
+    #[element("script")]
+    struct Inline {
+        #[attr]
+        nonce: Option<&'static str>,
+
+        #[element]
+        body: &'static str,
+    }
+
+    let with_nonce = Inline {
+        nonce: Some("r4nd0m"),
+        body: "go()",
+    };
+    assert_eq!(
+        with_nonce.try_render_to_string(),
+        Ok("<script nonce=\"r4nd0m\">go()</script>".to_string())
+    );
+
+    let without_nonce = Inline {
+        nonce: None,
+        body: "go()",
+    };
+    assert_eq!(
+        without_nonce.try_render_to_string(),
+        Err(RenderError::Csp(CspViolation::InlineScript))
+    );
+}
+
+#[test]
+fn dynamic_script_tags_require_nonce() {
+    #[derive(Element)]
+    #[element(tag = tag)]
+    struct Dynamic {
+        tag: &'static str,
+
+        #[attr]
+        nonce: Option<&'static str>,
+
+        #[element]
+        body: &'static str,
+    }
+
+    for tag in ["script", "SCRIPT"] {
+        let inline = Dynamic {
+            tag,
+            nonce: None,
+            body: "go()",
+        };
+        assert_eq!(
+            inline.try_render_to_string(),
+            Err(RenderError::Csp(CspViolation::InlineScript))
+        );
+    }
+
+    let with_nonce = Dynamic {
+        tag: "script",
+        nonce: Some("r4nd0m"),
+        body: "go()",
+    };
+    assert_eq!(
+        with_nonce.try_render_to_string(),
+        Ok("<script nonce=\"r4nd0m\">go()</script>".to_string())
+    );
+    let other = Dynamic {
+        tag: "p",
+        nonce: None,
+        body: "go()",
+    };
+    assert_eq!(other.try_render_to_string(), Ok("<p>go()</p>".to_string()));
+}
+
+#[test]
+fn external_scripts_are_allowed() {
+    let script = crate::Script {
+        src: "/app.js",
+        async_: true,
+        type_: "module",
+    };
+    assert_eq!(
+        script.try_render_to_string(),
+        Ok("<script src=\"/app.js\" async type=\"module\"></script>".to_string())
+    );
+}
@@ -1,3 +1,4 @@
+use crate::tests::voids;
 use crate::{Element, Signal, SignalSelector, SignalValue};
 
 #[derive(Signal)]
@@ -32,7 +33,9 @@ fn data_bind_with_signal_selector() {
 
     assert_eq!(
         html,
-        "<input data-bind=\"userName\" data-on:keydown=\"@get(/hello)\" value=\"John Doe\"/>"
+        voids(
+            "<input data-bind=\"userName\" data-on:keydown=\"@get(/hello)\" value=\"John Doe\"/>"
+        )
     );
 }
 
@@ -56,7 +59,7 @@ fn data_bind_with_field_bound_signal() {
 
     assert_eq!(
         html,
-        "<input data-bind=\"searchTerm\" id=\"search-input\"/>"
+        voids("<input data-bind=\"searchTerm\" id=\"search-input\"/>")
     );
 }
 
@@ -117,7 +120,7 @@ fn data_bind_with_nested_element_signals() {
 
     assert_eq!(
         html,
-        "<div data-bind=\"searchTerm\"></div><input data-bind=\"userName\"/>"
+        voids("<div data-bind=\"searchTerm\"></div><input data-bind=\"userName\"/>")
     );
 }
 
@@ -134,7 +137,7 @@ fn data_bind_with_string_literal() {
 
     let html = hello.to_string();
 
-    assert_eq!(html, "<input data-bind=\"legacySignal\"/>");
+    assert_eq!(html, voids("<input data-bind=\"legacySignal\"/>"));
 }
 
 #[test]
@@ -343,3 +346,532 @@ fn merge_signals() {
     assert_eq!(merged["userName"], serde_json::json!("john"));
     assert_eq!(merged["pageNum"], serde_json::json!(3));
 }
+
+#[test]
+fn progress_bar_binds_signals() {
+    use crate::datastar::Progress;
+
+    let html = Progress::bar(42.5, "Uploading").to_string();
+
+    assert_eq!(
+        html,
+        "<div class=\"progress\"><progress max=\"100\" value=\"42.5\" data-attr:value=\"$progressPercent\"></progress><span data-text=\"$progressStatus\">Uploading</span></div>"
+    );
+}
+
+#[test]
+fn progress_update_signals() {
+    use crate::datastar::Progress;
+
+    assert_eq!(
+        Progress::update(150.0, "Done").to_string(),
+        "event: datastar-patch-signals\ndata: signals {\"progressPercent\":100.0,\"progressStatus\":\"Done\"}\n\n"
+    );
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn progress_stream_is_throttled() {
+    use crate::datastar::Progress;
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let events: Vec<String> = Progress::throttle(
+        futures_util::stream::iter([10.0, 20.0, 30.0]),
+        Duration::from_secs(60),
+    )
+    .map(|patch| patch.to_string())
+    .collect()
+    .await;
+
+    assert_eq!(
+        events,
+        vec![
+            "event: datastar-patch-signals\ndata: signals {\"progressPercent\":10.0}\n\n",
+            "event: datastar-patch-signals\ndata: signals {\"progressPercent\":30.0}\n\n",
+        ]
+    );
+
+    let events: Vec<String> = Progress::throttle(
+        futures_util::stream::iter([10.0, 50.0, 100.0]),
+        Duration::from_secs(60),
+    )
+    .map(|patch| patch.to_string())
+    .collect()
+    .await;
+
+    assert_eq!(events.len(), 2);
+    assert!(events[1].contains("\"progressPercent\":100.0"));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn progress_stream_flushes_the_trailing_value_when_the_window_elapses() {
+    use crate::datastar::Progress;
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<f32>();
+    let progress = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|value| (value, receiver))
+    });
+    let mut events = Box::pin(Progress::throttle(progress, Duration::from_millis(20)));
+
+    sender.send(10.0).unwrap();
+    sender.send(20.0).unwrap();
+    sender.send(30.0).unwrap();
+    let first = events.next().await.unwrap().to_string();
+    assert!(first.contains("\"progressPercent\":10.0"));
+
+    // The sender stays open, so only the window timer can release 30.0.
+    let trailing = tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .to_string();
+    assert!(trailing.contains("\"progressPercent\":30.0"));
+    drop(sender);
+    assert!(events.next().await.is_none());
+}
+
+#[test]
+fn selector_attribute_consts() {
+    assert_eq!(UserName::DATA_BIND, "data-bind=\"userName\"");
+    assert_eq!(UserName::JSON_KEY, "\"userName\"");
+    assert_eq!(UserName::JS_REF, "$userName");
+    assert_eq!(PageNumber::DATA_BIND, "data-bind=\"pageNum\"");
+    assert_eq!(PageNumber::JS_REF, "$pageNum");
+}
+
+#[test]
+fn bind_emits_name_and_initial_value() {
+    #[derive(Signal)]
+    pub enum FormSignals {
+        Email(String),
+        Subscribed(bool),
+        Age(Option<u32>),
+    }
+
+    #[derive(Element)]
+    struct Form {
+        email: SignalValue<Email>,
+        subscribed: SignalValue<Subscribed>,
+        age: SignalValue<Age>,
+
+        #[element("input")]
+        #[attr("type" = "email", bind = email)]
+        email_input: (),
+
+        #[element("input")]
+        #[attr("type" = "checkbox", bind = subscribed)]
+        subscribed_input: (),
+
+        #[element("input")]
+        #[attr(bind = age)]
+        age_input: (),
+    }
+
+    let form = Form {
+        email: SignalValue::new("gob@bluth.com\"".to_string()),
+        subscribed: SignalValue::new(true),
+        age: SignalValue::new(None),
+        email_input: (),
+        subscribed_input: (),
+        age_input: (),
+    };
+
+    assert_eq!(
+        form.to_string(),
+        voids(concat!(
+            "<input type=\"email\" name=\"email\" data-bind=\"email\" value=\"gob@bluth.com&quot;\"/>",
+            "<input type=\"checkbox\" name=\"subscribed\" data-bind=\"subscribed\" checked/>",
+            "<input name=\"age\" data-bind=\"age\"/>"
+        ))
+    );
+}
+
+#[test]
+fn patch_elements_split_above_threshold() {
+    use crate::datastar::{PatchElements, PatchMode};
+
+    let rows: Vec<String> = (1..=5).map(|n| format!("<tr><td>{n}</td></tr>")).collect();
+    let patch = PatchElements::new(rows)
+        .selector("#rows")
+        .mode(PatchMode::Inner)
+        .split_above(40)
+        .to_string();
+
+    assert_eq!(
+        patch,
+        concat!(
+            "event: datastar-patch-elements\n",
+            "data: selector #rows\n",
+            "data: mode inner\n",
+            "data: elements <tr><td>1</td></tr>\n",
+            "data: elements <tr><td>2</td></tr>\n",
+            "\n",
+            "event: datastar-patch-elements\n",
+            "data: selector #rows\n",
+            "data: mode append\n",
+            "data: elements <tr><td>3</td></tr>\n",
+            "data: elements <tr><td>4</td></tr>\n",
+            "\n",
+            "event: datastar-patch-elements\n",
+            "data: selector #rows\n",
+            "data: mode append\n",
+            "data: elements <tr><td>5</td></tr>\n",
+            "\n",
+        )
+    );
+
+    let unsplit = PatchElements::new(vec!["<p>a</p>", "<p>b</p>"])
+        .split_above(1)
+        .to_string();
+    assert_eq!(unsplit.matches("event:").count(), 1);
+}
+
+#[test]
+fn patch_elements_split_keeps_order_for_every_mode() {
+    use crate::datastar::{PatchElements, PatchMode};
+
+    let split = |mode: PatchMode| {
+        let patch = PatchElements::new(vec!["<p>1</p>", "<p>2</p>", "<p>3</p>"])
+            .selector("#list")
+            .mode(mode)
+            .split_above(1)
+            .to_string();
+        patch
+            .split("\n\n")
+            .filter(|event| !event.is_empty())
+            .map(|event| {
+                let line = |prefix: &str| {
+                    event
+                        .lines()
+                        .find_map(|line| line.strip_prefix(prefix))
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                (line("data: mode "), line("data: elements "))
+            })
+            .collect::<Vec<_>>()
+    };
+    let events = |expected: &[(&str, &str)]| {
+        expected
+            .iter()
+            .map(|(mode, element)| (mode.to_string(), element.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        split(PatchMode::Inner),
+        events(&[
+            ("inner", "<p>1</p>"),
+            ("append", "<p>2</p>"),
+            ("append", "<p>3</p>")
+        ])
+    );
+    assert_eq!(
+        split(PatchMode::Append),
+        events(&[
+            ("append", "<p>1</p>"),
+            ("append", "<p>2</p>"),
+            ("append", "<p>3</p>")
+        ])
+    );
+    assert_eq!(
+        split(PatchMode::Before),
+        events(&[
+            ("before", "<p>1</p>"),
+            ("before", "<p>2</p>"),
+            ("before", "<p>3</p>")
+        ])
+    );
+    assert_eq!(
+        split(PatchMode::Prepend),
+        events(&[
+            ("prepend", "<p>3</p>"),
+            ("prepend", "<p>2</p>"),
+            ("prepend", "<p>1</p>")
+        ])
+    );
+    assert_eq!(
+        split(PatchMode::After),
+        events(&[
+            ("after", "<p>3</p>"),
+            ("after", "<p>2</p>"),
+            ("after", "<p>1</p>")
+        ])
+    );
+    for mode in [PatchMode::Outer, PatchMode::Replace] {
+        assert_eq!(split(mode).len(), 1, "{mode} patches must not be split");
+    }
+
+    let whole =
+        |patch: PatchElements<&str>| patch.split_above(1).to_string().matches("event:").count();
+    let elements = || vec!["<p>1</p>", "<p>2</p>", "<p>3</p>"];
+    assert_eq!(whole(PatchElements::new(elements()).selector("#list")), 1);
+    assert_eq!(
+        whole(PatchElements::new(elements()).mode(PatchMode::Append)),
+        1
+    );
+}
+
+#[cfg(feature = "strict-patches")]
+#[test]
+fn strict_patches_check_selector_and_mode() {
+    use crate::datastar::{PatchConsistencyError, PatchElements, PatchMode};
+
+    assert_eq!(
+        PatchElements::new(vec!["<div id=\"feed\"></div>"])
+            .selector("#feed")
+            .check(),
+        Ok(())
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<li>a</li>"])
+            .selector("#feed")
+            .mode(PatchMode::Append)
+            .check(),
+        Ok(())
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<div id=\"other\"></div>"])
+            .selector("#feed")
+            .check(),
+        Err(PatchConsistencyError::SelectorMismatch {
+            selector: "#feed".to_string(),
+            id: "other".to_string(),
+        })
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<div></div>"])
+            .selector("#feed")
+            .mode(PatchMode::Remove)
+            .check(),
+        Err(PatchConsistencyError::RemoveWithElements)
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<div id=\"feed\"></div>"])
+            .selector("#feed")
+            .split_above(1)
+            .check(),
+        Err(PatchConsistencyError::Unsplittable)
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<li>a</li>"])
+            .mode(PatchMode::Append)
+            .split_above(1)
+            .check(),
+        Err(PatchConsistencyError::Unsplittable)
+    );
+    assert_eq!(
+        PatchElements::new(vec!["<li>a</li>"])
+            .selector("#feed")
+            .mode(PatchMode::Append)
+            .split_above(1)
+            .check(),
+        Ok(())
+    );
+}
+
+#[cfg(feature = "strict-patches")]
+#[test]
+fn strict_patches_still_render_inconsistent_patches() {
+    use crate::datastar::PatchElements;
+
+    assert_eq!(
+        PatchElements::new(vec!["<div id=\"other\"></div>"])
+            .selector("#feed")
+            .to_string(),
+        "event: datastar-patch-elements\ndata: selector #feed\ndata: elements <div id=\"other\"></div>\n\n"
+    );
+}
+
+mod private_signals {
+    use crate::{Signal, SignalStore};
+
+    #[derive(Signal)]
+    #[signal(vis = "pub(super)")]
+    pub(crate) enum PanelSignals {
+        Expanded(bool),
+        #[signal(name = "tab", vis = "pub(crate)", doc_hidden)]
+        ActiveTab(u8),
+    }
+
+    #[derive(SignalStore)]
+    #[signal(vis = "pub(super)")]
+    pub(super) struct Sidebar {
+        pub(super) pinned: bool,
+    }
+}
+
+#[test]
+fn signal_selector_visibility_override() {
+    use private_signals::{ActiveTab, Expanded, Pinned, Sidebar};
+
+    assert_eq!(Expanded::NAME, "expanded");
+    assert_eq!(ActiveTab::NAME, "tab");
+    assert_eq!(Pinned::NAME, "pinned");
+    assert_eq!(Sidebar { pinned: true }.signals().len(), 1);
+}
+
+mod first_counter {
+    #[derive(crate::Signal)]
+    pub enum CounterSignals {
+        #[signal(name = "dupCount")]
+        Count(i32),
+    }
+}
+
+mod second_counter {
+    #[derive(crate::Signal)]
+    pub enum CounterSignals {
+        #[signal(name = "dupCount")]
+        Count(i32),
+    }
+}
+
+#[test]
+fn signal_name_collisions_in_one_render() {
+    use crate::signal::unique_names;
+    use std::fmt::Write as _;
+
+    #[derive(Element)]
+    #[element("input")]
+    #[attr(data_bind = first_counter::Count)]
+    struct First {}
+
+    #[derive(Element)]
+    #[element("input")]
+    #[attr(data_bind = second_counter::Count)]
+    struct Second {}
+
+    let mut twice = String::new();
+    unique_names(|| write!(twice, "{}{}", First {}, First {})).unwrap();
+    assert_eq!(
+        twice,
+        voids(r#"<input data-bind="dupCount"/><input data-bind="dupCount"/>"#)
+    );
+    assert_eq!(format!("{}{}", First {}, Second {}), twice);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn signal_name_collisions_fail_the_render() {
+    use crate::signal::unique_names;
+    use crate::{Render, RenderError};
+
+    #[derive(Element)]
+    #[element("input")]
+    #[attr(data_bind = first_counter::Count)]
+    struct First {}
+
+    #[derive(Element)]
+    #[element("input")]
+    #[attr(data_bind = second_counter::Count)]
+    struct Second {}
+
+    struct Page<A, B>(A, B);
+    impl<A: std::fmt::Display, B: std::fmt::Display> Render for Page<A, B> {
+        fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            unique_names(|| write!(f, "{}{}", self.0, self.1))
+        }
+    }
+
+    assert!(Page(First {}, First {}).try_render_to_string().is_ok());
+    let Err(RenderError::SignalNameCollision(collision)) =
+        Page(First {}, Second {}).try_render_to_string()
+    else {
+        panic!("expected a signal name collision");
+    };
+    assert_eq!(
+        collision.to_string(),
+        concat!(
+            "signal name `dupCount` is used by both ",
+            "`bluth::tests::datastar::first_counter::Count` and ",
+            "`bluth::tests::datastar::second_counter::Count`"
+        )
+    );
+
+    let names = unique_names(|| {
+        assert_eq!(first_counter::Count.as_ref(), "dupCount");
+        assert_eq!(second_counter::Count.as_ref(), "dupCount");
+        Ok(())
+    });
+    assert!(names.is_err());
+}
+
+#[cfg(feature = "linkme")]
+#[test]
+fn signal_names_registered_at_link_time() {
+    use crate::signal::{SIGNAL_NAMES, check_signal_names};
+
+    assert!(SIGNAL_NAMES.contains(&("dupCount", "bluth::tests::datastar::first_counter::Count")));
+    assert!(check_signal_names().is_err());
+}
+
+#[test]
+fn patch_mode_and_namespace_round_trip() {
+    use crate::datastar::{PatchMode, PatchNamespace};
+    use strum::VariantArray;
+
+    for mode in PatchMode::VARIANTS {
+        let name = mode.to_string();
+        assert_eq!(name, mode.as_ref());
+        assert_eq!(name.parse::<PatchMode>().unwrap(), *mode);
+        assert_eq!(name.to_uppercase().parse::<PatchMode>().unwrap(), *mode);
+
+        let json = serde_json::to_string(mode).unwrap();
+        assert_eq!(json, format!("\"{name}\""));
+        assert_eq!(serde_json::from_str::<PatchMode>(&json).unwrap(), *mode);
+    }
+
+    for namespace in PatchNamespace::VARIANTS {
+        let name = namespace.to_string();
+        assert_eq!(name, namespace.as_ref());
+        assert_eq!(name.parse::<PatchNamespace>().unwrap(), *namespace);
+        assert_eq!(
+            name.to_lowercase().parse::<PatchNamespace>().unwrap(),
+            *namespace
+        );
+
+        let json = serde_json::to_string(namespace).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PatchNamespace>(&json).unwrap(),
+            *namespace
+        );
+    }
+
+    assert_eq!(PatchMode::Outer.to_string(), "outer");
+    assert!("sideways".parse::<PatchMode>().is_err());
+    assert!(serde_json::from_str::<PatchMode>("\"sideways\"").is_err());
+    assert!(serde_json::from_str::<PatchNamespace>("1").is_err());
+}
+
+#[test]
+fn patch_signals_null_handling_and_pretty() {
+    use crate::datastar::{NullSignals, PatchSignals};
+
+    let signals = || {
+        vec![
+            TestSignals::UserName("jo".to_string()),
+            TestSignals::SearchTerm(None),
+        ]
+    };
+
+    assert_eq!(
+        PatchSignals::new(signals()).to_string(),
+        "event: datastar-patch-signals\ndata: signals {\"searchTerm\":null,\"userName\":\"jo\"}\n\n"
+    );
+    assert_eq!(
+        PatchSignals::new(signals()).skip_nulls().to_string(),
+        "event: datastar-patch-signals\ndata: signals {\"userName\":\"jo\"}\n\n"
+    );
+    assert_eq!(
+        PatchSignals::new(signals())
+            .nulls(NullSignals::Delete)
+            .pretty(true)
+            .to_string(),
+        "event: datastar-patch-signals\ndata: signals {\ndata: signals   \"searchTerm\": null,\ndata: signals   \"userName\": \"jo\"\ndata: signals }\n\n"
+    );
+}
@@ -0,0 +1,75 @@
+use crate::Element;
+use crate::context::RenderContext;
+
+#[derive(Element)]
+#[element("form")]
+struct Checkout {
+    #[element("button")]
+    submit: &'static str,
+
+    #[element("div", if_flag = "express-pay")]
+    #[attr(class = "express")]
+    express: &'static str,
+}
+
+#[derive(Element)]
+#[element("nav")]
+enum Banner {
+    #[element("p", if_flag = "new-checkout")]
+    New(&'static str),
+
+    #[element("p")]
+    Classic(&'static str),
+}
+
+#[test]
+fn field_hidden_without_flag() {
+    let checkout = Checkout {
+        submit: "Pay",
+        express: "Pay faster",
+    };
+
+    assert_eq!(checkout.to_string(), "<form><button>Pay</button></form>");
+    assert_eq!(
+        RenderContext::new()
+            .flags(vec!["other"])
+            .render(|| checkout.to_string()),
+        "<form><button>Pay</button></form>"
+    );
+}
+
+#[test]
+fn field_shown_with_flag() {
+    let checkout = Checkout {
+        submit: "Pay",
+        express: "Pay faster",
+    };
+
+    let html = RenderContext::new()
+        .flags(vec!["express-pay"])
+        .render(|| checkout.to_string());
+
+    assert_eq!(
+        html,
+        "<form><button>Pay</button><div class=\"express\">Pay faster</div></form>"
+    );
+}
+
+#[test]
+fn variant_toggled_by_flag() {
+    let context = RenderContext::new().flags(
+        ["new-checkout".to_string()]
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>(),
+    );
+
+    assert_eq!(Banner::New("Try it").to_string(), "");
+    assert_eq!(
+        context.render(|| Banner::New("Try it").to_string()),
+        "<nav><p>Try it</p></nav>"
+    );
+    assert_eq!(
+        Banner::Classic("Hello").to_string(),
+        "<nav><p>Hello</p></nav>"
+    );
+}
@@ -0,0 +1,106 @@
+use crate::Signal;
+use crate::form::{FieldError, FieldErrorKind, NumberInput};
+use crate::signal::SignalMap;
+use crate::tests::voids;
+use serde_json::json;
+
+#[derive(Signal)]
+pub enum FormSignals {
+    Quantity(String),
+}
+
+fn signals(value: serde_json::Value) -> SignalMap {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+#[test]
+fn number_input_renders_bounds_and_value() {
+    let input = NumberInput::new(Quantity).min(1).max(10).value(3);
+
+    assert_eq!(
+        input.to_string(),
+        voids(
+            "<input type=\"number\" name=\"quantity\" data-bind=\"quantity\" min=\"1\" max=\"10\" value=\"3\"/>"
+        )
+    );
+}
+
+#[test]
+fn number_input_parses_signal_strings() {
+    let input = NumberInput::new(Quantity).min(1).max(10);
+
+    assert_eq!(input.parse(&signals(json!({"quantity": " 4 "}))), Ok(4));
+    assert_eq!(input.parse(&signals(json!({"quantity": 5}))), Ok(5));
+    assert_eq!(
+        input.parse(&signals(json!({"quantity": ""}))),
+        Err(FieldError {
+            signal: "quantity",
+            kind: FieldErrorKind::Missing,
+        })
+    );
+    assert_eq!(
+        input
+            .parse(&signals(json!({"quantity": "lots"})))
+            .map_err(|e| e.kind),
+        Err(FieldErrorKind::Invalid("lots".to_string()))
+    );
+
+    let error = input
+        .parse(&signals(json!({"quantity": "11"})))
+        .err()
+        .map(|e| e.to_string());
+    assert_eq!(error.as_deref(), Some("quantity must be at most 10"));
+}
+
+#[test]
+fn number_input_rejects_non_finite_floats() {
+    let input = NumberInput::new(Quantity).min(0.0).max(10.0);
+
+    for raw in ["NaN", "inf", "-infinity", "1e400"] {
+        assert_eq!(
+            input
+                .parse(&signals(json!({ "quantity": raw })))
+                .map_err(|e| e.kind),
+            Err(FieldErrorKind::Invalid(raw.to_string()))
+        );
+    }
+    assert_eq!(
+        NumberInput::new(Quantity)
+            .parse(&signals(json!({ "quantity": "inf" })))
+            .map_err(|e| e.kind),
+        Err::<f32, _>(FieldErrorKind::Invalid("inf".to_string()))
+    );
+    assert_eq!(input.parse(&signals(json!({ "quantity": "2.5" }))), Ok(2.5));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn date_input_round_trip() {
+    use crate::form::DateInput;
+    use chrono::NaiveDate;
+
+    #[derive(Signal)]
+    pub enum DateSignals {
+        StartDate(String),
+    }
+
+    let min = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
+    let input = DateInput::new(StartDate).min(min);
+
+    assert_eq!(
+        input.to_string(),
+        voids(
+            "<input type=\"date\" name=\"startDate\" data-bind=\"startDate\" min=\"2024-01-01\"/>"
+        )
+    );
+    assert_eq!(
+        input.parse(&signals(json!({"startDate": "2024-03-05"}))),
+        Ok(NaiveDate::from_ymd_opt(2024, 3, 5).unwrap_or_default())
+    );
+    assert_eq!(
+        input
+            .parse(&signals(json!({"startDate": "2023-12-31"})))
+            .map_err(|e| e.kind),
+        Err(FieldErrorKind::BelowMin("2024-01-01".to_string()))
+    );
+}
@@ -133,6 +133,7 @@ fn format_option_u64() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn format_with_expression_args() {
     struct Point {
         x: f64,
@@ -181,3 +182,40 @@ fn format_empty() {
 
     assert_eq!(html, "<div id=\"test\"></div>");
 }
+
+#[test]
+fn field_with_custom_renderer() {
+    struct Cents(i64);
+
+    fn dollars(value: &Cents, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}.{:02}", value.0 / 100, value.0 % 100)
+    }
+
+    mod render {
+        pub fn label(value: &&'static str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "<{}>", value)
+        }
+    }
+
+    #[derive(Element)]
+    #[element("p")]
+    struct Price {
+        #[element("span", with = dollars)]
+        amount: Cents,
+        #[element("em", with = "dollars")]
+        discount: Option<Cents>,
+        #[element("ul", with = render::label)]
+        tags: Vec<&'static str>,
+    }
+
+    let price = Price {
+        amount: Cents(1234),
+        discount: Some(Cents(50)),
+        tags: vec!["a&b"],
+    };
+
+    assert_eq!(
+        price.to_string(),
+        "<p><span>$12.34</span><em>$0.50</em><ul>&lt;a&amp;b&gt;</ul></p>"
+    );
+}
@@ -51,7 +51,7 @@ fn doctype_fragment() {
     where
         T: Display,
     {
-        #[element]
+        #[element(raw)]
         doctype: &'static str,
 
         #[element("html")]
@@ -80,3 +80,39 @@ fn doctype_fragment() {
         "<!DOCTYPE html><html><div>World</div><div>Hello</div></html>"
     );
 }
+
+#[test]
+fn tuple_fragments_group_siblings() {
+    use crate::{Fragment, Render};
+
+    #[derive(Element)]
+    #[element("h1")]
+    struct Title {
+        #[element]
+        text: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("main")]
+    struct Page<T: Display> {
+        #[element]
+        content: T,
+    }
+
+    let header = fragment!(Title { text: "Hi" }, "<&>", 3u8);
+    assert_eq!(header.to_string(), "<h1>Hi</h1>&lt;&amp;&gt;3");
+    assert_eq!(fragment!().to_string(), "");
+
+    let page = Page {
+        content: Fragment((
+            Title { text: "A" },
+            Some(Title { text: "B" }),
+            None::<Title>,
+        )),
+    };
+    assert_eq!(page.to_string(), "<main><h1>A</h1><h1>B</h1></main>");
+
+    let twelve = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+    assert_eq!(twelve.render_to_string(), "123456789101112");
+    assert!(header.size_hint() >= Title { text: "Hi" }.size_hint());
+}
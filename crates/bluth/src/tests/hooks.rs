@@ -0,0 +1,125 @@
+use crate::tests::voids;
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::context::RenderContext;
+use crate::hooks::{HookAttrs, Hooks, RenderHook, reject};
+use crate::{Element, Render, RenderError};
+
+#[derive(Element)]
+#[element("figure")]
+struct Figure {
+    #[element("img")]
+    #[attr(src = "{src}")]
+    image: (),
+    src: &'static str,
+}
+
+#[derive(Element)]
+#[element("img")]
+#[attr(src = "/b.png", loading = "eager")]
+struct EagerImage {}
+
+#[derive(Element)]
+#[element("marquee")]
+struct Banner {}
+
+struct LazyImages;
+
+impl RenderHook for LazyImages {
+    fn on_element_start(&self, tag: &str, attrs: &mut HookAttrs<'_>) -> fmt::Result {
+        if tag == "img" {
+            attrs.push("loading", "lazy")?;
+        }
+        Ok(())
+    }
+}
+
+struct InvalidName;
+
+impl RenderHook for InvalidName {
+    fn on_element_start(&self, _tag: &str, attrs: &mut HookAttrs<'_>) -> fmt::Result {
+        attrs.push("x\" onload=\"alert(1)", "")?;
+        attrs.push_bare("a b")
+    }
+}
+
+struct Banned(&'static str);
+
+impl RenderHook for Banned {
+    fn on_element_start(&self, tag: &str, _attrs: &mut HookAttrs<'_>) -> fmt::Result {
+        if tag == self.0 {
+            return Err(reject(tag, "banned tag"));
+        }
+        Ok(())
+    }
+}
+
+struct CountEnds(Rc<Cell<usize>>);
+
+impl RenderHook for CountEnds {
+    fn on_element_end(&self, _tag: &str) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        Ok(())
+    }
+}
+
+#[test]
+fn hooks_inject_attrs_and_observe_elements() {
+    let ends = Rc::new(Cell::new(0));
+    let context =
+        RenderContext::new().with(Hooks::new().with(LazyImages).with(CountEnds(ends.clone())));
+    let figure = Figure {
+        image: (),
+        src: "/a.png",
+    };
+
+    let html = context.render(|| figure.to_string());
+
+    assert_eq!(
+        html,
+        voids(r#"<figure><img src="/a.png" loading="lazy"/></figure>"#)
+    );
+    assert_eq!(ends.get(), 2);
+    assert_eq!(
+        figure.to_string(),
+        voids(r#"<figure><img src="/a.png"/></figure>"#)
+    );
+}
+
+#[test]
+fn hooks_can_reject_elements() {
+    let context = RenderContext::new().with(Hooks::new().with(Banned("marquee")));
+
+    let result = context.render(|| Banner {}.try_render_to_string());
+
+    assert!(matches!(
+        result,
+        Err(RenderError::Rejected { ref tag, .. }) if tag == "marquee"
+    ));
+}
+
+#[test]
+fn hooks_skip_duplicate_and_invalid_attrs() {
+    let context = RenderContext::new().with(
+        Hooks::new()
+            .with(LazyImages)
+            .with(LazyImages)
+            .with(InvalidName),
+    );
+
+    assert_eq!(
+        context.render(|| EagerImage {}.to_string()),
+        voids(r#"<img src="/b.png" loading="eager"/>"#)
+    );
+
+    let figure = Figure {
+        image: (),
+        src: "/a.png",
+    };
+    assert_eq!(
+        context.render(|| figure.to_string()),
+        voids(r#"<figure><img src="/a.png" loading="lazy"/></figure>"#)
+    );
+}
@@ -0,0 +1,413 @@
+use crate::datastar::PatchElements;
+use crate::hub::{Deferred, Hub};
+use axum::response::IntoResponse;
+
+#[tokio::test]
+async fn hub_broadcasts_patches() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(8);
+    let mut first = hub.subscribe();
+    let mut second = hub.subscribe();
+
+    assert_eq!(hub.subscriber_count(), 2);
+    assert_eq!(
+        hub.patch_elements(PatchElements::new(vec!["<p>hi</p>"]).selector("#feed")),
+        2
+    );
+
+    let expected =
+        "event: datastar-patch-elements\ndata: selector #feed\ndata: elements <p>hi</p>\n\n";
+    assert_eq!(first.recv().await.as_deref(), Some(expected));
+    assert_eq!(second.recv().await.as_deref(), Some(expected));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn hub_subscription_response_is_event_stream() -> Result<(), anyhow::Error> {
+    let hub = Hub::default();
+    let response = hub.subscribe().into_response();
+
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deferred_renders_placeholder_then_patches() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(8);
+    let mut bystander = hub.subscribe();
+    let mut other_page = hub.subscribe_to("page-b");
+
+    let deferred = Deferred::spawn(&hub, "page-a", "Loading…", async { "<b>done</b>" })?;
+    let id = deferred.id().to_string();
+
+    assert_eq!(
+        deferred.to_string(),
+        format!("<div id=\"{}\">Loading…</div>", id)
+    );
+
+    // Let the spawned task resolve before the page's stream subscribes.
+    tokio::task::yield_now().await;
+    let mut page = hub.subscribe_to("page-a");
+
+    let event = page.recv().await.expect("patch event");
+    assert_eq!(
+        &*event,
+        format!(
+            "event: datastar-patch-elements\ndata: selector #{}\ndata: mode inner\ndata: elements <b>done</b>\n\n",
+            id
+        )
+    );
+
+    hub.send("ping");
+    hub.send_to("page-b", "pong");
+    assert_eq!(bystander.recv().await.as_deref(), Some("ping"));
+    assert_eq!(other_page.recv().await.as_deref(), Some("pong"));
+
+    Ok(())
+}
+
+#[test]
+fn deferred_requires_a_runtime() {
+    let hub = Hub::new(8);
+    assert!(Deferred::spawn(&hub, "page", "Loading…", async { "done" }).is_err());
+}
+
+#[tokio::test]
+async fn hub_pushes_toasts() -> Result<(), anyhow::Error> {
+    use crate::components::Toast;
+
+    let hub = Hub::new(8);
+    let mut subscription = hub.subscribe();
+
+    assert_eq!(hub.toast(Toast::info("Hello")), 1);
+
+    let event = subscription.recv().await.expect("toast event");
+    assert!(event.contains("data: selector #toasts\ndata: mode append\n"));
+    assert!(event.contains("Hello"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn hub_is_a_patch_sink() -> Result<(), anyhow::Error> {
+    use crate::datastar::PatchSink;
+
+    fn notify(sink: &impl PatchSink) -> usize {
+        sink.patch_elements(PatchElements::new(vec!["<p>hi</p>"]))
+    }
+
+    let hub = Hub::new(8);
+    let _subscription = hub.subscribe();
+
+    assert_eq!(notify(&hub), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn lagging_subscriber_drops_oldest() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(2);
+    let mut subscription = hub.subscribe();
+
+    for n in 1..=5 {
+        hub.send(n);
+    }
+
+    assert_eq!(subscription.recv().await.as_deref(), Some("4"));
+    assert_eq!(subscription.recv().await.as_deref(), Some("5"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn lagging_subscriber_gets_refresh_patch() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(2).on_lag(|missed| {
+        PatchElements::new(vec![format!("<main>refresh after {missed}</main>")]).selector("main")
+    });
+    let mut subscription = hub.subscribe();
+
+    for n in 1..=5 {
+        hub.send(n);
+    }
+
+    let refresh = subscription.recv().await;
+    assert!(
+        refresh
+            .as_deref()
+            .unwrap_or_default()
+            .contains("data: elements <main>refresh after 3</main>")
+    );
+    assert_eq!(subscription.recv().await.as_deref(), Some("4"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn lagging_subscriber_disconnects() -> Result<(), anyhow::Error> {
+    use crate::hub::LagPolicy;
+
+    let hub = Hub::new(2).lag_policy(LagPolicy::Disconnect);
+    let mut subscription = hub.subscribe();
+
+    for n in 1..=5 {
+        hub.send(n);
+    }
+
+    assert_eq!(subscription.recv().await, None);
+    assert_eq!(subscription.recv().await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn topic_subscriptions_only_receive_their_topic() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(8);
+    let mut lobby = hub.subscribe_to("room:lobby");
+    let mut kitchen = hub.subscribe_to("room:kitchen");
+    let mut everyone = hub.subscribe();
+
+    assert_eq!(hub.topic_subscriber_count("room:lobby"), 1);
+    assert_eq!(
+        hub.patch_to("room:lobby", PatchElements::new(vec!["<p>hi lobby</p>"])),
+        1
+    );
+    assert_eq!(hub.send_to("room:nowhere", "ignored"), 0);
+    hub.send("global");
+    hub.send_to("room:kitchen", "kitchen");
+
+    let lobby_event = lobby.recv().await;
+    assert!(
+        lobby_event
+            .as_deref()
+            .unwrap_or_default()
+            .contains("<p>hi lobby</p>")
+    );
+    assert_eq!(kitchen.recv().await.as_deref(), Some("kitchen"));
+    assert_eq!(everyone.recv().await.as_deref(), Some("global"));
+
+    drop(lobby);
+    assert_eq!(hub.send_to("room:lobby", "gone"), 0);
+    assert_eq!(hub.topic_subscriber_count("room:lobby"), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn topics_are_pruned_when_the_last_subscriber_leaves() -> Result<(), anyhow::Error> {
+    let hub = Hub::new(8);
+    let first = hub.subscribe_to("page:1");
+    let second = hub.subscribe_to("page:1");
+    let _other = hub.subscribe_to("page:2");
+    assert_eq!(hub.topic_count(), 2);
+
+    drop(first);
+    assert_eq!(hub.topic_count(), 2);
+    drop(second);
+    assert_eq!(hub.topic_count(), 1);
+
+    assert_eq!(hub.send_or_retain_to("page:1", "later"), 0);
+    let mut rejoined = hub.subscribe_to("page:1");
+    assert_eq!(rejoined.recv().await.as_deref(), Some("later"));
+    assert_eq!(hub.topic_count(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn presence_tracks_members_per_topic() -> Result<(), anyhow::Error> {
+    use crate::presence::{Presence, PresenceUser};
+    use axum::http::Extensions;
+
+    let hub = Hub::new(16);
+    let presence = Presence::new(hub.clone());
+    let mut watcher = hub.subscribe_to("room:1");
+
+    let mut extensions = Extensions::new();
+    extensions.insert(PresenceUser("gob".to_string()));
+    let guard = presence.join_from("room:1", &extensions);
+    let member_id = guard.id().to_string();
+
+    assert_eq!(presence.count("room:1"), 1);
+    assert_eq!(presence.count("room:2"), 0);
+    assert_eq!(
+        presence.members("room:1")[0].user_id.as_deref(),
+        Some("gob")
+    );
+
+    let joined = watcher.recv().await;
+    assert_eq!(
+        joined.as_deref(),
+        Some(
+            format!(
+                "event: datastar-patch-elements\ndata: selector #presence\ndata: mode append\ndata: elements <li id=\"{member_id}\">gob</li>\n\n"
+            )
+            .as_str()
+        )
+    );
+    assert_eq!(
+        watcher.recv().await.as_deref(),
+        Some("event: datastar-patch-signals\ndata: signals {\"onlineCount\":1}\n\n")
+    );
+
+    drop(guard);
+    assert_eq!(presence.count("room:1"), 0);
+    assert!(presence.last_seen("gob").is_some());
+
+    let left = watcher.recv().await;
+    assert!(
+        left.as_deref()
+            .unwrap_or_default()
+            .contains(&format!("data: selector #{member_id}\ndata: mode remove"))
+    );
+    assert_eq!(
+        watcher.recv().await.as_deref(),
+        Some("event: datastar-patch-signals\ndata: signals {\"onlineCount\":0}\n\n")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn presence_subscription_leaves_on_drop() -> Result<(), anyhow::Error> {
+    use crate::presence::Presence;
+
+    let presence = Presence::new(Hub::new(16));
+    let subscription = presence.subscribe("room:1", None);
+
+    assert_eq!(presence.count("room:1"), 1);
+    drop(subscription);
+    assert_eq!(presence.count("room:1"), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn throttle_coalesces_patches_per_selector() -> Result<(), anyhow::Error> {
+    use crate::datastar::PatchSink;
+    use crate::throttle::Throttle;
+    use std::time::Duration;
+
+    let hub = Hub::new(16);
+    let mut subscription = hub.subscribe();
+    let throttle = Throttle::new(hub.clone(), Duration::from_millis(20));
+
+    for n in 0..100 {
+        throttle
+            .patch_elements(PatchElements::new(vec![format!("<b>{n}</b>")]).selector("#ticker"));
+    }
+    throttle.patch_elements(PatchElements::new(vec!["<i>other</i>"]).selector("#other"));
+
+    let first = subscription.recv().await;
+    assert!(first.as_deref().unwrap_or_default().contains("<b>0</b>"));
+    let other = subscription.recv().await;
+    assert!(
+        other
+            .as_deref()
+            .unwrap_or_default()
+            .contains("<i>other</i>")
+    );
+    let latest = subscription.recv().await;
+    assert!(latest.as_deref().unwrap_or_default().contains("<b>99</b>"));
+    assert_eq!(hub.send("done"), 1);
+    assert_eq!(subscription.recv().await.as_deref(), Some("done"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn throttle_passes_selectorless_patches_through() -> Result<(), anyhow::Error> {
+    use crate::datastar::PatchSink;
+    use crate::throttle::Throttle;
+    use std::time::Duration;
+
+    let hub = Hub::new(16);
+    let mut subscription = hub.subscribe();
+    let throttle = Throttle::new(hub.clone(), Duration::from_secs(60));
+
+    throttle.patch_elements(PatchElements::new(vec![r#"<p id="a">a</p>"#]));
+    throttle.patch_elements(PatchElements::new(vec![r#"<p id="b">b</p>"#]));
+
+    assert!(
+        subscription
+            .recv()
+            .await
+            .unwrap_or_default()
+            .contains(r#"id="a""#)
+    );
+    assert!(
+        subscription
+            .recv()
+            .await
+            .unwrap_or_default()
+            .contains(r#"id="b""#)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn throttle_sends_immediately_without_a_runtime() {
+    use crate::datastar::PatchSink;
+    use crate::throttle::Throttle;
+    use std::time::Duration;
+
+    let hub = Hub::new(16);
+    let _subscription = hub.subscribe();
+    let throttle = Throttle::new(hub.clone(), Duration::from_secs(60));
+
+    let patch = || PatchElements::new(vec!["<b>tick</b>"]).selector("#ticker");
+    assert_eq!(throttle.patch_elements(patch()), 1);
+    assert_eq!(throttle.patch_elements(patch()), 1);
+}
+
+#[tokio::test]
+async fn origin_patches_skip_the_originating_subscription() -> Result<(), anyhow::Error> {
+    use crate::datastar::PatchSink;
+    use crate::hub::OriginToken;
+
+    let hub = Hub::new(8);
+    let token = OriginToken::generate();
+    let mut origin = hub.subscribe().origin(token.clone());
+    let mut other = hub.subscribe().origin(OriginToken::generate());
+
+    assert_eq!(
+        hub.from_origin(&token)
+            .patch_elements(PatchElements::new(vec!["<p>echo</p>"])),
+        2
+    );
+    hub.send("for everyone");
+
+    assert!(
+        other
+            .recv()
+            .await
+            .as_deref()
+            .unwrap_or_default()
+            .contains("<p>echo</p>")
+    );
+    assert_eq!(other.recv().await.as_deref(), Some("for everyone"));
+    assert_eq!(origin.recv().await.as_deref(), Some("for everyone"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn origin_token_from_request_header() -> Result<(), anyhow::Error> {
+    use crate::hub::{ORIGIN_HEADER, OriginToken};
+    use axum::extract::FromRequestParts;
+    use axum::http::{Request, StatusCode};
+
+    let (mut parts, _) = Request::builder()
+        .header(ORIGIN_HEADER, "origin-42")
+        .body(())?
+        .into_parts();
+    let token = OriginToken::from_request_parts(&mut parts, &()).await;
+    assert_eq!(token, Ok(OriginToken("origin-42".to_string())));
+
+    let (mut parts, _) = Request::builder().body(())?.into_parts();
+    let missing = OriginToken::from_request_parts(&mut parts, &()).await;
+    assert_eq!(missing, Err(StatusCode::BAD_REQUEST));
+
+    Ok(())
+}
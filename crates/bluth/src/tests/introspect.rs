@@ -0,0 +1,91 @@
+use crate::Element;
+use crate::introspect::{Describe, tree};
+
+#[derive(Element)]
+#[element("li")]
+struct Item {
+    #[attr]
+    data_id: u32,
+    #[element]
+    label: String,
+}
+
+#[derive(Element)]
+#[element("ul")]
+#[attr(class = "list")]
+struct List {
+    #[element]
+    items: Vec<Item>,
+    #[element("p")]
+    footer: Option<Footer>,
+}
+
+#[derive(Element)]
+#[element("footer")]
+struct Footer {
+    #[element]
+    text: String,
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Node {
+    #[element]
+    children: Vec<Node>,
+}
+
+#[test]
+fn describe_lists_tag_attrs_and_children() {
+    let schema = List::describe();
+    assert!(schema.name.ends_with("List"));
+    assert_eq!(schema.tag, Some("ul"));
+    assert_eq!(schema.attrs, vec!["class"]);
+    assert_eq!(schema.children.len(), 2);
+
+    let items = &schema.children[0];
+    assert_eq!(items.field, "items");
+    assert!(items.many);
+    assert!(items.ty.ends_with("Item"));
+    assert!(items.is_component());
+
+    let footer = &schema.children[1];
+    assert_eq!(footer.tag, Some("p"));
+    assert!(!footer.many);
+    assert!(footer.ty.ends_with("Footer"));
+
+    let item = Item::describe();
+    assert_eq!(item.attrs, vec!["data-id"]);
+    assert!(!item.children[0].is_component());
+}
+
+#[test]
+fn tree_walks_components_and_stops_on_recursion() {
+    let list = tree::<List>();
+    assert_eq!(list.children.len(), 2);
+    assert!(list.contains(std::any::type_name::<Footer>()));
+    assert_eq!(list.names().len(), 3);
+    assert!(!list.names().contains(std::any::type_name::<Node>()));
+
+    let node = tree::<Node>();
+    assert_eq!(node.children.len(), 1);
+    assert!(node.children[0].recursive);
+    assert!(node.to_string().contains("Node <div> (recursive)"));
+}
+
+#[test]
+fn describe_enum_variants() {
+    #[allow(dead_code)]
+    #[derive(Element)]
+    #[element(transparent)]
+    enum Slot {
+        #[element("span")]
+        Text(String),
+        #[element("div")]
+        Item(Item),
+    }
+
+    let schema = Slot::describe();
+    let fields: Vec<_> = schema.children.iter().map(|child| child.field).collect();
+    assert_eq!(fields, vec!["Text::0", "Item::0"]);
+    assert!(schema.children[1].is_component());
+}
@@ -0,0 +1,58 @@
+use crate::Element;
+use crate::html::Lazy;
+use std::cell::Cell;
+use std::fmt::{self, Display};
+
+#[test]
+fn lazy_wrapper_renders_on_display() {
+    #[derive(Element)]
+    #[element("div")]
+    struct Report<F>
+    where
+        F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+    {
+        #[element("section")]
+        summary: Lazy<F>,
+    }
+
+    let calls = Cell::new(0);
+    let report = Report {
+        summary: Lazy::new(|f: &mut fmt::Formatter<'_>| {
+            calls.set(calls.get() + 1);
+            write!(f, "expensive")
+        }),
+    };
+
+    assert_eq!(calls.get(), 0);
+    assert_eq!(
+        report.to_string(),
+        "<div><section>expensive</section></div>"
+    );
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn lazy_field_closure() {
+    #[derive(Element)]
+    #[element("ul")]
+    struct Stats<F, T>
+    where
+        F: Fn() -> T,
+        T: Display,
+    {
+        #[element("li", lazy)]
+        total: F,
+    }
+
+    let calls = Cell::new(0);
+    let stats = Stats {
+        total: || {
+            calls.set(calls.get() + 1);
+            7
+        },
+    };
+
+    assert_eq!(calls.get(), 0);
+    assert_eq!(stats.to_string(), "<ul><li>7</li></ul>");
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,50 @@
+use crate::context::{Dir, Locale, RenderContext};
+use crate::{Body, Element, Head, Html};
+
+#[test]
+fn html_picks_up_context_locale() {
+    #[derive(Element)]
+    #[element("p")]
+    #[attr(auto_dir)]
+    struct Note {
+        #[element]
+        text: &'static str,
+    }
+
+    let html = |lang| Html {
+        lang,
+        head: Head {
+            link: vec![],
+            script: vec![],
+        },
+        body: Body {
+            class: "page",
+            children: vec![Note { text: "hi" }],
+        },
+    };
+
+    assert_eq!(
+        html("en").to_string(),
+        r#"<html lang="en"><head></head><body class="page"><p>hi</p></body></html>"#
+    );
+
+    let arabic = RenderContext::new().with(Locale::new("ar-EG"));
+    assert_eq!(
+        arabic.render(|| html("").to_string()),
+        r#"<html lang="ar-EG" dir="rtl"><head></head><body class="page"><p dir="rtl">hi</p></body></html>"#
+    );
+    assert_eq!(
+        arabic.render(|| html("en").to_string()),
+        r#"<html lang="en"><head></head><body class="page"><p dir="rtl">hi</p></body></html>"#
+    );
+
+    assert_eq!(
+        RenderContext::new().render(|| html("he").to_string()),
+        r#"<html lang="he" dir="rtl"><head></head><body class="page"><p>hi</p></body></html>"#
+    );
+
+    assert_eq!(Locale::new("he").dir(), Dir::Rtl);
+    assert_eq!(Locale::new("az-Arab").dir(), Dir::Rtl);
+    assert_eq!(Locale::new("ku-Latn").dir(), Dir::Ltr);
+    assert_eq!(Locale::new("fr-CA").dir(), Dir::Ltr);
+}
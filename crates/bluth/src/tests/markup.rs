@@ -0,0 +1,80 @@
+use crate::tests::voids;
+use crate::{Element, Render, html};
+
+#[derive(Element)]
+#[element("li")]
+#[attr(class = "item")]
+struct Item {
+    #[element]
+    label: &'static str,
+}
+
+#[test]
+fn html_macro_renders_nested_elements() {
+    let name = "Ann & <Bob>";
+    let kind = "primary";
+    let markup = html! {
+        div class="card" id=(format!("user-{}", 7)) {
+            h2 { "Hello, " (name) "!" }
+            button class="btn btn-{kind}" disabled if name.is_empty() { "Go" }
+            br;
+            input type="text" value=(name) required;
+            my-widget "data-on:click"="@get('/x?a=1&b=2')";
+        }
+    };
+
+    assert_eq!(
+        markup.to_string(),
+        voids(concat!(
+            "<div class=\"card\" id=\"user-7\">",
+            "<h2>Hello, Ann &amp; &lt;Bob&gt;!</h2>",
+            "<button class=\"btn btn-primary\">Go</button>",
+            "<br/>",
+            "<input type=\"text\" value=\"Ann &amp; &lt;Bob&gt;\" required/>",
+            "<my-widget data-on:click=\"@get('/x?a=1&amp;b=2')\"></my-widget>",
+            "</div>"
+        ))
+    );
+    assert_eq!(markup.render_to_string(), markup.to_string());
+    assert!(markup.size_hint() >= "<div class=\"card\"><h2>Hello, !</h2></div>".len());
+}
+
+#[test]
+fn html_macro_control_flow_and_components() {
+    let items = vec![Item { label: "a" }, Item { label: "b" }];
+    let user: Option<&str> = None;
+    let active = true;
+
+    let list = html! {
+        ul class=["list", active => active, "empty" => items.is_empty()] {
+            @for item in &items {
+                (item)
+            }
+            @let count = items.len();
+            @if let Some(user) = user {
+                li { (user) }
+            } @else if count > 1 {
+                li { (count) " items" }
+            } @else {
+                li { "none" }
+            }
+        }
+    };
+
+    assert_eq!(
+        list.to_string(),
+        "<ul class=\"list active\"><li class=\"item\">a</li><li class=\"item\">b</li><li>2 items</li></ul>"
+    );
+
+    #[derive(Element)]
+    #[element("section")]
+    struct Section<T: std::fmt::Display> {
+        #[element]
+        body: T,
+    }
+
+    let section = Section {
+        body: html! { p { "inline" } },
+    };
+    assert_eq!(section.to_string(), "<section><p>inline</p></section>");
+}
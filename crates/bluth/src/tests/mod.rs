@@ -2,9 +2,40 @@
 #[cfg(test)]
 pub mod axum;
 
+#[cfg(feature = "hub")]
+#[cfg(test)]
+pub mod hub;
+
+#[cfg(feature = "axum-ws")]
+#[cfg(test)]
+pub mod ws;
+
+#[cfg(feature = "testing")]
+#[cfg(test)]
+pub mod testing;
+
+#[cfg(feature = "cookie-signals")]
+#[cfg(test)]
+pub mod cookie;
+
+#[cfg(feature = "sealed-signals")]
+#[cfg(test)]
+pub mod sealed;
+
 #[cfg(test)]
 pub mod basic;
 
+#[cfg(test)]
+pub mod borrowed;
+
+#[cfg(test)]
+pub mod builder;
+
+#[cfg(test)]
+pub mod cache;
+#[cfg(test)]
+pub mod locale;
+
 #[cfg(test)]
 pub mod fragments;
 
@@ -22,3 +53,102 @@ pub mod datastar;
 
 #[cfg(test)]
 pub mod url;
+
+#[cfg(test)]
+pub mod window;
+
+#[cfg(test)]
+pub mod xml;
+
+#[cfg(test)]
+pub mod lazy;
+
+#[cfg(test)]
+pub mod components;
+
+#[cfg(test)]
+pub mod scaffold;
+
+#[cfg(test)]
+pub mod signal_store;
+
+#[cfg(test)]
+pub mod form;
+
+#[cfg(test)]
+pub mod transparent;
+
+#[cfg(test)]
+pub mod markup;
+
+#[cfg(feature = "test-ids")]
+#[cfg(test)]
+pub mod test_ids;
+
+#[cfg(test)]
+pub mod flags;
+
+#[cfg(test)]
+pub mod render;
+
+#[cfg(test)]
+pub mod rows;
+
+#[cfg(feature = "csp-strict")]
+#[cfg(test)]
+pub mod csp;
+
+#[cfg(feature = "codegen")]
+#[cfg(test)]
+pub mod codegen;
+
+#[cfg(feature = "dev-templates")]
+#[cfg(test)]
+pub mod templates;
+
+#[cfg(feature = "xhtml")]
+#[cfg(test)]
+pub mod xhtml;
+
+#[cfg(feature = "introspect")]
+#[cfg(test)]
+pub mod introspect;
+
+#[cfg(feature = "render-hooks")]
+#[cfg(test)]
+pub mod hooks;
+
+/// Rewrites the `<br/>`-style void closes in an expected string into the
+/// `<br></br>` form emitted under the `xhtml` feature, so the same expectation
+/// holds whichever close the build renders. Non-void (XML) self-closes are kept.
+#[cfg(test)]
+pub(crate) fn voids(expected: &str) -> String {
+    const VOID: &[&str] = &[
+        "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+        "track", "wbr",
+    ];
+    if !cfg!(feature = "xhtml") {
+        return expected.to_string();
+    }
+    let mut out = String::with_capacity(expected.len());
+    let mut rest = expected;
+    while let Some(end) = rest.find("/>") {
+        let head = &rest[..end];
+        let tag = head
+            .rfind('<')
+            .map(|open| &head[open + 1..])
+            .and_then(|open| open.split([' ', '/']).next())
+            .unwrap_or_default();
+        out.push_str(head);
+        if VOID.contains(&tag) {
+            out.push_str("></");
+            out.push_str(tag);
+            out.push('>');
+        } else {
+            out.push_str("/>");
+        }
+        rest = &rest[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,374 @@
+use crate::{Element, Render};
+
+#[derive(Element)]
+#[element("li")]
+#[attr(class = "item")]
+struct Item {
+    #[element]
+    label: &'static str,
+}
+
+#[derive(Element)]
+#[element("ul")]
+struct List {
+    #[element]
+    items: Vec<Item>,
+}
+
+fn list() -> List {
+    List {
+        items: vec![Item { label: "a" }, Item { label: "b" }],
+    }
+}
+
+#[test]
+fn render_to_string_matches_display() {
+    let list = list();
+    assert_eq!(list.render_to_string(), list.to_string());
+    assert_eq!(
+        list.render_to_string(),
+        "<ul><li class=\"item\">a</li><li class=\"item\">b</li></ul>"
+    );
+}
+
+#[test]
+fn render_to_writer_writes_bytes() {
+    let mut buffer = Vec::new();
+    list().render_to_writer(&mut buffer).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "<ul><li class=\"item\">a</li><li class=\"item\">b</li></ul>"
+    );
+}
+
+#[test]
+fn render_to_writer_surfaces_io_errors() {
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::StorageFull, "full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = list().render_to_writer(&mut Full).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::StorageFull);
+}
+
+#[test]
+fn render_through_trait_object() {
+    let items: Vec<Box<dyn Render>> = vec![Box::new(Item { label: "x" }), Box::new(list())];
+    let html: String = items.iter().map(|item| item.render_to_string()).collect();
+    assert_eq!(
+        html,
+        "<li class=\"item\">x</li><ul><li class=\"item\">a</li><li class=\"item\">b</li></ul>"
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn derived_components_track_render_time() {
+    use crate::metrics::render_time;
+
+    #[derive(Element)]
+    #[element("ol")]
+    struct Timed {
+        #[element]
+        items: Vec<Item>,
+    }
+
+    let timed = Timed {
+        items: vec![Item { label: "a" }, Item { label: "b" }],
+    };
+    let before = render_time::<Item>().renders;
+    timed.render_to_string();
+
+    assert_eq!(render_time::<Timed>().renders, 1);
+    assert!(render_time::<Item>().renders >= before + 2);
+    assert!(render_time::<Timed>().total >= render_time::<Timed>().max);
+}
+
+struct Failing;
+
+impl Render for Failing {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        f.write_str("<p>partial")?;
+        Err(crate::render::report(crate::RenderError::Fmt))
+    }
+}
+
+#[test]
+fn error_boundary_substitutes_fallback() {
+    use crate::components::ErrorBoundary;
+
+    #[derive(Element)]
+    #[element("main")]
+    struct Page {
+        #[element]
+        widget: ErrorBoundary<Failing, &'static str>,
+
+        #[element]
+        list: ErrorBoundary<List>,
+    }
+
+    let page = Page {
+        widget: ErrorBoundary::new(Failing).fallback("<p>unavailable</p>"),
+        list: ErrorBoundary::new(list()),
+    };
+
+    assert_eq!(
+        page.try_render_to_string().unwrap(),
+        format!(
+            "<main><p>unavailable</p>{}</main>",
+            list().render_to_string()
+        )
+    );
+    assert_eq!(
+        ErrorBoundary::new(Failing).to_string(),
+        r#"<div class="bluth-error-boundary" role="alert"></div>"#
+    );
+    assert_eq!(ErrorBoundary::new(list()).fallback("-").size_hint(), 0);
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Tree {
+    #[element]
+    children: Vec<Tree>,
+}
+
+fn tree(depth: usize) -> Tree {
+    Tree {
+        children: (0..depth.min(1)).map(|_| tree(depth - 1)).collect(),
+    }
+}
+
+#[test]
+fn render_with_limits_bounds_depth_and_size() {
+    use crate::RenderError;
+    use crate::render::render_with_limits;
+
+    assert_eq!(
+        render_with_limits(&tree(2), 1024, 3).unwrap(),
+        "<div><div><div></div></div></div>"
+    );
+    assert_eq!(
+        render_with_limits(&tree(3), 1024, 3),
+        Err(RenderError::TooDeep { max_depth: 3 })
+    );
+    assert_eq!(
+        render_with_limits(&tree(2), 20, 10),
+        Err(RenderError::TooLarge { max_bytes: 20 })
+    );
+    assert_eq!(
+        render_with_limits(&list(), 1024, 10).unwrap(),
+        list().render_to_string()
+    );
+
+    assert_eq!(tree(50).render_to_string().len(), 51 * 11);
+}
+
+#[test]
+fn render_with_limits_bounds_buffered_children() {
+    use crate::RenderError;
+    use crate::components::ErrorBoundary;
+    use crate::render::render_with_limits;
+    use std::cell::Cell;
+
+    struct Endless<'a>(&'a Cell<usize>);
+
+    impl Render for Endless<'_> {
+        fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            for _ in 0..1_000_000 {
+                self.0.set(self.0.get() + 1);
+                f.write_str("<p>row</p>")?;
+            }
+            Ok(())
+        }
+    }
+
+    let writes = Cell::new(0);
+    let boundary = ErrorBoundary::new(Endless(&writes)).fallback("<p>too big</p>");
+    assert_eq!(
+        render_with_limits(&boundary, 100, 10).unwrap(),
+        "<p>too big</p>"
+    );
+    assert_eq!(writes.get(), 11);
+
+    #[derive(Element)]
+    #[element("main")]
+    struct Page {
+        #[element]
+        header: &'static str,
+
+        #[element]
+        body: ErrorBoundary<List, &'static str>,
+    }
+
+    let page = Page {
+        header: "<h1>Title</h1>",
+        body: ErrorBoundary::new(list()).fallback("-"),
+    };
+    let full = page.render_to_string();
+    assert_eq!(render_with_limits(&page, full.len(), 10).unwrap(), full);
+    assert_eq!(
+        render_with_limits(&page, full.len() - 1, 10),
+        Err(RenderError::TooLarge {
+            max_bytes: full.len() - 1
+        })
+    );
+}
+
+#[test]
+fn derived_size_hint_estimates_output() {
+    #[derive(Element)]
+    #[element("tr")]
+    #[attr(class = "row")]
+    struct Row {
+        #[element("td")]
+        name: String,
+
+        #[element("td")]
+        cells: Vec<u32>,
+    }
+
+    let row = Row {
+        name: "Ann".to_string(),
+        cells: vec![1, 2],
+    };
+
+    let hint = row.size_hint();
+    assert!(hint >= "<tr class=\"row\"><td>Ann</td><td></td></tr>".len());
+    assert!(row.render_to_string().capacity() >= hint);
+    assert_eq!(Render::size_hint(&&row), hint);
+}
+
+#[test]
+fn markup_size_hint_takes_the_shortest_branch() {
+    use crate::html;
+
+    let flag = false;
+    let items: Vec<&str> = Vec::new();
+    let markup = html! {
+        div {
+            @if flag {
+                "a much longer branch of literal text"
+            } @else {
+                "b"
+            }
+            @for item in &items {
+                "item: " (item)
+            }
+        }
+    };
+
+    assert_eq!(markup.to_string(), "<div>b</div>");
+    assert!(markup.size_hint() <= markup.to_string().len());
+}
+
+#[test]
+fn heterogeneous_children_fields() {
+    use crate::render::boxed;
+    use crate::{AnyElement, Body};
+
+    #[derive(Element)]
+    #[element("h1")]
+    struct Heading {
+        #[element]
+        text: String,
+    }
+
+    #[derive(Element)]
+    #[element("section")]
+    struct Section {
+        #[element]
+        children: Vec<Box<dyn Render>>,
+    }
+
+    let section = Section {
+        children: vec![Box::new(Item { label: "x" }), Box::new(list())],
+    };
+    assert_eq!(
+        section.to_string(),
+        "<section><li class=\"item\">x</li><ul><li class=\"item\">a</li><li class=\"item\">b</li></ul></section>"
+    );
+
+    let body: Body<AnyElement> = Body {
+        class: "page",
+        children: vec![
+            boxed(Item { label: "y" }),
+            boxed(Heading {
+                text: "Title".to_string(),
+            }),
+        ],
+    };
+    assert_eq!(
+        body.to_string(),
+        "<body class=\"page\"><li class=\"item\">y</li><h1>Title</h1></body>"
+    );
+}
+
+#[test]
+fn primitives_render_with_escaping() {
+    use crate::AnyElement;
+    use std::borrow::Cow;
+
+    #[derive(Element)]
+    #[element("p")]
+    struct Mixed {
+        #[element]
+        children: Vec<AnyElement>,
+    }
+
+    let mixed = Mixed {
+        children: vec![
+            Box::new("a < b"),
+            Box::new(String::from(" & ")),
+            Box::new(42u8),
+            Box::new(-1.5f64),
+            Box::new(true),
+            Box::new('<'),
+            Box::new(Some(Cow::Borrowed("!"))),
+            Box::new(None::<i32>),
+        ],
+    };
+    assert_eq!(mixed.to_string(), "<p>a &lt; b &amp; 42-1.5true&lt;!</p>");
+    assert_eq!("<x>".render_to_string(), "&lt;x&gt;");
+    assert_eq!(Some(7).render_to_string(), "7");
+}
+
+#[test]
+fn children_built_imperatively() {
+    use crate::Children;
+
+    #[derive(Element)]
+    #[element("main")]
+    struct Page {
+        #[element]
+        children: Children,
+    }
+
+    let mut nav = Children::new();
+    nav.push_static("<nav>")
+        .push_text("Home & <away>")
+        .push_static("</nav>");
+
+    let mut children = Children::with_capacity(4);
+    children
+        .append(nav)
+        .push(list())
+        .extend([Item { label: "c" }])
+        .push_raw(String::from("<hr/>"));
+
+    assert_eq!(children.len(), 6);
+    let page = Page { children };
+    assert_eq!(
+        page.to_string(),
+        "<main><nav>Home &amp; &lt;away&gt;</nav><ul><li class=\"item\">a</li><li class=\"item\">b</li></ul><li class=\"item\">c</li><hr/></main>"
+    );
+    assert!(page.size_hint() >= "<main></main><nav></nav>".len());
+}
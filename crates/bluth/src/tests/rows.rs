@@ -0,0 +1,105 @@
+use crate::Element;
+use crate::rows::Rows;
+
+#[derive(Element)]
+#[element("tr")]
+struct Row {
+    #[element("td")]
+    name: String,
+}
+
+#[derive(Element)]
+#[element("tbody")]
+struct Body<I, F>
+where
+    Rows<I, F>: std::fmt::Display,
+{
+    #[element]
+    rows: Rows<I, F>,
+}
+
+fn row(name: &str) -> Result<Row, String> {
+    Ok(Row {
+        name: name.to_string(),
+    })
+}
+
+#[test]
+fn renders_rows_from_iterator() {
+    let body = Body {
+        rows: Rows::new(vec![row("a"), row("b")]),
+    };
+
+    assert_eq!(
+        body.to_string(),
+        "<tbody><tr><td>a</td></tr><tr><td>b</td></tr></tbody>"
+    );
+}
+
+#[test]
+fn error_stops_rows_with_trailing_element() {
+    let rows = vec![row("a"), Err("connection <reset>".to_string()), row("b")];
+    let body = Body {
+        rows: Rows::new(rows),
+    };
+
+    assert_eq!(
+        body.to_string(),
+        r#"<tbody><tr><td>a</td></tr><div class="bluth-row-error" role="alert">connection &lt;reset&gt;</div></tbody>"#
+    );
+}
+
+#[test]
+fn custom_error_element() {
+    let rows = Rows::new(vec![Err::<Row, _>("boom")])
+        .on_error(|error| format!("<tr><td colspan=\"2\">{error}</td></tr>"));
+
+    assert_eq!(rows.to_string(), r#"<tr><td colspan="2">boom</td></tr>"#);
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn streams_rows_as_chunks() {
+    use crate::rows::RowStream;
+    use futures_util::StreamExt;
+
+    let rows = futures_util::stream::iter(vec![row("a"), Err("gone".to_string()), row("b")]);
+    let chunks: Vec<String> = RowStream::new(rows)
+        .head("<table>")
+        .tail("</table>")
+        .into_stream()
+        .collect()
+        .await;
+
+    assert_eq!(
+        chunks,
+        vec![
+            "<table>".to_string(),
+            "<tr><td>a</td></tr>".to_string(),
+            r#"<div class="bluth-row-error" role="alert">gone</div>"#.to_string(),
+            "</table>".to_string(),
+        ]
+    );
+}
+
+#[cfg(all(feature = "stream", feature = "axum"))]
+#[tokio::test]
+async fn row_stream_response() {
+    use crate::rows::RowStream;
+    use axum::response::IntoResponse;
+
+    let rows = futures_util::stream::iter(vec![row("a"), row("b")]);
+    let response = RowStream::new(rows)
+        .head("<ul>")
+        .tail("</ul>")
+        .into_response();
+
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/html; charset=utf-8"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, "<ul><tr><td>a</td></tr><tr><td>b</td></tr></ul>");
+}
@@ -0,0 +1,37 @@
+use crate::Signal;
+use crate::scaffold::live_search;
+use crate::tests::voids;
+
+#[derive(Signal)]
+pub enum SearchSignals {
+    Query(String),
+}
+
+crate::define_url!(SearchUrl, "/search");
+
+#[test]
+fn live_search_input() {
+    let search = live_search(Query, &SearchUrl::new(), "results");
+
+    assert_eq!(SearchUrl::PATTERN, "/search");
+    assert_eq!(
+        search.to_string(),
+        voids(
+            "<input type=\"search\" data-bind=\"query\" data-on:input__debounce.300ms=\"@get('/search')\" aria-controls=\"results\"/>"
+        )
+    );
+    assert_eq!(
+        search.container().to_string(),
+        "<div id=\"results\" aria-live=\"polite\"></div>"
+    );
+}
+
+#[test]
+fn live_search_results_patch() {
+    let search = live_search(Query, &SearchUrl::new(), "results");
+
+    assert_eq!(
+        search.results(vec!["<li>one</li>"]).to_string(),
+        "event: datastar-patch-elements\ndata: selector #results\ndata: mode inner\ndata: elements <li>one</li>\n\n"
+    );
+}
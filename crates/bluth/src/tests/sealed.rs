@@ -0,0 +1,112 @@
+use crate::sealed::{SealError, Sealed, Sealer};
+use crate::{Signal, SignalEnum};
+
+#[derive(Signal)]
+pub enum ShopSignals {
+    Cursor(u64),
+    Price(u32),
+}
+
+fn install() {
+    let _ = Sealer::new("shop-secret").install();
+}
+
+#[test]
+fn sealed_values_round_trip_and_reject_forgery() {
+    install();
+    assert!(matches!(
+        Sealer::new("shop-secret").install(),
+        Err(SealError::AlreadyInstalled)
+    ));
+
+    let token = Sealed::<Cursor>::new(42).token().unwrap();
+    assert_eq!(
+        Sealed::<Cursor>::open(&token).map(Sealed::into_inner).ok(),
+        Some(42)
+    );
+    assert!(matches!(
+        Sealed::<Price>::open(&token),
+        Err(SealError::Invalid)
+    ));
+
+    let (_, mac) = token.split_once('.').unwrap();
+    let forged = format!("{}.{}", crate::sign::encode("9000"), mac);
+    assert!(Sealed::<Cursor>::open(&forged).is_err());
+
+    let signal = Sealed::<Price>::new(1999).to_signal().unwrap();
+    assert_eq!(signal.signal_name(), "price");
+    assert_eq!(
+        serde_json::to_value(&signal).unwrap(),
+        serde_json::json!({ "price": signal.token() })
+    );
+}
+
+#[test]
+fn encrypted_sealer_hides_value() {
+    let sealer = Sealer::new("shop-secret").encrypted();
+    let token = sealer.seal("cursor", &"page-7").unwrap();
+    assert_ne!(sealer.seal("cursor", &"page-7").unwrap(), token);
+    assert!(!token.contains("page"));
+    assert!(!String::from_utf8_lossy(&crate::sign::decode(&token).unwrap()).contains("page-7"));
+    assert_eq!(
+        sealer.open::<String>("cursor", &token).as_deref(),
+        Some("page-7")
+    );
+    assert_eq!(sealer.open::<String>("price", &token), None);
+
+    let mut tampered = crate::sign::decode(&token).unwrap();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        sealer.open::<String>("cursor", &crate::sign::encode(tampered)),
+        None
+    );
+}
+
+#[test]
+fn sealing_propagates_serialization_errors() {
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("not serializable"))
+        }
+    }
+
+    let result = Sealer::new("shop-secret").seal("cursor", &Unserializable);
+    assert!(matches!(result, Err(SealError::Serialize(_))));
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn extractor_unwraps_sealed_signals() -> Result<(), anyhow::Error> {
+    use crate::testing::TestApp;
+    use crate::{SignalExtractor, Signals};
+    use axum::{Router, http::StatusCode, routing::post};
+
+    install();
+
+    let app = TestApp::new(
+        Router::new()
+            .route(
+                "/cart",
+                post(
+                    |Signals((SignalExtractor(cursor), price)): Signals<(
+                        SignalExtractor<Cursor>,
+                        Sealed<Price>,
+                    )>| async move { format!("{cursor} {}", *price) },
+                ),
+            )
+            .route("/price", post(|_: Sealed<Price>| async { "ok" })),
+    );
+
+    let body = serde_json::json!({ "cursor": 3, "price": Sealed::<Price>::new(500).token()? });
+    let response = app.post("/cart").signals_json(body).send().await;
+    assert_eq!(response.body, "3 500");
+
+    let forged = serde_json::json!({ "price": "MQ.AAAA" });
+    let response = app.post("/price").signals_json(forged).send().await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.body, "Invalid sealed signal: price");
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::signal::SignalMap;
+use crate::{SignalEnum, SignalSelector, SignalStore};
+use serde_json::json;
+
+#[derive(SignalStore, Clone, Debug, PartialEq)]
+pub struct AppState {
+    user_name: String,
+    #[signal(name = "clicks")]
+    click_count: i32,
+}
+
+#[derive(SignalStore)]
+#[signal_store(name = CartSignals)]
+pub struct Cart {
+    items: Vec<String>,
+}
+
+#[test]
+fn signal_store_generates_selectors() {
+    assert_eq!(UserName::NAME, "userName");
+    assert_eq!(ClickCount::NAME, "clicks");
+    assert_eq!(Items::NAME, "items");
+
+    let cart = Cart {
+        items: vec!["banana".to_string()],
+    };
+    let signals: Vec<CartSignals> = cart.signals();
+    assert_eq!(signals[0].signal_name(), "items");
+}
+
+#[test]
+fn signal_store_diff() {
+    let old = AppState {
+        user_name: "Gob".to_string(),
+        click_count: 1,
+    };
+    let new = AppState {
+        click_count: 2,
+        ..old.clone()
+    };
+
+    let changed = AppState::diff(&old, &new);
+
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0].signal_name(), "clicks");
+    assert_eq!(changed[0].to_json_value(), json!(2));
+    assert!(AppState::diff(&new, &new).is_empty());
+}
+
+#[test]
+fn signal_store_apply() {
+    let mut state = AppState {
+        user_name: "Gob".to_string(),
+        click_count: 1,
+    };
+    let incoming: SignalMap = serde_json::from_value(json!({
+        "userName": "Buster",
+        "clicks": "not a number",
+        "other": true,
+    }))
+    .unwrap_or_default();
+
+    state.apply(incoming);
+
+    assert_eq!(
+        state,
+        AppState {
+            user_name: "Buster".to_string(),
+            click_count: 1,
+        }
+    );
+}
@@ -0,0 +1,49 @@
+use crate::Element;
+use crate::templates::{register, unregister};
+
+#[derive(Element)]
+#[element("li")]
+struct Tag(&'static str);
+
+#[derive(Element)]
+#[element("article")]
+struct OverriddenCard {
+    #[attr]
+    id: u32,
+
+    #[element("h2")]
+    title: String,
+
+    #[element]
+    tag: Tag,
+
+    #[element("ul")]
+    extra: Vec<Tag>,
+}
+
+#[test]
+fn runtime_template_overrides_compiled_markup() {
+    let card = OverriddenCard {
+        id: 3,
+        title: "Fish & Chips".to_string(),
+        tag: Tag("new"),
+        extra: vec![],
+    };
+    let compiled = card.to_string();
+    assert_eq!(
+        compiled,
+        r#"<article id="3"><h2>Fish &amp; Chips</h2><li>new</li><ul></ul></article>"#
+    );
+
+    register(
+        "OverriddenCard",
+        r#"<section data-id="{id}"><h1>{title}</h1>{tag}{extra}{{raw}}</section>"#,
+    );
+    assert_eq!(
+        card.to_string(),
+        r#"<section data-id="3"><h1>Fish &amp; Chips</h1><li>new</li>{extra}{raw}</section>"#
+    );
+
+    unregister("OverriddenCard");
+    assert_eq!(card.to_string(), compiled);
+}
@@ -0,0 +1,32 @@
+use crate::Element;
+use crate::tests::voids;
+
+#[test]
+fn explicit_test_id() {
+    #[derive(Element)]
+    #[element("section", test_id = "checkout-card")]
+    #[attr(class = "card")]
+    struct CheckoutCard {
+        #[element]
+        title: &'static str,
+    }
+
+    let html = CheckoutCard { title: "Total" }.to_string();
+
+    assert_eq!(
+        html,
+        r#"<section class="card" data-testid="checkout-card">Total</section>"#
+    );
+}
+
+#[test]
+fn test_id_on_void_root() {
+    #[derive(Element)]
+    #[element("input", test_id = "search")]
+    struct SearchBox {}
+
+    assert_eq!(
+        SearchBox {}.to_string(),
+        voids(r#"<input data-testid="search"/>"#)
+    );
+}
@@ -0,0 +1,141 @@
+use crate::datastar::{PatchElements, PatchMode, PatchSignals};
+use crate::testing::{TestApp, parse_events};
+use crate::{Element, Signal, SignalExtractor};
+use axum::{Router, http::StatusCode, response::Html, routing::get};
+
+#[derive(Signal)]
+pub enum TestSignals {
+    SearchTerm(String),
+    PageNum(i32),
+}
+
+#[derive(Element)]
+#[element("main")]
+struct Home {
+    #[element("h1")]
+    title: &'static str,
+}
+
+fn app() -> TestApp {
+    TestApp::new(
+        Router::new()
+            .route(
+                "/search",
+                get(
+                    |SignalExtractor(term): SignalExtractor<SearchTerm>| async move {
+                        PatchElements::new(vec![
+                            format!("<li>{term}</li>"),
+                            "<li>b</li>".to_string(),
+                        ])
+                        .selector("#results")
+                        .mode(PatchMode::Append)
+                    },
+                )
+                .post(
+                    |SignalExtractor(page): SignalExtractor<PageNum>| async move {
+                        PatchSignals::new(vec![TestSignals::PageNum(page + 1)])
+                    },
+                ),
+            )
+            .route(
+                "/",
+                get(|| async { Html(Home { title: "home" }.to_string()) }),
+            ),
+    )
+}
+
+#[tokio::test]
+async fn get_encodes_signals_in_query() {
+    let response = app()
+        .get("/search")
+        .signals(&[TestSignals::SearchTerm("a & b".to_string())])
+        .send()
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.content_type(), Some("text/event-stream"));
+
+    let events = response.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event.as_deref(), Some("datastar-patch-elements"));
+    assert_eq!(events[0].data("selector").as_deref(), Some("#results"));
+    assert_eq!(events[0].data("mode").as_deref(), Some("append"));
+    assert_eq!(
+        events[0].elements().as_deref(),
+        Some("<li>a & b</li>\n<li>b</li>")
+    );
+}
+
+#[tokio::test]
+async fn post_sends_signals_as_json() {
+    let response = app()
+        .post("/search")
+        .signals(&[TestSignals::PageNum(2)])
+        .send()
+        .await;
+
+    let events = response.events();
+    assert_eq!(events[0].event.as_deref(), Some("datastar-patch-signals"));
+    assert_eq!(events[0].signals(), Some(serde_json::json!({"pageNum": 3})));
+}
+
+#[tokio::test]
+async fn missing_datastar_header_is_rejected() {
+    let response = app().get("/search?datastar=%7B%7D").send().await;
+    assert!(response.status.is_client_error());
+
+    let response = app().get("/").send().await;
+    assert_eq!(response.html(), Some("<main><h1>home</h1></main>"));
+    assert!(response.events().is_empty());
+}
+
+#[tokio::test]
+async fn send_events_stops_reading_endless_streams() {
+    use axum::body::Body;
+    use futures_util::stream::{self, StreamExt};
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    let app = TestApp::new(
+        Router::new()
+            .route(
+                "/endless",
+                get(|| async {
+                    Body::from_stream(stream::repeat_with(|| {
+                        Ok::<_, Infallible>("event: tick\ndata: n 1\n\n")
+                    }))
+                }),
+            )
+            .route(
+                "/stalled",
+                get(|| async {
+                    Body::from_stream(
+                        stream::iter([Ok::<_, Infallible>("event: tick\ndata: n 1\n\nevent: ti")])
+                            .chain(stream::pending()),
+                    )
+                }),
+            ),
+    );
+
+    let response = app
+        .get("/endless")
+        .send_events(2, Duration::from_secs(5))
+        .await;
+    assert!(response.events().len() >= 2);
+
+    let response = app
+        .get("/stalled")
+        .send_events(2, Duration::from_millis(20))
+        .await;
+    assert_eq!(response.events().len(), 1);
+    assert_eq!(response.body, "event: tick\ndata: n 1\n\n");
+}
+
+#[test]
+fn parses_multiple_events() {
+    let events = parse_events("event: a\nid: 1\ndata: x 1\n\n: comment\n\nevent: b\ndata: y\n");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].id.as_deref(), Some("1"));
+    assert_eq!(events[0].data("x").as_deref(), Some("1"));
+    assert_eq!(events[1].data, vec!["y".to_string()]);
+}
@@ -0,0 +1,154 @@
+use crate::Element;
+use crate::tests::voids;
+
+#[derive(Element)]
+#[element("div")]
+#[attr(class = "card")]
+struct Card {
+    #[element]
+    title: String,
+}
+
+#[test]
+fn transparent_forwards_rendering() {
+    #[derive(Element)]
+    #[element(transparent)]
+    struct Wrapped(Card);
+
+    let html = Wrapped(Card {
+        title: "Hi".to_string(),
+    })
+    .to_string();
+
+    assert_eq!(html, r#"<div class="card">Hi</div>"#);
+}
+
+#[test]
+fn transparent_adds_root_attrs() {
+    #[derive(Element)]
+    #[element(transparent)]
+    #[attr(class = "highlighted", data_on_click = "@get('/card')")]
+    struct Highlighted(Card);
+
+    let html = Highlighted(Card {
+        title: "Hi".to_string(),
+    })
+    .to_string();
+
+    assert_eq!(
+        html,
+        r#"<div class="card highlighted" data-on-click="@get('/card')">Hi</div>"#
+    );
+}
+
+#[test]
+fn transparent_overrides_root_attr() {
+    #[derive(Element)]
+    #[element("input")]
+    #[attr(type = "text", name = "q")]
+    struct Search {}
+
+    #[derive(Element)]
+    #[element(transparent)]
+    #[attr(type = "search", autofocus = true)]
+    struct Focused(Search);
+
+    let html = Focused(Search {}).to_string();
+
+    assert_eq!(html, voids(r#"<input type="search" name="q" autofocus/>"#));
+}
+
+#[test]
+fn with_attr_on_derived_element() {
+    use crate::html::RootAttrs;
+
+    let html = Card {
+        title: "Hi".to_string(),
+    }
+    .with_attr("data-test", "checkout-card")
+    .with_attr("class", "wide")
+    .to_string();
+
+    assert_eq!(
+        html,
+        r#"<div class="card wide" data-test="checkout-card">Hi</div>"#
+    );
+}
+
+#[test]
+fn with_attrs_escapes_values() {
+    use crate::html::RootAttrs;
+
+    let html = Card {
+        title: "Hi".to_string(),
+    }
+    .with_attrs([("data-track", "buy \"now\""), ("data-pos", "1")])
+    .to_string();
+
+    assert_eq!(
+        html,
+        r#"<div class="card" data-track="buy &quot;now&quot;" data-pos="1">Hi</div>"#
+    );
+}
+
+#[test]
+fn with_attr_drops_invalid_names() {
+    use crate::html::RootAttrs;
+
+    let html = Card {
+        title: "Hi".to_string(),
+    }
+    .with_attr("onclick=alert(1) x", "y")
+    .with_attrs([("", "empty"), ("data-ok", "1"), ("a\"b", "quote")])
+    .to_string();
+
+    assert_eq!(html, r#"<div class="card" data-ok="1">Hi</div>"#);
+}
+
+#[test]
+fn transparent_enum_renders_variants_without_wrapper() {
+    #[derive(Element)]
+    #[element("p")]
+    struct Notice {
+        #[element]
+        text: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element(transparent)]
+    enum Block {
+        Card(Card),
+        Notice(Notice),
+        #[element("hr")]
+        Divider {},
+        #[element("a")]
+        #[attr(class = "link")]
+        Link {
+            #[attr]
+            href: &'static str,
+
+            #[element]
+            label: &'static str,
+        },
+        Empty,
+    }
+
+    let blocks = [
+        Block::Card(Card {
+            title: "Hi".to_string(),
+        }),
+        Block::Notice(Notice { text: "a < b" }),
+        Block::Divider {},
+        Block::Link {
+            href: "/x",
+            label: "X",
+        },
+        Block::Empty,
+    ];
+    let html: String = blocks.iter().map(ToString::to_string).collect();
+
+    assert_eq!(
+        html,
+        voids(r#"<div class="card">Hi</div><p>a &lt; b</p><hr/><a class="link" href="/x">X</a>"#)
+    );
+}
@@ -1,3 +1,4 @@
+use crate::tests::voids;
 define_url!(UserPageUrl, "/users", user_id: u64);
 define_url!(ItemDetailUrl, "/items/detail", item_id: u64, active: bool);
 define_url!(MultiParamUrl, "/items", item_id: u32, category_id: u32, active: bool);
@@ -18,6 +19,19 @@ fn test_multiple_params_pattern() {
     assert_eq!(ItemDetailUrl::PATTERN, "/items/detail/{item_id}/{active}");
 }
 
+#[test]
+fn test_pattern_syntaxes() {
+    assert_eq!(
+        ItemDetailUrl::PATTERN_BRACED,
+        "/items/detail/{item_id}/{active}"
+    );
+    assert_eq!(
+        ItemDetailUrl::PATTERN_COLON,
+        "/items/detail/:item_id/:active"
+    );
+    assert_eq!(BlogUrl::PATTERN_COLON, "/blog");
+}
+
 #[test]
 fn test_multiple_params_path() {
     let url = ItemDetailUrl::new(5333, true);
@@ -35,8 +49,317 @@ fn test_three_params() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_field_access() {
     let url = ItemDetailUrl::new(999, false);
     assert_eq!(url.item_id, 999);
     assert_eq!(url.active, false);
 }
+
+define_url!(BlogUrl, "/blog");
+define_url!(AuthorUrl, parent = BlogUrl, label = "Author", "/blog/authors", author_id: u64);
+define_url!(PostUrl, parent = AuthorUrl(author_id), "/blog/posts", author_id: u64, post_id: u64);
+
+#[test]
+fn test_no_params() {
+    assert_eq!(BlogUrl::PATTERN, "/blog");
+    assert_eq!(BlogUrl::new().path(), "/blog");
+}
+
+#[test]
+fn test_parent_chain() {
+    use crate::url::{UrlNode, ancestry};
+
+    assert_eq!(AuthorUrl::PATTERN, "/blog/authors/{author_id}");
+    assert_eq!(PostUrl::PATTERN, "/blog/posts/{author_id}/{post_id}");
+
+    let post = PostUrl::new(7, 42);
+    assert_eq!(post.label(), "Post");
+    assert_eq!(
+        ancestry(&post),
+        vec![
+            ("Blog", "/blog".to_string()),
+            ("Author", "/blog/authors/7".to_string()),
+            ("Post", "/blog/posts/7/42".to_string()),
+        ]
+    );
+    assert!(BlogUrl::new().parent().is_none());
+}
+
+#[test]
+fn test_breadcrumbs_for_url() {
+    use crate::components::Breadcrumbs;
+
+    let html = Breadcrumbs::for_url(&PostUrl::new(7, 42))
+        .current_label("Hello & Goodbye")
+        .to_string();
+
+    assert_eq!(
+        html,
+        "<nav aria-label=\"breadcrumb\"><ol><li><a href=\"/blog\">Blog</a></li><li><a href=\"/blog/authors/7\">Author</a></li><li><span aria-current=\"page\">Hello &amp; Goodbye</span></li></ol></nav>"
+    );
+}
+
+#[test]
+fn test_breadcrumbs_root() {
+    use crate::components::Breadcrumbs;
+
+    let html = Breadcrumbs::for_url(&BlogUrl::new()).to_string();
+
+    assert_eq!(
+        html,
+        "<nav aria-label=\"breadcrumb\"><ol><li><span aria-current=\"page\">Blog</span></li></ol></nav>"
+    );
+}
+
+define_url!(HomeUrl, sitemap(priority = 1.0, changefreq = Daily), "/");
+define_url!(ArticleUrl, parent = HomeUrl, sitemap(changefreq = Weekly), "/articles", slug: String);
+define_url!(AboutUrl, sitemap, "/about");
+
+#[test]
+fn test_sitemap_xml() {
+    use crate::seo::{ChangeFreq, SitemapRegistry, SitemapUrl};
+
+    assert_eq!(HomeUrl::PATTERN, "/");
+    assert_eq!(ArticleUrl::PATTERN, "/articles/{slug}");
+    assert_eq!(AboutUrl::PATTERN, "/about");
+    assert_eq!(HomeUrl::PRIORITY, Some(1.0));
+    assert_eq!(ArticleUrl::CHANGEFREQ, Some(ChangeFreq::Weekly));
+    assert_eq!(AboutUrl::PRIORITY, None);
+
+    let registry = SitemapRegistry::new();
+    registry
+        .register(&HomeUrl::new())
+        .register(&ArticleUrl::new("a&b".into()))
+        .register(&AboutUrl::new())
+        .register(&HomeUrl::new());
+
+    let xml = registry.sitemap_xml("https://example.com/").to_string();
+
+    assert_eq!(
+        xml,
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+            "<url><loc>https://example.com/</loc><changefreq>daily</changefreq><priority>1.0</priority></url>",
+            "<url><loc>https://example.com/articles/a&amp;b</loc><changefreq>weekly</changefreq></url>",
+            "<url><loc>https://example.com/about</loc></url>",
+            "</urlset>"
+        )
+    );
+}
+
+#[test]
+fn test_global_sitemap_registry() {
+    crate::seo::register(&ArticleUrl::new("global".into()));
+
+    let xml = crate::seo::sitemap_xml("https://example.com").to_string();
+
+    assert!(xml.contains("<loc>https://example.com/articles/global</loc>"));
+}
+
+#[test]
+fn test_robots_txt() {
+    use crate::seo::Robots;
+
+    let robots = Robots::new()
+        .disallow("/admin")
+        .allow("/")
+        .sitemap("https://example.com/sitemap.xml");
+
+    assert_eq!(
+        robots.to_string(),
+        "User-agent: *\nDisallow: /admin\nAllow: /\nSitemap: https://example.com/sitemap.xml\n"
+    );
+}
+
+#[test]
+fn test_canonical_and_alternate_links() {
+    use crate::seo::{Alternate, Canonical, base_url, set_base_url};
+
+    let _ = set_base_url("https://example.com/");
+    assert_eq!(base_url(), "https://example.com");
+
+    let canonical = Canonical(ArticleUrl::new("hello".into()));
+    assert_eq!(
+        canonical.to_string(),
+        voids("<link rel=\"canonical\" href=\"https://example.com/articles/hello\"/>")
+    );
+
+    let alternate = Alternate::new("fr", ArticleUrl::new("bonjour".into()));
+    assert_eq!(
+        alternate.to_string(),
+        voids(
+            "<link rel=\"alternate\" href=\"https://example.com/articles/bonjour\" hreflang=\"fr\"/>"
+        )
+    );
+}
+
+#[test]
+fn test_url_path_trait() {
+    use crate::url::UrlPath;
+
+    fn pattern_of<U: UrlPath>(_url: &U) -> &'static str {
+        U::pattern()
+    }
+
+    let url = UserPageUrl::new(7);
+    assert_eq!(pattern_of(&url), "/users/{user_id}");
+    assert_eq!(UrlPath::path(&url), "/users/7");
+    assert_eq!(url.to_string(), "/users/7");
+}
+
+#[test]
+fn test_url_path_as_href() {
+    use crate::Element;
+    use crate::components::LoadMore;
+    use crate::url::UrlPath;
+
+    #[derive(Element)]
+    #[element("a")]
+    struct UserLink {
+        #[attr]
+        href: UserPageUrl,
+
+        #[element]
+        name: &'static str,
+    }
+
+    let link = UserLink {
+        href: UserPageUrl::new(3),
+        name: "Ann",
+    };
+    assert_eq!(link.to_string(), r#"<a href="/users/3">Ann</a>"#);
+
+    struct Search(&'static str);
+
+    impl UrlPath for Search {
+        fn pattern() -> &'static str {
+            "/search"
+        }
+
+        fn path(&self) -> String {
+            format!("/search?q={}", self.0)
+        }
+    }
+
+    assert_eq!(Search::pattern(), "/search");
+    assert_eq!(
+        LoadMore::new(&Search("rust")).to_string(),
+        r#"<div id="load-more" data-on-intersect__once="@get('/search?q=rust')"></div>"#
+    );
+}
+
+#[derive(Debug, PartialEq, strum::AsRefStr, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SettingsTab {
+    Profile,
+    TwoFactor,
+}
+
+crate::path_segment!(SettingsTab);
+
+define_url!(SettingsUrl, "/settings", tab: SettingsTab);
+define_url!(TagUrl, "/tags", name: String);
+
+#[test]
+fn test_enum_path_segment() {
+    assert_eq!(SettingsUrl::PATTERN, "/settings/{tab}");
+    assert_eq!(
+        SettingsUrl::new(SettingsTab::TwoFactor).path(),
+        "/settings/two-factor"
+    );
+
+    let url: SettingsUrl =
+        serde_json::from_value(serde_json::json!({ "tab": "two-factor" })).unwrap();
+    assert_eq!(url.tab, SettingsTab::TwoFactor);
+}
+
+#[test]
+fn test_path_segment_errors() {
+    let err = serde_json::from_value::<SettingsUrl>(serde_json::json!({ "tab": "billing" }))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid path parameter `tab`: `billing` is not a valid SettingsTab"
+    );
+
+    let err = serde_json::from_value::<UserPageUrl>(serde_json::json!({}))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "missing path parameter `user_id`");
+}
+
+#[test]
+fn test_string_segment_round_trip() {
+    assert_eq!(TagUrl::new("rust".to_string()).path(), "/tags/rust");
+
+    let url: TagUrl = serde_json::from_value(serde_json::json!({ "name": "c++" })).unwrap();
+    assert_eq!(url.name, "c++");
+}
+
+#[test]
+fn test_preload_hints() {
+    use crate::seo::{Prefetch, Preload};
+
+    assert_eq!(
+        Preload::new("/app.css", "style").to_string(),
+        voids(r#"<link rel="preload" href="/app.css" as="style"/>"#)
+    );
+    assert_eq!(
+        Preload::font("/inter.woff2", "font/woff2").to_string(),
+        voids(
+            r#"<link rel="preload" href="/inter.woff2" as="font" type="font/woff2" crossorigin/>"#
+        )
+    );
+    assert_eq!(
+        Prefetch::new(&AboutUrl::new()).to_string(),
+        voids(r#"<link rel="prefetch" href="/about"/>"#)
+    );
+}
+
+#[test]
+fn test_prefetch_links_register_during_render() {
+    use crate::seo::{Prefetch, PrefetchLink, collect_prefetches};
+
+    let links = [
+        PrefetchLink::new(&AboutUrl::new(), "About"),
+        PrefetchLink::new(&UserPageUrl::new(1), "Ann"),
+        PrefetchLink::new(&AboutUrl::new(), "About us"),
+    ];
+
+    let (body, prefetches) =
+        collect_prefetches(|| links.iter().map(ToString::to_string).collect::<String>());
+
+    assert_eq!(
+        body,
+        r#"<a href="/about">About</a><a href="/users/1">Ann</a><a href="/about">About us</a>"#
+    );
+    assert_eq!(
+        prefetches,
+        vec![
+            Prefetch::new(&AboutUrl::new()),
+            Prefetch::new(&UserPageUrl::new(1)),
+        ]
+    );
+
+    assert_eq!(links[0].to_string(), r#"<a href="/about">About</a>"#);
+    assert!(collect_prefetches(|| ()).1.is_empty());
+}
+
+#[test]
+fn route_patterns_strip_their_prefix() {
+    use crate::url::{is_under_prefix, route_pattern};
+
+    assert!(is_under_prefix("/admin/users", "/admin"));
+    assert!(is_under_prefix("/admin", "/admin"));
+    assert!(is_under_prefix("/status", ""));
+    assert!(!is_under_prefix("/administrator", "/admin"));
+    assert!(!is_under_prefix("/status", "/admin"));
+
+    assert_eq!(route_pattern("/admin/users/{id}", "/admin"), "/users/{id}");
+    assert_eq!(route_pattern("/admin", "/admin"), "/");
+    assert_eq!(route_pattern("/status", ""), "/status");
+    assert_eq!(route_pattern("/status", "/admin"), "/status");
+}
@@ -0,0 +1,67 @@
+use crate::Element;
+use crate::window::{Window, page_count};
+
+#[derive(Element)]
+#[element("li")]
+struct Item {
+    #[element]
+    name: String,
+}
+
+#[derive(Element)]
+#[element("ul")]
+struct ItemList<'a> {
+    #[element]
+    items: Window<'a, Item>,
+}
+
+fn items(count: usize) -> Vec<Item> {
+    (1..=count)
+        .map(|i| Item {
+            name: format!("item {i}"),
+        })
+        .collect()
+}
+
+#[test]
+fn renders_only_the_window() {
+    let all = items(5);
+    let list = ItemList {
+        items: Window::page(&all, 2, 2),
+    };
+
+    assert_eq!(list.to_string(), "<ul><li>item 3</li><li>item 4</li></ul>");
+}
+
+#[test]
+fn window_past_the_end_is_empty() {
+    let all = items(3);
+    let window = Window::new(&all, 10, 5);
+
+    assert!(window.is_empty());
+    assert!(!window.has_next());
+    assert_eq!(ItemList { items: window }.to_string(), "<ul></ul>");
+}
+
+#[test]
+fn page_helpers() {
+    let all = items(7);
+    let first = Window::page(&all, 1, 3);
+    let last = Window::page(&all, 3, 3);
+
+    assert_eq!(first.page_count(), 3);
+    assert_eq!(first.current_page(), 1);
+    assert!(!first.has_prev());
+    assert!(first.has_next());
+
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.current_page(), 3);
+    assert!(last.has_prev());
+    assert!(!last.has_next());
+    assert_eq!(last.total(), 7);
+
+    assert_eq!(page_count(0, 10), 0);
+    assert_eq!(page_count(10, 10), 1);
+    assert_eq!(page_count(11, 10), 2);
+    assert_eq!(page_count(5, 0), 0);
+}
@@ -0,0 +1,48 @@
+use crate::components::Toast;
+use crate::datastar::{PatchElements, PatchSink};
+use crate::ws::channel;
+
+fn notify(sink: &impl PatchSink) -> usize {
+    sink.patch_elements(PatchElements::new(vec!["<p>hi</p>"]).selector("#feed"))
+}
+
+#[tokio::test]
+async fn ws_sender_frames_patches() -> Result<(), anyhow::Error> {
+    let (sender, mut stream) = channel(8);
+
+    assert_eq!(notify(&sender), 1);
+    assert_eq!(
+        stream.recv().await.as_deref(),
+        Some("event: datastar-patch-elements\ndata: selector #feed\ndata: elements <p>hi</p>\n\n")
+    );
+
+    sender.toast(Toast::info("Saved"));
+    let toast = stream.recv().await;
+    let toast = toast.as_deref().unwrap_or_default();
+    assert!(toast.contains("data: selector #toasts"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ws_sender_reports_closed_stream() -> Result<(), anyhow::Error> {
+    let (sender, stream) = channel(8);
+    drop(stream);
+
+    assert!(sender.is_closed());
+    assert_eq!(notify(&sender), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ws_sender_drops_events_once_the_buffer_is_full() -> Result<(), anyhow::Error> {
+    let (sender, mut stream) = channel(1);
+
+    assert_eq!(notify(&sender), 1);
+    assert_eq!(notify(&sender), 0);
+    assert!(stream.recv().await.is_some());
+    assert_eq!(notify(&sender), 1);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::Element;
+
+#[test]
+fn void_elements_get_explicit_closing_tags() {
+    #[derive(Element)]
+    #[element("img")]
+    struct Image {
+        #[attr]
+        src: &'static str,
+    }
+
+    #[derive(Element)]
+    #[element("p")]
+    struct Paragraph {
+        #[element("br")]
+        br: (),
+
+        #[element]
+        image: Image,
+    }
+
+    let paragraph = Paragraph {
+        br: (),
+        image: Image { src: "a.png" },
+    };
+    assert_eq!(
+        paragraph.to_string(),
+        r#"<p><br></br><img src="a.png"></img></p>"#
+    );
+}
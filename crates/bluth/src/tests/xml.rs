@@ -0,0 +1,169 @@
+use crate::Element;
+use crate::tests::voids;
+
+#[test]
+fn xml_self_closes_empty_elements() {
+    #[derive(Element)]
+    #[element("svg", xml)]
+    #[attr(xmlns = "http://www.w3.org/2000/svg", xmlns:xlink = "http://www.w3.org/1999/xlink")]
+    struct Icon {
+        #[element("use")]
+        #[attr(xlink:href = "#{symbol}")]
+        glyph: (),
+
+        #[element("title")]
+        title: Vec<String>,
+
+        symbol: &'static str,
+    }
+
+    let icon = Icon {
+        glyph: (),
+        title: vec![],
+        symbol: "star",
+    };
+
+    assert_eq!(
+        icon.to_string(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"><use xlink:href=\"#star\"/><title/></svg>"
+    );
+}
+
+#[test]
+fn xml_disables_void_elements() {
+    #[derive(Element)]
+    #[element("item", xml)]
+    struct FeedItem {
+        #[element("link")]
+        link: String,
+
+        #[element("source")]
+        source: Option<String>,
+    }
+
+    let item = FeedItem {
+        link: "https://example.com/?a=1&b=2".into(),
+        source: None,
+    };
+
+    assert_eq!(
+        item.to_string(),
+        "<item><link>https://example.com/?a=1&amp;b=2</link><source/></item>"
+    );
+}
+
+#[test]
+fn xml_escapes_text_content() {
+    #[derive(Element)]
+    #[element("note", xml)]
+    struct Note {
+        #[element("body")]
+        body: String,
+
+        #[element("tags")]
+        tags: Vec<&'static str>,
+
+        #[element]
+        count: u32,
+    }
+
+    let note = Note {
+        body: r#"Tom & "Jerry" <it's>"#.into(),
+        tags: vec!["a<b"],
+        count: 3,
+    };
+
+    assert_eq!(
+        note.to_string(),
+        "<note><body>Tom &amp; &quot;Jerry&quot; &lt;it&apos;s&gt;</body><tags>a&lt;b</tags>3</note>"
+    );
+}
+
+#[test]
+fn xml_nested_components() {
+    #[derive(Element)]
+    #[element("urlset", xml)]
+    #[attr(xmlns = "http://www.sitemaps.org/schemas/sitemap/0.9")]
+    struct UrlSet {
+        #[element]
+        urls: Vec<Url>,
+    }
+
+    #[derive(Element)]
+    #[element("url", xml)]
+    struct Url {
+        #[element("loc")]
+        loc: String,
+    }
+
+    let set = UrlSet {
+        urls: vec![Url {
+            loc: "https://example.com/".into(),
+        }],
+    };
+
+    assert_eq!(
+        set.to_string(),
+        "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"><url><loc>https://example.com/</loc></url></urlset>"
+    );
+}
+
+#[test]
+fn xml_unit_variant() {
+    #[derive(Element)]
+    #[element("status", xml)]
+    enum Status {
+        Empty,
+    }
+
+    assert_eq!(Status::Empty.to_string(), "<status/>");
+}
+
+#[test]
+fn xml_field_level_option() {
+    #[derive(Element)]
+    #[element("div")]
+    struct Page {
+        #[element("math", xml)]
+        formula: (),
+
+        #[element("br")]
+        br: (),
+    }
+
+    let page = Page {
+        formula: (),
+        br: (),
+    };
+
+    assert_eq!(page.to_string(), voids("<div><math/><br/></div>"));
+}
+
+#[test]
+fn raw_fields_skip_escaping() {
+    use crate::html::{PreEscaped, Raw};
+
+    #[derive(Element)]
+    #[element("entry", xml)]
+    struct Entry {
+        #[element("title")]
+        title: String,
+
+        #[element("content")]
+        content: Raw<String>,
+
+        #[element("summary")]
+        summary: Option<PreEscaped<&'static str>>,
+    }
+
+    let entry = Entry {
+        title: "<b>Tom & Jerry</b>".to_string(),
+        content: Raw("<p>Tom &amp; Jerry</p>".to_string()),
+        summary: Some(Raw("<i>cartoon</i>")),
+    };
+
+    assert_eq!(
+        entry.to_string(),
+        "<entry><title>&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</title><content><p>Tom &amp; Jerry</p></content><summary><i>cartoon</i></summary></entry>"
+    );
+}
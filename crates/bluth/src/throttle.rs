@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::datastar::{PatchElements, PatchSink};
+
+struct Slot {
+    last_sent: Instant,
+    pending: Option<String>,
+    scheduled: bool,
+}
+
+#[derive(Clone)]
+pub struct Throttle<S> {
+    sink: S,
+    window: Duration,
+    slots: Arc<Mutex<HashMap<String, Slot>>>,
+}
+
+impl<S> Throttle<S>
+where
+    S: PatchSink + Clone + Send + Sync + 'static,
+{
+    pub fn new(sink: S, window: Duration) -> Self {
+        Self {
+            sink,
+            window,
+            slots: Arc::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.sink
+    }
+
+    fn coalesce(&self, key: String, event: String) -> usize {
+        let now = Instant::now();
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+
+        let Some(slot) = slots.get_mut(&key) else {
+            slots.insert(
+                key,
+                Slot {
+                    last_sent: now,
+                    pending: None,
+                    scheduled: false,
+                },
+            );
+            drop(slots);
+            return self.sink.send(event);
+        };
+
+        let elapsed = now.duration_since(slot.last_sent);
+        if elapsed >= self.window && !slot.scheduled {
+            slot.last_sent = now;
+            drop(slots);
+            return self.sink.send(event);
+        }
+
+        if slot.scheduled {
+            slot.pending = Some(event);
+            return 0;
+        }
+
+        // Without a runtime to flush the trailing patch later, fall back to
+        // sending it right away rather than dropping it.
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            slot.last_sent = now;
+            drop(slots);
+            return self.sink.send(event);
+        };
+
+        slot.pending = Some(event);
+        slot.scheduled = true;
+        let delay = self.window.saturating_sub(elapsed);
+        let throttle = self.clone();
+        runtime.spawn(async move {
+            tokio::time::sleep(delay).await;
+            throttle.flush(&key);
+        });
+        0
+    }
+
+    fn flush(&self, key: &str) {
+        let event = {
+            let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(slot) = slots.get_mut(key) else {
+                return;
+            };
+            slot.scheduled = false;
+            slot.last_sent = Instant::now();
+            slot.pending.take()
+        };
+
+        if let Some(event) = event {
+            self.sink.send(event);
+        }
+    }
+}
+
+impl<S> PatchSink for Throttle<S>
+where
+    S: PatchSink + Clone + Send + Sync + 'static,
+{
+    fn send(&self, event: impl Display) -> usize {
+        self.sink.send(event)
+    }
+
+    fn patch_elements<T: Display>(&self, patch: PatchElements<T>) -> usize {
+        // Selector-less patches target whatever ids their elements carry, so
+        // two of them may hit unrelated nodes; only coalesce explicit targets.
+        match patch.selector.clone() {
+            Some(key) => self.coalesce(key, patch.to_string()),
+            None => self.sink.send(patch),
+        }
+    }
+}
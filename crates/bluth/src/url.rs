@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+pub trait UrlNode {
+    fn label(&self) -> &'static str;
+    fn path(&self) -> String;
+    fn parent(&self) -> Option<Box<dyn UrlNode>>;
+}
+
+pub trait UrlPath {
+    fn pattern() -> &'static str
+    where
+        Self: Sized;
+
+    fn path(&self) -> String;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSegmentError {
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl Display for PathSegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid {}", self.value, self.expected)
+    }
+}
+
+impl std::error::Error for PathSegmentError {}
+
+pub trait PathSegment: Sized {
+    fn encode(&self) -> String;
+    fn decode(segment: &str) -> Result<Self, PathSegmentError>;
+}
+
+impl PathSegment for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(segment: &str) -> Result<Self, PathSegmentError> {
+        Ok(segment.to_string())
+    }
+}
+
+macro_rules! impl_path_segment_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl PathSegment for $ty {
+                fn encode(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode(segment: &str) -> Result<Self, PathSegmentError> {
+                    segment.parse().map_err(|_| PathSegmentError {
+                        value: segment.to_string(),
+                        expected: stringify!($ty),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_path_segment_from_str!(
+    bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+#[macro_export]
+macro_rules! path_segment {
+    ($ty:ty) => {
+        impl $crate::url::PathSegment for $ty {
+            fn encode(&self) -> String {
+                ::core::convert::AsRef::<str>::as_ref(self).to_string()
+            }
+
+            fn decode(segment: &str) -> Result<Self, $crate::url::PathSegmentError> {
+                ::core::str::FromStr::from_str(segment).map_err(|_| $crate::url::PathSegmentError {
+                    value: segment.to_string(),
+                    expected: stringify!($ty),
+                })
+            }
+        }
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathParamError {
+    Missing(&'static str),
+    Invalid {
+        param: &'static str,
+        source: PathSegmentError,
+    },
+}
+
+impl Display for PathParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathParamError::Missing(param) => write!(f, "missing path parameter `{}`", param),
+            PathParamError::Invalid { param, source } => {
+                write!(f, "invalid path parameter `{}`: {}", param, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathParamError {}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(transparent)]
+pub struct PathParams(HashMap<String, String>);
+
+impl PathParams {
+    pub fn get<T: PathSegment>(&self, param: &'static str) -> Result<T, PathParamError> {
+        let value = self.0.get(param).ok_or(PathParamError::Missing(param))?;
+        T::decode(value).map_err(|source| PathParamError::Invalid { param, source })
+    }
+}
+
+impl FromIterator<(String, String)> for PathParams {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+// `define_routes!` asserts this in a const block, so a route outside its
+// group's prefix fails to compile instead of panicking when the router is built.
+pub const fn is_under_prefix(pattern: &str, prefix: &str) -> bool {
+    let (pattern, prefix) = (pattern.as_bytes(), prefix.as_bytes());
+    if pattern.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if pattern[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    prefix.is_empty() || pattern.len() == prefix.len() || pattern[prefix.len()] == b'/'
+}
+
+/// The part of `pattern` below `prefix`, or `pattern` itself when it is not
+/// under `prefix` (see [`is_under_prefix`]).
+pub const fn route_pattern(pattern: &'static str, prefix: &str) -> &'static str {
+    if !is_under_prefix(pattern, prefix) {
+        return pattern;
+    }
+    let rest = pattern.split_at(prefix.len()).1;
+    if rest.is_empty() { "/" } else { rest }
+}
+
+pub fn ancestry(url: &dyn UrlNode) -> Vec<(&'static str, String)> {
+    let mut chain = vec![(url.label(), url.path())];
+    let mut current = url.parent();
+    while let Some(node) = current {
+        chain.push((node.label(), node.path()));
+        current = node.parent();
+    }
+    chain.reverse();
+    chain
+}
@@ -0,0 +1,105 @@
+use std::slice;
+
+pub fn page_count(total: usize, per_page: usize) -> usize {
+    if per_page == 0 {
+        return 0;
+    }
+    total.div_ceil(per_page)
+}
+
+#[derive(Debug)]
+pub struct Window<'a, T> {
+    items: &'a [T],
+    offset: usize,
+    limit: usize,
+}
+
+impl<T> Clone for Window<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Window<'_, T> {}
+
+impl<'a, T> Window<'a, T> {
+    pub fn new(items: &'a [T], offset: usize, limit: usize) -> Self {
+        Self {
+            items,
+            offset,
+            limit,
+        }
+    }
+
+    pub fn page(items: &'a [T], page: usize, per_page: usize) -> Self {
+        let offset = page.saturating_sub(1).saturating_mul(per_page);
+        Self::new(items, offset, per_page)
+    }
+
+    pub fn items(&self) -> &'a [T] {
+        let start = self.offset.min(self.items.len());
+        let end = start.saturating_add(self.limit).min(self.items.len());
+        &self.items[start..end]
+    }
+
+    pub fn iter(&self) -> slice::Iter<'a, T> {
+        self.items().iter()
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn len(&self) -> usize {
+        self.items().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items().is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn page_count(&self) -> usize {
+        page_count(self.items.len(), self.limit)
+    }
+
+    pub fn current_page(&self) -> usize {
+        if self.limit == 0 {
+            return 0;
+        }
+        self.offset / self.limit + 1
+    }
+
+    pub fn has_prev(&self) -> bool {
+        self.offset > 0 && self.limit > 0
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.offset.saturating_add(self.limit) < self.items.len()
+    }
+}
+
+impl<'a, T> IntoIterator for Window<'a, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &Window<'a, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
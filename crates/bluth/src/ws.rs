@@ -0,0 +1,92 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::components::{Toast, ToastQueue};
+use crate::datastar::PatchSink;
+
+/// Events buffered per socket by [`upgrade`] before [`WsSender::send`] starts
+/// dropping them.
+pub const DEFAULT_CAPACITY: usize = 64;
+
+#[derive(Clone)]
+pub struct WsSender {
+    sender: mpsc::Sender<Arc<str>>,
+}
+
+impl WsSender {
+    /// Queues `event` for the socket, returning 0 when the socket has closed
+    /// or is too far behind to take it.
+    pub fn send(&self, event: impl Display) -> usize {
+        match self.sender.try_send(Arc::from(event.to_string())) {
+            Ok(()) => 1,
+            Err(_) => 0,
+        }
+    }
+
+    pub fn toast(&self, toast: Toast) -> usize {
+        let mut queue = ToastQueue::new();
+        queue.push(toast);
+        self.patch_elements(queue.into_patch())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+impl PatchSink for WsSender {
+    fn send(&self, event: impl Display) -> usize {
+        WsSender::send(self, event)
+    }
+}
+
+pub struct WsStream {
+    receiver: mpsc::Receiver<Arc<str>>,
+}
+
+impl WsStream {
+    pub async fn recv(&mut self) -> Option<Arc<str>> {
+        self.receiver.recv().await
+    }
+
+    /// Forwards events to the socket until every sender is dropped or the
+    /// client goes away, whichever comes first.
+    pub async fn serve(mut self, mut socket: WebSocket) {
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => {
+                    let Some(event) = event else { break };
+                    if socket.send(Message::text(event.as_ref())).await.is_err() {
+                        return;
+                    }
+                }
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+        let _ = socket.send(Message::Close(None)).await;
+    }
+}
+
+pub fn channel(capacity: usize) -> (WsSender, WsStream) {
+    let (sender, receiver) = mpsc::channel(capacity);
+    (WsSender { sender }, WsStream { receiver })
+}
+
+pub fn upgrade<F, Fut>(ws: WebSocketUpgrade, handler: F) -> Response
+where
+    F: FnOnce(WsSender) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    ws.on_upgrade(move |socket| async move {
+        let (sender, stream) = channel(DEFAULT_CAPACITY);
+        tokio::spawn(handler(sender));
+        stream.serve(socket).await;
+    })
+}
@@ -0,0 +1,64 @@
+use bluth::Element;
+
+#[derive(Element)]
+#[element("section")]
+#[attr(class = "card")]
+struct CheckoutCard {
+    #[element("h2")]
+    title: &'static str,
+}
+
+#[derive(Element)]
+#[element("input")]
+struct SearchBox {}
+
+#[derive(Element)]
+#[element("nav", test_id = "main-menu")]
+struct Menu {}
+
+#[derive(Element)]
+#[element("main")]
+struct Page {
+    #[element("aside")]
+    note: &'static str,
+
+    #[element]
+    card: CheckoutCard,
+}
+
+#[test]
+fn derives_test_ids_from_the_type_name() {
+    assert_eq!(
+        CheckoutCard { title: "Total" }.to_string(),
+        r#"<section class="card" data-testid="checkout-card"><h2>Total</h2></section>"#
+    );
+    #[cfg(not(feature = "xhtml"))]
+    let expected = r#"<input data-testid="search-box"/>"#;
+    #[cfg(feature = "xhtml")]
+    let expected = r#"<input data-testid="search-box"></input>"#;
+    assert_eq!(SearchBox {}.to_string(), expected);
+}
+
+#[test]
+fn explicit_test_id_wins() {
+    assert_eq!(
+        Menu {}.to_string(),
+        r#"<nav data-testid="main-menu"></nav>"#
+    );
+}
+
+#[test]
+fn only_root_tags_get_test_ids() {
+    let page = Page {
+        note: "Free shipping",
+        card: CheckoutCard { title: "Total" },
+    };
+
+    assert_eq!(
+        page.to_string(),
+        concat!(
+            r#"<main data-testid="page"><aside>Free shipping</aside>"#,
+            r#"<section class="card" data-testid="checkout-card"><h2>Total</h2></section></main>"#
+        )
+    );
+}
@@ -0,0 +1,432 @@
+use bluth::html::is_void_element;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Element {
+        tag: String,
+        attrs: Vec<(String, Option<String>)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c: char| !pred(c)).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn parse_nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return match parent {
+                    Some(tag) => Err(format!("unclosed <{}>", tag)),
+                    None => Ok(nodes),
+                };
+            }
+            if let Some(after) = rest.strip_prefix("<!--") {
+                let end = after.find("-->").ok_or("unterminated comment")?;
+                self.pos += 4 + end + 3;
+            } else if rest.starts_with("<!") {
+                let end = rest.find('>').ok_or("unterminated declaration")?;
+                self.pos += end + 1;
+            } else if let Some(after) = rest.strip_prefix("</") {
+                let end = after.find('>').ok_or("unterminated closing tag")?;
+                let tag = after[..end].trim().to_ascii_lowercase();
+                self.pos += 2 + end + 1;
+                return match parent {
+                    Some(open) if open == tag => Ok(nodes),
+                    _ => Err(format!("unexpected </{}>", tag)),
+                };
+            } else if rest.starts_with('<') {
+                nodes.push(self.parse_element()?);
+            } else {
+                let text = self.take_while(|c| c != '<');
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text));
+                }
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<Node, String> {
+        self.pos += 1;
+        let tag = self
+            .take_while(|c| c.is_ascii_alphanumeric() || c == '-')
+            .to_ascii_lowercase();
+        if tag.is_empty() {
+            return Err("expected a tag name after `<`".to_string());
+        }
+
+        let mut attrs = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if let Some(after) = rest.strip_prefix("/>") {
+                self.pos = self.input.len() - after.len();
+                return Ok(Node::Element {
+                    tag,
+                    attrs,
+                    children: Vec::new(),
+                });
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            if rest.is_empty() {
+                return Err(format!("unterminated <{}", tag));
+            }
+            let name = self
+                .take_while(|c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/'))
+                .to_string();
+            if name.is_empty() {
+                return Err(format!("malformed attribute in <{}>", tag));
+            }
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                let quote = self
+                    .rest()
+                    .chars()
+                    .next()
+                    .filter(|c| *c == '"' || *c == '\'');
+                Some(match quote {
+                    Some(quote) => {
+                        self.pos += 1;
+                        let value = self.take_while(|c| c != quote).to_string();
+                        if self.rest().is_empty() {
+                            return Err(format!("unterminated attribute value in <{}>", tag));
+                        }
+                        self.pos += 1;
+                        value
+                    }
+                    None => self
+                        .take_while(|c| !c.is_whitespace() && c != '>')
+                        .to_string(),
+                })
+            } else {
+                None
+            };
+            attrs.push((name, value));
+        }
+
+        let children = if is_void_element(&tag) {
+            Vec::new()
+        } else {
+            self.parse_nodes(Some(&tag))?
+        };
+        Ok(Node::Element {
+            tag,
+            attrs,
+            children,
+        })
+    }
+}
+
+fn shape(node: &Node) -> String {
+    match node {
+        Node::Text(_) => "#text".to_string(),
+        Node::Element { tag, children, .. } => {
+            let children: Vec<_> = children.iter().map(shape).collect();
+            format!("{}({})", tag, children.join(","))
+        }
+    }
+}
+
+fn is_repeated(children: &[Node]) -> bool {
+    children.len() > 1
+        && children
+            .iter()
+            .all(|child| matches!(child, Node::Element { .. }))
+        && children
+            .iter()
+            .all(|child| shape(child) == shape(&children[0]))
+}
+
+fn attr_key(name: &str) -> String {
+    let is_ident = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name.starts_with(|c: char| c.is_ascii_alphabetic());
+    if is_ident {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+fn attr_list(attrs: &[(String, Option<String>)]) -> Option<String> {
+    if attrs.is_empty() {
+        return None;
+    }
+    let items: Vec<String> = attrs
+        .iter()
+        .map(|(name, value)| match value {
+            Some(value) => format!(
+                "{} = {:?}",
+                attr_key(name),
+                value.replace('{', "{{").replace('}', "}}")
+            ),
+            None => attr_key(name),
+        })
+        .collect();
+    Some(format!("#[attr({})]", items.join(", ")))
+}
+
+#[derive(Default)]
+struct Emitter {
+    structs: Vec<String>,
+    names: HashMap<String, usize>,
+}
+
+impl Emitter {
+    fn unique(&mut self, base: String) -> String {
+        let count = self.names.entry(base.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            base
+        } else {
+            format!("{}{}", base, count)
+        }
+    }
+
+    fn node_name(node: &Node) -> String {
+        match node {
+            Node::Element { tag, attrs, .. } => attrs
+                .iter()
+                .find(|(name, _)| name == "class")
+                .and_then(|(_, value)| value.as_deref())
+                .and_then(|class| class.split_whitespace().next())
+                .unwrap_or(tag)
+                .to_string(),
+            Node::Text(_) => "text".to_string(),
+        }
+    }
+
+    fn emit_struct(&mut self, name: String, node: &Node) -> String {
+        let Node::Element {
+            tag,
+            attrs,
+            children,
+        } = node
+        else {
+            unreachable!("structs are emitted for elements");
+        };
+
+        let mut fields = Vec::new();
+        let mut field_names = HashMap::new();
+        let mut field_name = |base: String| {
+            let count = field_names.entry(base.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                base
+            } else {
+                format!("{}_{}", base, count)
+            }
+        };
+
+        for child in children {
+            let field = field_name(Self::node_name(child).to_snake_case());
+            let (annotations, ty) = self.child_field(child);
+            fields.push((annotations, field, ty));
+        }
+
+        let mut out = String::new();
+        let _ = writeln!(out, "#[derive(Element)]");
+        let _ = writeln!(out, "#[element({:?})]", tag);
+        if let Some(attrs) = attr_list(attrs) {
+            let _ = writeln!(out, "{}", attrs);
+        }
+        if fields.is_empty() {
+            let _ = writeln!(out, "pub struct {} {{}}", name);
+        } else {
+            let _ = writeln!(out, "pub struct {} {{", name);
+            for (i, (annotations, field, ty)) in fields.iter().enumerate() {
+                if i > 0 {
+                    let _ = writeln!(out);
+                }
+                for annotation in annotations {
+                    let _ = writeln!(out, "    {}", annotation);
+                }
+                let _ = writeln!(out, "    pub {}: {},", field, ty);
+            }
+            let _ = writeln!(out, "}}");
+        }
+        self.structs.push(out);
+        name
+    }
+
+    fn child_field(&mut self, child: &Node) -> (Vec<String>, String) {
+        let Node::Element {
+            tag,
+            attrs,
+            children,
+        } = child
+        else {
+            return (vec!["#[element]".to_string()], "String".to_string());
+        };
+
+        let mut annotations = vec![format!("#[element({:?})]", tag)];
+        annotations.extend(attr_list(attrs));
+
+        if is_void_element(tag) {
+            return (annotations, "()".to_string());
+        }
+        match children.as_slice() {
+            [] => (annotations, "()".to_string()),
+            [Node::Text(_)] => (annotations, "String".to_string()),
+            _ if is_repeated(children) => {
+                let base = Self::node_name(&children[0]).to_upper_camel_case();
+                let item = self.unique(base);
+                let item = self.emit_struct(item, &children[0]);
+                (annotations, format!("Vec<{}>", item))
+            }
+            _ => {
+                let base = Self::node_name(child).to_upper_camel_case();
+                let name = self.unique(base);
+                let name = self.emit_struct(name, child);
+                (vec!["#[element]".to_string()], name)
+            }
+        }
+    }
+}
+
+pub fn convert(html: &str, name: &str) -> Result<String, String> {
+    let mut parser = Parser {
+        input: html,
+        pos: 0,
+    };
+    let nodes = parser.parse_nodes(None)?;
+    let mut elements = nodes
+        .iter()
+        .filter(|node| matches!(node, Node::Element { .. }));
+    let root = match (elements.next(), elements.next()) {
+        (Some(root), None) => root,
+        (None, _) => return Err("no element found".to_string()),
+        (Some(_), Some(_)) => return Err("expected a single root element".to_string()),
+    };
+
+    let mut emitter = Emitter::default();
+    let name = emitter.unique(name.to_upper_camel_case());
+    emitter.emit_struct(name, root);
+
+    let mut out = String::from("use bluth::Element;\n");
+    let (root, nested) = emitter
+        .structs
+        .split_last()
+        .expect("root struct was emitted");
+    for item in std::iter::once(root).chain(nested) {
+        out.push('\n');
+        out.push_str(item);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_nested_markup() {
+        let html = r#"
+            <!-- card -->
+            <div class="card" data-id="7">
+                <h2>Title</h2>
+                <ul class="tags">
+                    <li>rust</li>
+                    <li>html</li>
+                </ul>
+                <footer><a href="/more">More</a><button disabled>Go</button></footer>
+                <img src="/a.png" alt="A">
+            </div>
+        "#;
+
+        assert_eq!(
+            convert(html, "card").unwrap(),
+            concat!(
+                "use bluth::Element;\n",
+                "\n",
+                "#[derive(Element)]\n",
+                "#[element(\"div\")]\n",
+                "#[attr(class = \"card\", \"data-id\" = \"7\")]\n",
+                "pub struct Card {\n",
+                "    #[element(\"h2\")]\n",
+                "    pub h2: String,\n",
+                "\n",
+                "    #[element(\"ul\")]\n",
+                "    #[attr(class = \"tags\")]\n",
+                "    pub tags: Vec<Li>,\n",
+                "\n",
+                "    #[element]\n",
+                "    pub footer: Footer,\n",
+                "\n",
+                "    #[element(\"img\")]\n",
+                "    #[attr(src = \"/a.png\", alt = \"A\")]\n",
+                "    pub img: (),\n",
+                "}\n",
+                "\n",
+                "#[derive(Element)]\n",
+                "#[element(\"li\")]\n",
+                "pub struct Li {\n",
+                "    #[element]\n",
+                "    pub text: String,\n",
+                "}\n",
+                "\n",
+                "#[derive(Element)]\n",
+                "#[element(\"footer\")]\n",
+                "pub struct Footer {\n",
+                "    #[element(\"a\")]\n",
+                "    #[attr(href = \"/more\")]\n",
+                "    pub a: String,\n",
+                "\n",
+                "    #[element(\"button\")]\n",
+                "    #[attr(disabled)]\n",
+                "    pub button: String,\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_markup() {
+        assert_eq!(
+            convert("<div><span></div>", "x"),
+            Err("unexpected </div>".to_string())
+        );
+        assert_eq!(
+            convert("<p>a</p><p>b</p>", "x"),
+            Err("expected a single root element".to_string())
+        );
+        assert_eq!(
+            convert(r#"<a href="foo"#, "x"),
+            Err("unterminated attribute value in <a>".to_string())
+        );
+        assert!(
+            convert(r#"<a title="{x}">x</a>"#, "link")
+                .unwrap()
+                .contains(r#"#[attr(title = "{{x}}")]"#)
+        );
+    }
+}
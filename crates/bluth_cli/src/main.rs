@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod import;
+mod templates;
+
+use templates::Param;
+
+const USAGE: &str = "\
+usage:
+  bluth new component <Name> [--tag <tag>] [--attrs a,b,c] [--out <dir>] [--force]
+  bluth new signals <Name> [name:Type ...] [--out <dir>] [--force]
+  bluth new url <Name> <prefix> [name:Type ...] [--out <dir>] [--force]
+  bluth import <file.html|-> [--name <Name>]";
+
+struct Options {
+    positional: Vec<String>,
+    tag: String,
+    attrs: Vec<String>,
+    out: PathBuf,
+    force: bool,
+    name: Option<String>,
+}
+
+fn parse_options(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        positional: Vec::new(),
+        tag: "div".to_string(),
+        attrs: Vec::new(),
+        out: PathBuf::from("."),
+        force: false,
+        name: None,
+    };
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--tag" => options.tag = value("--tag")?,
+            "--attrs" => {
+                options.attrs = value("--attrs")?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|attr| !attr.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "--out" => options.out = PathBuf::from(value("--out")?),
+            "--force" => options.force = true,
+            "--name" => options.name = Some(value("--name")?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}`", flag)),
+            _ => options.positional.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+fn generate(options: &Options) -> Result<(String, String), String> {
+    let positional: Vec<&str> = options.positional.iter().map(String::as_str).collect();
+    let params = |specs: &[&str]| -> Result<Vec<Param>, String> {
+        specs.iter().map(|spec| Param::parse(spec)).collect()
+    };
+
+    match positional.as_slice() {
+        ["new", "component", name] => Ok((
+            templates::file_name(name),
+            templates::component(name, &options.tag, &options.attrs),
+        )),
+        ["new", "signals", name, specs @ ..] => Ok((
+            templates::file_name(name),
+            templates::signals(name, &params(specs)?),
+        )),
+        ["new", "url", name, prefix, specs @ ..] if prefix.starts_with('/') => Ok((
+            templates::file_name(name),
+            templates::url(name, prefix, &params(specs)?),
+        )),
+        ["new", "url", _, prefix, ..] => {
+            Err(format!("url prefix must start with `/`, got `{}`", prefix))
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn import(options: &Options, source: &str) -> Result<String, String> {
+    let html = if source == "-" {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(source)
+    }
+    .map_err(|err| format!("failed to read {}: {}", source, err))?;
+
+    let name = options.name.clone().unwrap_or_else(|| {
+        std::path::Path::new(source)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| *stem != "-")
+            .unwrap_or("Imported")
+            .to_string()
+    });
+    import::convert(&html, &name)
+}
+
+fn run() -> Result<Option<PathBuf>, String> {
+    let options = parse_options(std::env::args().skip(1))?;
+    if let [command, source] = options.positional.as_slice()
+        && command == "import"
+    {
+        print!("{}", import(&options, source)?);
+        return Ok(None);
+    }
+    let (file_name, contents) = generate(&options)?;
+    let path = options.out.join(file_name);
+
+    if path.exists() && !options.force {
+        return Err(format!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        ));
+    }
+    std::fs::create_dir_all(&options.out)
+        .and_then(|_| std::fs::write(&path, contents))
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+    Ok(Some(path))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(Some(path)) => {
+            println!("created {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(None) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
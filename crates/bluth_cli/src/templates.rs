@@ -0,0 +1,166 @@
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub ty: String,
+}
+
+impl Param {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec.split_once(':') {
+            Some((name, ty)) if !name.is_empty() && !ty.is_empty() => Ok(Self {
+                name: name.to_string(),
+                ty: ty.to_string(),
+            }),
+            _ => Err(format!("expected `name:Type`, got `{}`", spec)),
+        }
+    }
+}
+
+pub fn file_name(type_name: &str) -> String {
+    format!("{}.rs", type_name.to_snake_case())
+}
+
+fn field_name(attr: &str) -> String {
+    let name = attr.to_snake_case();
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+pub fn component(name: &str, tag: &str, attrs: &[String]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "use bluth::Element;");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#[derive(Element)]");
+    let _ = writeln!(out, "#[element(\"{}\")]", tag);
+    if attrs.is_empty() {
+        let _ = writeln!(out, "pub struct {} {{}}", name.to_upper_camel_case());
+        return out;
+    }
+    let _ = writeln!(out, "pub struct {} {{", name.to_upper_camel_case());
+    for (i, attr) in attrs.iter().enumerate() {
+        if i > 0 {
+            let _ = writeln!(out);
+        }
+        let field = field_name(attr);
+        if field.trim_end_matches('_').replace('_', "-") == *attr {
+            let _ = writeln!(out, "    #[attr]");
+        } else {
+            let _ = writeln!(out, "    #[attr(\"{}\")]", attr);
+        }
+        let _ = writeln!(out, "    pub {}: String,", field);
+    }
+    let _ = writeln!(out, "}}");
+    out
+}
+
+pub fn signals(name: &str, params: &[Param]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "use bluth::Signal;");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#[derive(Signal)]");
+    let _ = writeln!(out, "pub enum {} {{", name.to_upper_camel_case());
+    for param in params {
+        let _ = writeln!(
+            out,
+            "    {}({}),",
+            param.name.to_upper_camel_case(),
+            param.ty
+        );
+    }
+    let _ = writeln!(out, "}}");
+    out
+}
+
+pub fn url(name: &str, prefix: &str, params: &[Param]) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "bluth::define_url!({}, \"{}\"",
+        name.to_upper_camel_case(),
+        prefix
+    );
+    for param in params {
+        let _ = write!(out, ", {}: {}", field_name(&param.name), param.ty);
+    }
+    let _ = writeln!(out, ");");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_with_attrs() {
+        let attrs = ["class", "id", "data-user", "type", "aria-label"].map(String::from);
+        assert_eq!(
+            component("card", "div", &attrs),
+            concat!(
+                "use bluth::Element;\n",
+                "\n",
+                "#[derive(Element)]\n",
+                "#[element(\"div\")]\n",
+                "pub struct Card {\n",
+                "    #[attr]\n",
+                "    pub class: String,\n",
+                "\n",
+                "    #[attr]\n",
+                "    pub id: String,\n",
+                "\n",
+                "    #[attr]\n",
+                "    pub data_user: String,\n",
+                "\n",
+                "    #[attr]\n",
+                "    pub type_: String,\n",
+                "\n",
+                "    #[attr]\n",
+                "    pub aria_label: String,\n",
+                "}\n",
+            )
+        );
+        assert_eq!(file_name("PrimaryCard"), "primary_card.rs");
+    }
+
+    #[test]
+    fn signals_enum() {
+        let params = vec![
+            Param::parse("search:String").unwrap(),
+            Param::parse("page_num:u32").unwrap(),
+        ];
+        assert_eq!(
+            signals("PageSignals", &params),
+            concat!(
+                "use bluth::Signal;\n",
+                "\n",
+                "#[derive(Signal)]\n",
+                "pub enum PageSignals {\n",
+                "    Search(String),\n",
+                "    PageNum(u32),\n",
+                "}\n",
+            )
+        );
+        assert!(Param::parse("search").is_err());
+    }
+
+    #[test]
+    fn url_definition() {
+        let params = vec![Param::parse("post_id:u64").unwrap()];
+        assert_eq!(
+            url("PostUrl", "/posts", &params),
+            "bluth::define_url!(PostUrl, \"/posts\", post_id: u64);\n"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use proc_macro2::{Span, TokenStream};
+use syn::ext::IdentExt;
 use syn::parse::Parser;
-use syn::{Attribute, Ident, Meta, Type};
+use syn::{Attribute, GenericArgument, Ident, Meta, PathArguments, Type};
 
 #[derive(Debug, Clone)]
 pub struct AttrSpec {
@@ -22,6 +23,15 @@ pub enum AttrValue {
     Path(syn::Path),
     SignalFieldBinding(syn::Ident),
     Expr(syn::Expr),
+    Conditional(syn::Expr),
+    List(Vec<ListEntry>),
+    AutoDir,
+}
+
+#[derive(Clone)]
+pub struct ListEntry {
+    pub name: String,
+    pub cond: Option<syn::Expr>,
 }
 
 impl std::fmt::Debug for AttrValue {
@@ -36,6 +46,12 @@ impl std::fmt::Debug for AttrValue {
                 .field(&ident.to_string())
                 .finish(),
             AttrValue::Expr(_) => f.debug_tuple("Expr").field(&"...").finish(),
+            AttrValue::Conditional(_) => f.debug_tuple("Conditional").field(&"...").finish(),
+            AttrValue::AutoDir => f.write_str("AutoDir"),
+            AttrValue::List(entries) => f
+                .debug_list()
+                .entries(entries.iter().map(|entry| &entry.name))
+                .finish(),
         }
     }
 }
@@ -58,6 +74,12 @@ impl std::fmt::Debug for FormatSpec {
 #[derive(Debug, Default)]
 pub struct ElementSpec {
     pub tag: Option<String>,
+    pub tag_field: Option<Ident>,
+    pub xml: bool,
+    pub transparent: bool,
+    pub raw: bool,
+    pub debug_expand: bool,
+    pub test_id: Option<String>,
     pub attrs: Vec<AttrSpec>,
     pub format: Option<FormatSpec>,
     pub map_or: Option<String>,
@@ -66,23 +88,53 @@ pub struct ElementSpec {
 #[derive(Debug, Default)]
 pub struct FieldSpec {
     pub tag: Option<String>,
+    pub xml: bool,
+    pub lazy: bool,
+    pub raw: bool,
+    pub iter: bool,
+    pub enumerate: bool,
+    pub chunk: Option<usize>,
+    pub with: Option<TokenStream>,
+    pub on_err: Option<TokenStream>,
+    pub render_if: Option<TokenStream>,
+    pub if_flag: Option<String>,
+    pub empty: Option<TokenStream>,
     pub should_render: bool,
     pub attrs: Vec<AttrSpec>,
     pub format: Option<FormatSpec>,
     pub map_or: Option<String>,
     pub is_attr: bool,
     pub attr_rename: Option<String>,
+    pub attr_flatten: bool,
+    pub attr_spread: bool,
+    pub attr_explicit: bool,
+    pub attr_skip_if_empty: bool,
 }
 
 impl ElementSpec {
     pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut spec = ElementSpec::default();
+        let mut key = None;
 
         for attr in attrs {
             let path = attr.path();
 
             if path.is_ident("element") {
-                spec.tag = Some(parse_single_string_arg(attr)?);
+                let args = parse_element_args(attr)?;
+                if let Some(empty) = args.empty {
+                    return Err(syn::Error::new_spanned(
+                        empty,
+                        "#[element(empty = ...)] is only supported on collection fields",
+                    ));
+                }
+                spec.tag = args.tag;
+                spec.tag_field = args.tag_field;
+                spec.xml = args.xml;
+                spec.transparent = args.transparent;
+                spec.raw = args.raw;
+                spec.debug_expand = args.debug_expand;
+                spec.test_id = args.test_id;
+                key = args.key;
             } else if path.is_ident("format") {
                 spec.format = Some(parse_format_args(attr)?);
             } else if path.is_ident("map_or") {
@@ -93,7 +145,15 @@ impl ElementSpec {
             }
         }
 
-        if !spec.attrs.is_empty() && spec.tag.is_none() {
+        if let Some(key) = key {
+            spec.attrs.insert(0, key);
+        }
+
+        if !spec.attrs.is_empty()
+            && spec.tag.is_none()
+            && spec.tag_field.is_none()
+            && !spec.transparent
+        {
             return Err(syn::Error::new(
                 Span::call_site(),
                 "#[attr(...)] requires #[element(\"tag\")] to be specified",
@@ -116,8 +176,27 @@ impl FieldSpec {
             let path = attr.path();
 
             if path.is_ident("element") {
+                let args = parse_element_args(attr)?;
+                if let Some(tag_field) = args.tag_field {
+                    return Err(syn::Error::new_spanned(
+                        tag_field,
+                        "#[element(tag = field)] is only supported on structs",
+                    ));
+                }
                 spec.should_render = true;
-                spec.tag = parse_optional_string_arg(attr)?;
+                spec.attrs.splice(0..0, args.key);
+                spec.tag = args.tag;
+                spec.xml = args.xml;
+                spec.lazy = args.lazy;
+                spec.raw = args.raw;
+                spec.iter = args.iter;
+                spec.enumerate = args.enumerate;
+                spec.chunk = args.chunk;
+                spec.with = args.with;
+                spec.on_err = args.on_err;
+                spec.render_if = args.render_if;
+                spec.if_flag = args.if_flag;
+                spec.empty = args.empty;
             } else if path.is_ident("format") {
                 spec.format = Some(parse_format_args(attr)?);
             } else if path.is_ident("map_or") {
@@ -125,9 +204,19 @@ impl FieldSpec {
             } else if path.is_ident("attr") {
                 let parsed = parse_field_attr_attribute(attr, field_name, field_type)?;
                 match parsed {
-                    FieldAttrResult::IsAttr { rename } => {
+                    FieldAttrResult::IsAttr {
+                        rename,
+                        flatten,
+                        spread,
+                        explicit,
+                        skip_if_empty,
+                    } => {
                         spec.is_attr = true;
                         spec.attr_rename = rename;
+                        spec.attr_flatten = flatten;
+                        spec.attr_spread = spread;
+                        spec.attr_explicit = explicit;
+                        spec.attr_skip_if_empty = skip_if_empty;
                     }
                     FieldAttrResult::Attrs(attrs) => {
                         spec.attrs.extend(attrs);
@@ -174,22 +263,145 @@ fn parse_format_args(attr: &Attribute) -> syn::Result<FormatSpec> {
     parser.parse2(tokens)
 }
 
-fn parse_optional_string_arg(attr: &Attribute) -> syn::Result<Option<String>> {
-    match &attr.meta {
-        Meta::Path(_) => Ok(None),
-        Meta::List(list) => {
-            if list.tokens.is_empty() {
-                Ok(None)
-            } else {
-                let lit: syn::LitStr = syn::parse2(list.tokens.clone())?;
-                Ok(Some(lit.value()))
+#[derive(Debug, Default)]
+pub struct ElementArgs {
+    pub tag: Option<String>,
+    pub tag_field: Option<Ident>,
+    pub key: Option<AttrSpec>,
+    pub xml: bool,
+    pub lazy: bool,
+    pub transparent: bool,
+    pub raw: bool,
+    pub iter: bool,
+    pub enumerate: bool,
+    pub chunk: Option<usize>,
+    pub with: Option<TokenStream>,
+    pub on_err: Option<TokenStream>,
+    pub debug_expand: bool,
+    pub test_id: Option<String>,
+    pub render_if: Option<TokenStream>,
+    pub if_flag: Option<String>,
+    pub empty: Option<TokenStream>,
+}
+
+fn parse_element_args(attr: &Attribute) -> syn::Result<ElementArgs> {
+    let tokens = match &attr.meta {
+        Meta::Path(_) => return Ok(ElementArgs::default()),
+        Meta::List(list) => list.tokens.clone(),
+        Meta::NameValue(_) => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "unexpected name=value syntax",
+            ));
+        }
+    };
+
+    let parser = |input: syn::parse::ParseStream| {
+        let mut args = ElementArgs::default();
+
+        if input.peek(syn::LitStr) {
+            let lit: syn::LitStr = input.parse()?;
+            args.tag = Some(lit.value());
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
             }
         }
-        Meta::NameValue(_) => Err(syn::Error::new_spanned(
-            attr,
-            "unexpected name=value syntax",
-        )),
-    }
+
+        while !input.is_empty() {
+            let option: Ident = input.parse()?;
+            match option.to_string().as_str() {
+                "xml" => args.xml = true,
+                "lazy" => args.lazy = true,
+                "transparent" => args.transparent = true,
+                "raw" => args.raw = true,
+                "iter" => args.iter = true,
+                "enumerate" => args.enumerate = true,
+                "chunk" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let size: syn::LitInt = input.parse()?;
+                    let value = size.base10_parse::<usize>()?;
+                    if value == 0 {
+                        return Err(syn::Error::new_spanned(
+                            size,
+                            "chunk size must be at least 1",
+                        ));
+                    }
+                    args.chunk = Some(value);
+                }
+                "debug_expand" => args.debug_expand = true,
+                "render_if" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let condition: syn::Expr = input.parse()?;
+                    args.render_if = Some(quote::quote! { #condition });
+                }
+                "render_if_fn" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let predicate: syn::Path = input.parse()?;
+                    args.render_if = Some(quote::quote! { (#predicate)(self) });
+                }
+                "if_flag" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    args.if_flag = Some(lit.value());
+                }
+                "empty" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let placeholder: syn::Expr = input.parse()?;
+                    args.empty = Some(quote::quote! { #placeholder });
+                }
+                "with" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let path: syn::Path = if input.peek(syn::LitStr) {
+                        input.parse::<syn::LitStr>()?.parse()?
+                    } else {
+                        input.parse()?
+                    };
+                    args.with = Some(quote::quote! { #path });
+                }
+                "on_err" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let fallback: syn::Expr = input.parse()?;
+                    args.on_err = Some(quote::quote! { #fallback });
+                }
+                "tag" => {
+                    input.parse::<syn::Token![=]>()?;
+                    args.tag_field = Some(input.parse()?);
+                }
+                "key" | "data_key" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let value = match input.parse::<syn::Expr>()? {
+                        syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                            syn::parse_quote! { self.#path }
+                        }
+                        expr => expr,
+                    };
+                    let name = if option == "key" { "id" } else { "data-key" };
+                    args.key = Some(AttrSpec {
+                        key: AttrKey::Literal(name.to_string()),
+                        value: AttrValue::Expr(value),
+                    });
+                }
+                "test_id" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    args.test_id = Some(lit.value());
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        option,
+                        "unknown #[element(...)] option",
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    };
+
+    parser.parse2(tokens)
 }
 
 fn parse_attr_attribute(attr: &Attribute) -> syn::Result<Vec<AttrSpec>> {
@@ -234,12 +446,21 @@ fn parse_attr_attribute(attr: &Attribute) -> syn::Result<Vec<AttrSpec>> {
                     value: AttrValue::Expr(expr),
                 });
             }
-            AttrItem::BareKey { key } => {
+            AttrItem::KeyIf { key, cond } => {
                 attrs.push(AttrSpec {
-                    key: AttrKey::Literal(key),
-                    value: AttrValue::Bool(true),
+                    key: classify_key(&key),
+                    value: AttrValue::Conditional(cond),
                 });
             }
+            AttrItem::KeyList { key, list } => {
+                attrs.push(AttrSpec {
+                    key: classify_key(&key),
+                    value: AttrValue::List(list),
+                });
+            }
+            AttrItem::BareKey { key } => {
+                attrs.push(bare_attr(key));
+            }
         }
     }
 
@@ -252,11 +473,25 @@ enum AttrItem {
     KeyPath { key: String, path: syn::Path },
     KeySignalField { key: String, field: syn::Ident },
     KeyExpr { key: String, expr: syn::Expr },
+    KeyIf { key: String, cond: syn::Expr },
+    KeyList { key: String, list: Vec<ListEntry> },
     BareKey { key: String },
 }
 
+pub fn bare_attr(key: String) -> AttrSpec {
+    let value = if key == "auto-dir" {
+        AttrValue::AutoDir
+    } else {
+        AttrValue::Bool(true)
+    };
+    AttrSpec {
+        key: AttrKey::Literal(key),
+        value,
+    }
+}
+
 fn is_signal_field_binding_key(key: &str) -> bool {
-    key == "data-bind" || key == "data_bind"
+    key == "data-bind" || key == "data_bind" || key == "bind"
 }
 
 fn looks_like_field_name(ident: &Ident) -> bool {
@@ -267,57 +502,98 @@ fn looks_like_field_name(ident: &Ident) -> bool {
         .is_some_and(char::is_lowercase)
 }
 
+pub fn parse_attr_key(input: syn::parse::ParseStream) -> syn::Result<String> {
+    if input.peek(syn::LitStr) {
+        let lit: syn::LitStr = input.parse()?;
+        return Ok(lit.value());
+    }
+
+    let mut key = String::new();
+    if input.peek(syn::Token![@]) {
+        input.parse::<syn::Token![@]>()?;
+        key.push('@');
+    }
+    key.push_str(&attr_name_from_ident(&Ident::parse_any(input)?));
+    loop {
+        if input.peek(syn::Token![:]) && !input.peek(syn::Token![::]) {
+            input.parse::<syn::Token![:]>()?;
+            key.push(':');
+            key.push_str(&attr_name_from_ident(&Ident::parse_any(input)?));
+        } else if input.peek(syn::Token![.]) {
+            input.parse::<syn::Token![.]>()?;
+            key.push('.');
+            if input.peek(syn::LitInt) {
+                key.push_str(&input.parse::<syn::LitInt>()?.to_string());
+            } else {
+                key.push_str(&Ident::parse_any(input)?.unraw().to_string());
+            }
+        } else {
+            break;
+        }
+    }
+    Ok(key)
+}
+
 impl syn::parse::Parse for AttrItem {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let key = if input.peek(syn::LitStr) {
-            let lit: syn::LitStr = input.parse()?;
-            lit.value()
-        } else {
-            let ident: Ident = input.parse()?;
-            normalize_attr_key(&ident.to_string())
-        };
+        let key = parse_attr_key(input)?;
+
+        if input.peek(syn::Token![if]) {
+            input.parse::<syn::Token![if]>()?;
+            let cond = syn::Expr::parse_without_eager_brace(input)?;
+            return Ok(AttrItem::KeyIf { key, cond });
+        }
 
         if input.peek(syn::Token![=]) {
             input.parse::<syn::Token![=]>()?;
 
-            if input.peek(syn::LitStr) {
+            if input.peek(syn::token::Bracket) {
+                let content;
+                syn::bracketed!(content in input);
+                let entries =
+                    syn::punctuated::Punctuated::<ListEntry, syn::Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                Ok(AttrItem::KeyList {
+                    key,
+                    list: entries.into_iter().collect(),
+                })
+            } else if input.peek(syn::LitStr) {
                 let lit: syn::LitStr = input.parse()?;
-                return Ok(AttrItem::KeyValue {
+                Ok(AttrItem::KeyValue {
                     key,
                     value: lit.value(),
-                });
+                })
             } else if input.peek(syn::LitBool) {
                 let lit: syn::LitBool = input.parse()?;
-                return Ok(AttrItem::KeyBool {
+                Ok(AttrItem::KeyBool {
                     key,
                     value: lit.value,
-                });
+                })
             } else {
                 let expr: syn::Expr = input.parse()?;
 
-                if is_signal_field_binding_key(&key) {
-                    if let syn::Expr::Path(expr_path) = &expr {
-                        if let Some(ident) = expr_path.path.get_ident() {
-                            if looks_like_field_name(ident) {
-                                return Ok(AttrItem::KeySignalField {
-                                    key,
-                                    field: ident.clone(),
-                                });
-                            }
-                        }
-                    }
+                if is_signal_field_binding_key(&key)
+                    && let syn::Expr::Path(expr_path) = &expr
+                    && let Some(ident) = expr_path.path.get_ident()
+                    && looks_like_field_name(ident)
+                {
+                    return Ok(AttrItem::KeySignalField {
+                        key,
+                        field: ident.clone(),
+                    });
                 }
 
-                if let syn::Expr::Path(expr_path) = &expr {
-                    if expr_path.qself.is_none() {
-                        return Ok(AttrItem::KeyPath {
-                            key,
-                            path: expr_path.path.clone(),
-                        });
-                    }
+                if let syn::Expr::Path(expr_path) = &expr
+                    && expr_path.qself.is_none()
+                {
+                    return Ok(AttrItem::KeyPath {
+                        key,
+                        path: expr_path.path.clone(),
+                    });
                 }
 
-                return Ok(AttrItem::KeyExpr { key, expr });
+                Ok(AttrItem::KeyExpr { key, expr })
             }
         } else {
             Ok(AttrItem::BareKey { key })
@@ -325,8 +601,31 @@ impl syn::parse::Parse for AttrItem {
     }
 }
 
+impl syn::parse::Parse for ListEntry {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(syn::LitStr) {
+            input.parse::<syn::LitStr>()?.value()
+        } else {
+            attr_name_from_ident(&Ident::parse_any(input)?)
+        };
+        let cond = if input.peek(syn::Token![=>]) {
+            input.parse::<syn::Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(ListEntry { name, cond })
+    }
+}
+
 enum FieldAttrResult {
-    IsAttr { rename: Option<String> },
+    IsAttr {
+        rename: Option<String>,
+        flatten: bool,
+        spread: bool,
+        explicit: bool,
+        skip_if_empty: bool,
+    },
     Attrs(Vec<AttrSpec>),
 }
 
@@ -336,13 +635,29 @@ fn parse_field_attr_attribute(
     _field_type: &Type,
 ) -> syn::Result<FieldAttrResult> {
     match &attr.meta {
-        Meta::Path(_) => Ok(FieldAttrResult::IsAttr { rename: None }),
+        Meta::Path(_) => Ok(FieldAttrResult::IsAttr {
+            rename: None,
+            flatten: false,
+            spread: false,
+            explicit: false,
+            skip_if_empty: false,
+        }),
         Meta::List(list) => {
             if list.tokens.is_empty() {
-                return Ok(FieldAttrResult::IsAttr { rename: None });
+                return Ok(FieldAttrResult::IsAttr {
+                    rename: None,
+                    flatten: false,
+                    spread: false,
+                    explicit: false,
+                    skip_if_empty: false,
+                });
             }
 
             let mut rename = None;
+            let mut flatten = false;
+            let mut spread = false;
+            let mut explicit = false;
+            let mut skip_if_empty = false;
             let mut attrs = Vec::new();
 
             let parser =
@@ -354,6 +669,18 @@ fn parse_field_attr_attribute(
                     FieldAttrItem::Rename(name) => {
                         rename = Some(name);
                     }
+                    FieldAttrItem::Flatten => {
+                        flatten = true;
+                    }
+                    FieldAttrItem::Spread => {
+                        spread = true;
+                    }
+                    FieldAttrItem::Explicit => {
+                        explicit = true;
+                    }
+                    FieldAttrItem::SkipIfEmpty => {
+                        skip_if_empty = true;
+                    }
                     FieldAttrItem::Attr(attr_item) => match attr_item {
                         AttrItem::KeyValue { key, value } => {
                             attrs.push(AttrSpec {
@@ -382,10 +709,7 @@ fn parse_field_attr_attribute(
                             });
                         }
                         AttrItem::BareKey { key } => {
-                            attrs.push(AttrSpec {
-                                key: AttrKey::Literal(key),
-                                value: AttrValue::Bool(true),
-                            });
+                            attrs.push(bare_attr(key));
                         }
                         AttrItem::KeyExpr { key, expr } => {
                             attrs.push(AttrSpec {
@@ -393,16 +717,57 @@ fn parse_field_attr_attribute(
                                 value: AttrValue::Expr(expr),
                             });
                         }
+                        AttrItem::KeyIf { key, cond } => {
+                            attrs.push(AttrSpec {
+                                key: classify_key(&key),
+                                value: AttrValue::Conditional(cond),
+                            });
+                        }
+                        AttrItem::KeyList { key, list } => {
+                            attrs.push(AttrSpec {
+                                key: classify_key(&key),
+                                value: AttrValue::List(list),
+                            });
+                        }
                     },
                 }
             }
 
-            if rename.is_some() && attrs.is_empty() {
-                Ok(FieldAttrResult::IsAttr { rename })
+            let combined = rename.is_some()
+                || explicit
+                || skip_if_empty
+                || !attrs.is_empty()
+                || (flatten && spread);
+            if (flatten || spread) && combined {
+                Err(syn::Error::new_spanned(
+                    list,
+                    "#[attr(flatten)] and #[attr(spread)] cannot be combined with other attr options",
+                ))
+            } else if (explicit || skip_if_empty) && !attrs.is_empty() {
+                Err(syn::Error::new_spanned(
+                    list,
+                    "#[attr(explicit)] and #[attr(skip_if_empty)] cannot be combined with attribute values",
+                ))
+            } else if (rename.is_some() || flatten || spread || explicit || skip_if_empty)
+                && attrs.is_empty()
+            {
+                Ok(FieldAttrResult::IsAttr {
+                    rename,
+                    flatten,
+                    spread,
+                    explicit,
+                    skip_if_empty,
+                })
             } else if !attrs.is_empty() {
                 Ok(FieldAttrResult::Attrs(attrs))
             } else {
-                Ok(FieldAttrResult::IsAttr { rename: None })
+                Ok(FieldAttrResult::IsAttr {
+                    rename: None,
+                    flatten: false,
+                    spread: false,
+                    explicit: false,
+                    skip_if_empty: false,
+                })
             }
         }
         Meta::NameValue(_) => Err(syn::Error::new_spanned(
@@ -414,6 +779,10 @@ fn parse_field_attr_attribute(
 
 enum FieldAttrItem {
     Rename(String),
+    Flatten,
+    Spread,
+    Explicit,
+    SkipIfEmpty,
     Attr(AttrItem),
 }
 
@@ -427,6 +796,22 @@ impl syn::parse::Parse for FieldAttrItem {
                 let lit: syn::LitStr = input.parse()?;
                 return Ok(FieldAttrItem::Rename(lit.value()));
             }
+            if ident == "flatten" && !input.peek2(syn::Token![=]) {
+                input.parse::<Ident>()?;
+                return Ok(FieldAttrItem::Flatten);
+            }
+            if ident == "spread" && !input.peek2(syn::Token![=]) {
+                input.parse::<Ident>()?;
+                return Ok(FieldAttrItem::Spread);
+            }
+            if ident == "explicit" && !input.peek2(syn::Token![=]) {
+                input.parse::<Ident>()?;
+                return Ok(FieldAttrItem::Explicit);
+            }
+            if ident == "skip_if_empty" && !input.peek2(syn::Token![=]) {
+                input.parse::<Ident>()?;
+                return Ok(FieldAttrItem::SkipIfEmpty);
+            }
         }
         // A bare string literal (not followed by =) is a rename shorthand:
         // #[attr("data-url")] is equivalent to #[attr(name = "data-url")]
@@ -439,7 +824,19 @@ impl syn::parse::Parse for FieldAttrItem {
 }
 
 fn normalize_attr_key(key: &str) -> String {
-    key.replace('_', "-")
+    key.split("__")
+        .map(|part| part.replace('_', "-"))
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+pub fn attr_name_from_ident(ident: &Ident) -> String {
+    let name = ident.unraw().to_string();
+    let name = match name.strip_suffix('_') {
+        Some(stripped) if !stripped.is_empty() => stripped,
+        _ => &name,
+    };
+    normalize_attr_key(name)
 }
 
 fn has_interpolation(s: &str) -> bool {
@@ -458,7 +855,10 @@ fn has_interpolation(s: &str) -> bool {
                 }
                 ident.push(chars.next().unwrap());
             }
-            if is_valid_identifier(&ident) {
+            let path = ident
+                .split_once(':')
+                .map_or(ident.as_str(), |(path, _)| path);
+            if is_field_path(path) {
                 return true;
             }
         }
@@ -466,6 +866,11 @@ fn has_interpolation(s: &str) -> bool {
     false
 }
 
+fn is_field_path(s: &str) -> bool {
+    s.split('.')
+        .all(|segment| is_valid_identifier(segment) || segment.parse::<usize>().is_ok())
+}
+
 fn is_valid_identifier(s: &str) -> bool {
     let mut chars = s.chars();
     let Some(first) = chars.next() else {
@@ -474,7 +879,7 @@ fn is_valid_identifier(s: &str) -> bool {
     (first.is_alphabetic() || first == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
 }
 
-fn classify_key(key: &str) -> AttrKey {
+pub fn classify_key(key: &str) -> AttrKey {
     if has_interpolation(key) {
         AttrKey::Interpolated(key.to_string())
     } else {
@@ -482,7 +887,7 @@ fn classify_key(key: &str) -> AttrKey {
     }
 }
 
-fn classify_value(val: &str) -> AttrValue {
+pub fn classify_value(val: &str) -> AttrValue {
     if has_interpolation(val) {
         AttrValue::Interpolated(val.to_string())
     } else {
@@ -490,7 +895,7 @@ fn classify_value(val: &str) -> AttrValue {
     }
 }
 
-fn type_name_matches(ty: &Type, name: &str) -> bool {
+pub fn type_name_matches(ty: &Type, name: &str) -> bool {
     let Type::Path(type_path) = ty else {
         return false;
     };
@@ -505,14 +910,83 @@ pub fn is_bool_type(ty: &Type) -> bool {
     type_name_matches(ty, "bool")
 }
 
-pub fn is_vec_type(ty: &Type) -> bool {
-    type_name_matches(ty, "Vec")
+pub fn iter_item_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(reference) => iter_item_type(&reference.elem),
+        Type::Slice(slice) => Some(&slice.elem),
+        Type::Array(array) => Some(&array.elem),
+        Type::Path(_)
+            if type_name_matches(ty, "Vec")
+                || type_name_matches(ty, "VecDeque")
+                || type_name_matches(ty, "Window") =>
+        {
+            inner_type(ty)
+        }
+        _ => None,
+    }
 }
 
 pub fn is_option_type(ty: &Type) -> bool {
     type_name_matches(ty, "Option")
 }
 
+pub fn is_result_type(ty: &Type) -> bool {
+    type_name_matches(ty, "Result")
+}
+
+pub fn is_text_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_text_type(&reference.elem),
+        Type::Path(_) => {
+            type_name_matches(ty, "String")
+                || type_name_matches(ty, "str")
+                || type_name_matches(ty, "Cow")
+        }
+        _ => false,
+    }
+}
+
+pub fn is_raw_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_raw_type(&reference.elem),
+        _ => type_name_matches(ty, "Raw") || type_name_matches(ty, "PreEscaped"),
+    }
+}
+
+pub fn is_data_attrs_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_data_attrs_type(&reference.elem),
+        _ => type_name_matches(ty, "DataAttrs"),
+    }
+}
+
+pub fn is_style_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_style_type(&reference.elem),
+        _ => type_name_matches(ty, "Style"),
+    }
+}
+
+pub fn is_children_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_children_type(&reference.elem),
+        _ => type_name_matches(ty, "Children"),
+    }
+}
+
+pub fn inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
 pub fn is_unit_type(ty: &Type) -> bool {
     matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
 }
@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Fields, Type};
+
+use crate::attributes::{inner_type, is_option_type, type_name_matches};
+
+enum Setter<'a> {
+    Required { default: bool },
+    Optional(&'a Type),
+    Many(&'a Type),
+}
+
+fn parse_builder_default(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut default = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown #[builder(...)] option, expected `default`"))
+            }
+        })?;
+    }
+    Ok(default)
+}
+
+pub fn generate_builder(
+    input: &DeriveInput,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("{}Builder", name);
+    let builder_name = builder.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "ElementBuilder can only be derived for structs with named fields",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "ElementBuilder can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut slots = Vec::new();
+    let mut inits = Vec::new();
+    let mut setters = Vec::new();
+    let mut builds = Vec::new();
+
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let default = parse_builder_default(&field.attrs)?;
+
+        let setter = if is_option_type(ty) {
+            inner_type(ty).map_or(Setter::Required { default }, Setter::Optional)
+        } else if type_name_matches(ty, "Vec") {
+            inner_type(ty).map_or(Setter::Required { default }, Setter::Many)
+        } else {
+            Setter::Required { default }
+        };
+
+        match setter {
+            Setter::Required { default } => {
+                slots.push(quote! { #ident: ::core::option::Option<#ty> });
+                inits.push(quote! { #ident: ::core::option::Option::None });
+                setters.push(quote! {
+                    #vis fn #ident(mut self, value: impl ::core::convert::Into<#ty>) -> Self {
+                        self.#ident = ::core::option::Option::Some(value.into());
+                        self
+                    }
+                });
+                let field_name = ident.unraw().to_string();
+                builds.push(if default {
+                    quote! { #ident: self.#ident.unwrap_or_default() }
+                } else {
+                    quote! {
+                        #ident: self.#ident.ok_or(#bluth_crate::BuilderError {
+                            builder: #builder_name,
+                            field: #field_name,
+                        })?
+                    }
+                });
+            }
+            Setter::Optional(inner) => {
+                slots.push(quote! { #ident: #ty });
+                inits.push(quote! { #ident: ::core::option::Option::None });
+                setters.push(quote! {
+                    #vis fn #ident(mut self, value: impl ::core::convert::Into<#inner>) -> Self {
+                        self.#ident = ::core::option::Option::Some(value.into());
+                        self
+                    }
+                });
+                builds.push(quote! { #ident: self.#ident });
+            }
+            Setter::Many(item) => {
+                let push = format_ident!("push_{}", ident);
+                slots.push(quote! { #ident: #ty });
+                inits.push(quote! { #ident: ::std::vec::Vec::new() });
+                setters.push(quote! {
+                    #vis fn #ident(mut self, values: impl ::core::iter::IntoIterator<Item = #item>) -> Self {
+                        self.#ident.extend(values);
+                        self
+                    }
+
+                    #vis fn #push(mut self, value: impl ::core::convert::Into<#item>) -> Self {
+                        self.#ident.push(value.into());
+                        self
+                    }
+                });
+                builds.push(quote! { #ident: self.#ident });
+            }
+        }
+    }
+
+    Ok(quote! {
+        #[must_use]
+        #vis struct #builder #impl_generics #where_clause {
+            #(#slots,)*
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #vis fn new() -> Self {
+                Self {
+                    #(#inits,)*
+                }
+            }
+
+            #(#setters)*
+
+            #vis fn build(self) -> ::core::result::Result<#name #ty_generics, #bluth_crate::BuilderError> {
+                ::core::result::Result::Ok(#name {
+                    #(#builds,)*
+                })
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #builder #ty_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis fn builder() -> #builder #ty_generics {
+                #builder::new()
+            }
+        }
+    })
+}
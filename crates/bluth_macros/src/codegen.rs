@@ -1,12 +1,14 @@
 use proc_macro2::{TokenStream, TokenTree};
-use quote::quote;
+use quote::{ToTokens, quote};
 use std::collections::{HashMap, HashSet};
 use syn::{DataEnum, DataStruct, Fields, GenericArgument, Ident, PathArguments, Type};
 
 use crate::attributes::{
-    AttrKey, AttrSpec, AttrValue, ElementSpec, FieldSpec, FormatSpec, is_bool_type, is_option_type,
-    is_unit_type, is_vec_type,
+    AttrKey, AttrSpec, AttrValue, ElementSpec, FieldSpec, FormatSpec, attr_name_from_ident,
+    inner_type, is_bool_type, is_children_type, is_data_attrs_type, is_option_type, is_raw_type,
+    is_result_type, is_style_type, is_text_type, is_unit_type, iter_item_type,
 };
+use crate::features::{Feature, enabled};
 
 pub struct SignalFieldInfo {
     pub selector_type: syn::Path,
@@ -44,15 +46,83 @@ fn collect_signal_fields(fields: &Fields) -> HashMap<String, SignalFieldInfo> {
         .collect()
 }
 
+// Expansion needs this before `bluth` exists, so it mirrors
+// `bluth::html::is_void_element`; `tests::basic` keeps the two in step.
 const VOID_ELEMENTS: &[&str] = &[
     "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
     "track", "wbr",
 ];
 
-fn is_void_element(tag: &str) -> bool {
+pub fn is_void_element(tag: &str) -> bool {
     VOID_ELEMENTS.contains(&tag)
 }
 
+pub fn void_close(tag: &str) -> String {
+    if enabled(Feature::Xhtml) {
+        format!("></{}>", tag)
+    } else {
+        "/>".to_string()
+    }
+}
+
+fn xml_element(
+    tag: &str,
+    attr_code: TokenStream,
+    content: &TokenStream,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    if content.is_empty() {
+        return quote! {
+            write!(f, "<{}", #tag)?;
+            #attr_code
+            write!(f, "/>")?;
+        };
+    }
+
+    quote! {
+        write!(f, "<{}", #tag)?;
+        #attr_code
+        let mut __bluth_content = #bluth_crate::render::Buffer::default();
+        {
+            use ::core::fmt::Write as _;
+            let f = &mut __bluth_content;
+            #content
+        }
+        if __bluth_content.is_empty() {
+            write!(f, "/>")?;
+        } else {
+            write!(f, ">{}</{}>", __bluth_content.into_string(), #tag)?;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TextEscape {
+    None,
+    Html,
+    Xml,
+}
+
+impl TextEscape {
+    fn for_type(ty: &syn::Type, xml: bool, raw: bool) -> Self {
+        if raw || !is_text_type(ty) {
+            TextEscape::None
+        } else if xml {
+            TextEscape::Xml
+        } else {
+            TextEscape::Html
+        }
+    }
+}
+
+fn escape_text(value: TokenStream, escape: TextEscape, bluth_crate: &TokenStream) -> TokenStream {
+    match escape {
+        TextEscape::None => value,
+        TextEscape::Html => quote! { #bluth_crate::html::escape_html(#value) },
+        TextEscape::Xml => quote! { #bluth_crate::html::escape_xml(#value) },
+    }
+}
+
 pub fn generate_struct_render(
     data: &DataStruct,
     spec: &ElementSpec,
@@ -60,17 +130,25 @@ pub fn generate_struct_render(
 ) -> syn::Result<TokenStream> {
     let signal_fields = collect_signal_fields(&data.fields);
 
+    if spec.transparent {
+        return generate_transparent_render(data, spec, &signal_fields, bluth_crate);
+    }
+
     let field_renders = match &data.fields {
         Fields::Named(fields) => {
             if let Some(ref format_spec) = spec.format {
                 generate_formatted_struct_render(fields, format_spec)
             } else {
-                generate_named_field_renders(fields, &signal_fields, bluth_crate)?
+                generate_named_field_renders(fields, spec.xml, &signal_fields, bluth_crate)?
             }
         }
         Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
             let field = fields.unnamed.first().unwrap();
-            generate_tuple_struct_render(&field.ty, &spec.map_or)
+            let text_type = inner_type(&field.ty)
+                .filter(|_| is_option_type(&field.ty))
+                .unwrap_or(&field.ty);
+            let escape = TextEscape::for_type(text_type, spec.xml, spec.raw);
+            generate_tuple_struct_render(&field.ty, &spec.map_or, escape, bluth_crate)
         }
         Fields::Unnamed(_) | Fields::Unit => TokenStream::new(),
     };
@@ -86,7 +164,60 @@ pub fn generate_struct_render(
     ))
 }
 
-fn collect_field_attrs(fields: &Fields) -> syn::Result<Vec<(Ident, syn::Type, String)>> {
+fn generate_transparent_render(
+    data: &DataStruct,
+    spec: &ElementSpec,
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let Fields::Unnamed(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "#[element(transparent)] requires a single-field tuple struct",
+        ));
+    };
+    if fields.unnamed.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            fields,
+            "#[element(transparent)] requires a single-field tuple struct",
+        ));
+    }
+
+    if spec.attrs.is_empty() {
+        return Ok(quote! {
+            write!(f, "{}", self.0)?;
+        });
+    }
+
+    let attr_code = emit_attrs(&spec.attrs, true, signal_fields, bluth_crate);
+    Ok(quote! {
+        let mut __bluth_attrs = String::new();
+        {
+            use ::core::fmt::Write as _;
+            let f = &mut __bluth_attrs;
+            #attr_code
+        }
+        let mut __bluth_inner = #bluth_crate::render::Buffer::default();
+        {
+            use ::core::fmt::Write as _;
+            write!(__bluth_inner, "{}", self.0)?;
+        }
+        let __bluth_inner = __bluth_inner.into_string();
+        f.write_str(&#bluth_crate::html::inject_root_attrs(&__bluth_inner, &__bluth_attrs))?;
+    })
+}
+
+struct FieldAttr {
+    field: Ident,
+    ty: syn::Type,
+    name: String,
+    flatten: bool,
+    spread: bool,
+    explicit: bool,
+    skip_if_empty: bool,
+}
+
+fn collect_field_attrs(fields: &Fields) -> syn::Result<Vec<FieldAttr>> {
     let mut result = Vec::new();
 
     if let Fields::Named(named) = fields {
@@ -95,10 +226,18 @@ fn collect_field_attrs(fields: &Fields) -> syn::Result<Vec<(Ident, syn::Type, St
             let field_spec = FieldSpec::from_attrs(&field.attrs, field_name, &field.ty)?;
 
             if field_spec.is_attr {
-                let attr_name = field_spec
+                let name = field_spec
                     .attr_rename
-                    .unwrap_or_else(|| field_name.to_string().replace('_', "-"));
-                result.push((field_name.clone(), field.ty.clone(), attr_name));
+                    .unwrap_or_else(|| attr_name_from_ident(field_name));
+                result.push(FieldAttr {
+                    field: field_name.clone(),
+                    ty: field.ty.clone(),
+                    name,
+                    flatten: field_spec.attr_flatten,
+                    spread: field_spec.attr_spread,
+                    explicit: field_spec.attr_explicit,
+                    skip_if_empty: field_spec.attr_skip_if_empty,
+                });
             }
         }
     }
@@ -146,9 +285,7 @@ fn generate_formatted_struct_render(
 
 fn prefix_self_to_idents(tokens: TokenStream, field_names: &HashSet<String>) -> TokenStream {
     let mut result = Vec::new();
-    let mut iter = tokens.into_iter().peekable();
-
-    while let Some(token) = iter.next() {
+    for token in tokens {
         match token {
             TokenTree::Ident(ref ident) if field_names.contains(&ident.to_string()) => {
                 let self_ident = proc_macro2::Ident::new("self", ident.span());
@@ -175,6 +312,7 @@ fn prefix_self_to_idents(tokens: TokenStream, field_names: &HashSet<String>) ->
 
 fn generate_named_field_renders(
     fields: &syn::FieldsNamed,
+    xml: bool,
     signal_fields: &HashMap<String, SignalFieldInfo>,
     bluth_crate: &TokenStream,
 ) -> syn::Result<TokenStream> {
@@ -193,18 +331,118 @@ fn generate_named_field_renders(
             continue;
         }
 
-        let is_vec = is_vec_type(field_type);
         let is_option = is_option_type(field_type);
+        let option_inner = inner_type(field_type).filter(|_| is_option);
+        let option_items = option_inner.and_then(iter_item_type);
+        let item_type = iter_item_type(field_type).or(option_items);
+        let is_iter = iter_item_type(field_type).is_some() || field_spec.iter;
+        let item_inner = item_type
+            .filter(|ty| is_option_type(ty))
+            .and_then(inner_type);
+        let result_ok = inner_type(field_type).filter(|_| is_result_type(field_type));
         let is_unit = is_unit_type(field_type);
+        let xml = xml || field_spec.xml;
+        let text_type = item_inner
+            .or(item_type)
+            .or(option_inner)
+            .or(result_ok)
+            .unwrap_or(field_type);
+        let escape = if field_spec.with.is_some() {
+            TextEscape::for_type(&syn::parse_quote!(String), xml, field_spec.raw)
+        } else {
+            TextEscape::for_type(text_type, xml, field_spec.raw)
+        };
+        let with = field_spec.with.as_ref();
+        if let Some(with) = with
+            && (field_spec.lazy || field_spec.format.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                with,
+                "#[element(with = ...)] cannot be combined with lazy or #[format]",
+            ));
+        }
 
         let content = if is_unit {
             quote! {}
-        } else if is_vec {
+        } else if field_spec.lazy {
+            quote! {
+                write!(f, "{}", (self.#field_name)())?;
+            }
+        } else if is_iter {
+            let items = match field_type {
+                syn::Type::Reference(_) => quote! { self.#field_name },
+                _ => quote! { &self.#field_name },
+            };
+            render_items(
+                items,
+                item_inner.is_some(),
+                escape,
+                with,
+                field_spec.empty.as_ref(),
+                bluth_crate,
+            )
+        } else if option_items.is_some() {
+            let render = render_items(
+                quote! { items.iter() },
+                item_inner.is_some(),
+                escape,
+                with,
+                field_spec.empty.as_ref(),
+                bluth_crate,
+            );
+            match field_spec.map_or {
+                Some(ref default_val) => quote! {
+                    match &self.#field_name {
+                        Some(items) => { #render }
+                        None => write!(f, "{}", #default_val)?,
+                    }
+                },
+                None => {
+                    let otherwise = field_spec.empty.as_ref().map(|empty| {
+                        quote! { else { #bluth_crate::Render::render(&(#empty), f)?; } }
+                    });
+                    quote! {
+                        if let Some(items) = &self.#field_name {
+                            #render
+                        } #otherwise
+                    }
+                }
+            }
+        } else if let Some(empty) = &field_spec.empty {
+            return Err(syn::Error::new_spanned(
+                empty,
+                "#[element(empty = ...)] requires a collection field",
+            ));
+        } else if result_ok.is_some() {
+            let v = escape_text(
+                display_with(quote! { v }, with, bluth_crate),
+                escape,
+                bluth_crate,
+            );
+            let otherwise = match (&field_spec.on_err, &field_spec.map_or) {
+                (Some(on_err), _) => quote! { #bluth_crate::Render::render(&(#on_err), f)?; },
+                (None, Some(default_val)) => quote! { write!(f, "{}", #default_val)?; },
+                (None, None) => {
+                    let error_escape =
+                        TextEscape::for_type(&syn::parse_quote!(String), xml, field_spec.raw);
+                    let error = escape_text(quote! { error }, error_escape, bluth_crate);
+                    quote! { write!(f, "{}", #error)?; }
+                }
+            };
             quote! {
-                for item in &self.#field_name {
-                    write!(f, "{}", item)?;
+                match &self.#field_name {
+                    Ok(v) => write!(f, "{}", #v)?,
+                    Err(error) => {
+                        let _ = &error;
+                        #otherwise
+                    }
                 }
             }
+        } else if let Some(on_err) = &field_spec.on_err {
+            return Err(syn::Error::new_spanned(
+                on_err,
+                "#[element(on_err = ...)] requires a Result field",
+            ));
         } else if is_option {
             if let Some(ref default_val) = field_spec.map_or {
                 if let Some(ref format_spec) = field_spec.format {
@@ -225,17 +463,27 @@ fn generate_named_field_renders(
                         }
                     }
                 } else {
+                    let v = escape_text(
+                        display_with(quote! { v }, with, bluth_crate),
+                        escape,
+                        bluth_crate,
+                    );
                     quote! {
                         match &self.#field_name {
-                            Some(v) => write!(f, "{}", v)?,
+                            Some(v) => write!(f, "{}", #v)?,
                             None => write!(f, "{}", #default_val)?,
                         }
                     }
                 }
             } else {
+                let v = escape_text(
+                    display_with(quote! { v }, with, bluth_crate),
+                    escape,
+                    bluth_crate,
+                );
                 quote! {
                     if let Some(ref v) = self.#field_name {
-                        write!(f, "{}", v)?;
+                        write!(f, "{}", #v)?;
                     }
                 }
             }
@@ -251,33 +499,56 @@ fn generate_named_field_renders(
                 }
             }
         } else {
+            let value = escape_text(
+                display_with(quote! { &self.#field_name }, with, bluth_crate),
+                escape,
+                bluth_crate,
+            );
             quote! {
-                write!(f, "{}", self.#field_name)?;
+                write!(f, "{}", #value)?;
             }
         };
 
-        let render = if let Some(ref tag) = field_spec.tag {
-            let is_void = is_void_element(tag);
-            let attr_code = emit_attrs(&field_spec.attrs, true, signal_fields, bluth_crate);
-
-            if is_void {
-                quote! {
-                    write!(f, "<{}", #tag)?;
-                    #attr_code
-                    write!(f, "/>")?;
-                }
-            } else {
-                quote! {
-                    write!(f, "<{}", #tag)?;
-                    #attr_code
-                    write!(f, ">")?;
-                    #content
-                    write!(f, "</{}>", #tag)?;
-                }
+        let render = if field_spec.enumerate || field_spec.chunk.is_some() {
+            if !is_iter {
+                let option = if field_spec.enumerate {
+                    "#[element(enumerate)]"
+                } else {
+                    "#[element(chunk = ...)]"
+                };
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    format!("{} requires a collection field", option),
+                ));
             }
+            generate_enumerated_items(
+                field_name,
+                field_type,
+                &field_spec,
+                escape,
+                xml,
+                signal_fields,
+                bluth_crate,
+            )?
+        } else if let Some(ref tag) = field_spec.tag {
+            let attr_code = emit_attrs(&field_spec.attrs, true, signal_fields, bluth_crate);
+            wrap_field_tag(tag, xml, attr_code, content, bluth_crate)
         } else {
             content
         };
+        let render = match field_spec.tag {
+            Some(ref tag) => guard_script(tag, render, bluth_crate),
+            None => render,
+        };
+
+        let render = match render_condition(&field_spec, bluth_crate) {
+            Some(condition) => quote! {
+                if #condition {
+                    #render
+                }
+            },
+            None => render,
+        };
 
         if is_unit {
             renders.push(quote! { let _ = &self.#field_name; });
@@ -289,28 +560,264 @@ fn generate_named_field_renders(
     Ok(quote! { #(#renders)* })
 }
 
+fn generate_enumerated_items(
+    field_name: &Ident,
+    field_type: &Type,
+    field_spec: &FieldSpec,
+    escape: TextEscape,
+    xml: bool,
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let items = match field_type {
+        syn::Type::Reference(_) => quote! { self.#field_name },
+        _ => quote! { &self.#field_name },
+    };
+    let item = match &field_spec.format {
+        Some(FormatSpec {
+            format_string,
+            args: Some(args),
+        }) => quote! { write!(f, #format_string, #args)?; },
+        Some(FormatSpec { format_string, .. }) if has_positional_arg(format_string) => {
+            quote! { write!(f, #format_string, item)?; }
+        }
+        Some(FormatSpec { format_string, .. }) => quote! { write!(f, #format_string)?; },
+        None => {
+            let item = escape_text(
+                display_with(quote! { item }, field_spec.with.as_ref(), bluth_crate),
+                escape,
+                bluth_crate,
+            );
+            quote! { write!(f, "{}", #item)?; }
+        }
+    };
+    let wrap = |content: TokenStream| match &field_spec.tag {
+        Some(tag) => {
+            let attr_code = emit_item_attrs(
+                &field_spec.attrs,
+                ENUMERATE_LOCALS,
+                signal_fields,
+                bluth_crate,
+            );
+            wrap_field_tag(tag, xml, attr_code, content, bluth_crate)
+        }
+        None => content,
+    };
+    let (track_empty, empty) = match &field_spec.empty {
+        Some(empty) => (
+            Some(quote! { is_empty = false; }),
+            Some(quote! {
+                if is_empty {
+                    #bluth_crate::Render::render(&(#empty), f)?;
+                }
+            }),
+        ),
+        None => (None, None),
+    };
+    let init_empty = track_empty
+        .as_ref()
+        .map(|_| quote! { let mut is_empty = true; });
+    let markers = quote! {
+        let index_parity = if index % 2 == 0 { "even" } else { "odd" };
+        let is_first = index == 0;
+        let is_last = items.peek().is_none();
+        let is_only = is_first && is_last;
+    };
+
+    let Some(size) = field_spec.chunk else {
+        let item = wrap(item);
+        return Ok(quote! {
+            #init_empty
+            let mut items = ::core::iter::IntoIterator::into_iter(#items).enumerate().peekable();
+            while let Some((index, item)) = items.next() {
+                #track_empty
+                #markers
+                let _ = (&index, &index_parity, &is_first, &is_last, &is_only, &item);
+                #item
+            }
+            #empty
+        });
+    };
+
+    if field_spec.enumerate || field_spec.tag.is_none() {
+        return Err(syn::Error::new_spanned(
+            field_name,
+            "#[element(chunk = ...)] requires a row tag and cannot be combined with enumerate",
+        ));
+    }
+    let row = wrap(quote! {
+        for item in row {
+            #item
+        }
+    });
+    Ok(quote! {
+        #init_empty
+        let mut items = ::core::iter::IntoIterator::into_iter(#items).peekable();
+        let mut index = 0usize;
+        while items.peek().is_some() {
+            let row: ::std::vec::Vec<_> = items.by_ref().take(#size).collect();
+            #track_empty
+            #markers
+            let _ = (&index_parity, &is_first, &is_last, &is_only);
+            #row
+            index += 1;
+        }
+        #empty
+    })
+}
+
+const ENUMERATE_LOCALS: &[&str] = &[
+    "index",
+    "index_parity",
+    "is_first",
+    "is_last",
+    "is_only",
+    "item",
+];
+
+fn has_positional_arg(format_string: &str) -> bool {
+    let mut chars = format_string.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '{' {
+            if chars.peek() == Some(&'{') {
+                chars.next();
+                continue;
+            }
+            if matches!(chars.peek(), Some('}') | Some(':')) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn wrap_field_tag(
+    tag: &str,
+    xml: bool,
+    attr_code: TokenStream,
+    content: TokenStream,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let (attr_code, end_hook) = element_hooks(quote! { #tag }, attr_code, bluth_crate);
+    if xml {
+        let element = xml_element(tag, attr_code, &content, bluth_crate);
+        quote! {
+            #element
+            #end_hook
+        }
+    } else if is_void_element(tag) {
+        let close = void_close(tag);
+        quote! {
+            write!(f, "<{}", #tag)?;
+            #attr_code
+            write!(f, #close)?;
+            #end_hook
+        }
+    } else {
+        quote! {
+            write!(f, "<{}", #tag)?;
+            #attr_code
+            write!(f, ">")?;
+            #content
+            write!(f, "</{}>", #tag)?;
+            #end_hook
+        }
+    }
+}
+
+fn display_with(
+    value: TokenStream,
+    with: Option<&TokenStream>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    match with {
+        Some(with) => quote! { #bluth_crate::render::DisplayWith(#value, #with) },
+        None => value,
+    }
+}
+
+fn render_items(
+    items: TokenStream,
+    skip_none: bool,
+    escape: TextEscape,
+    with: Option<&TokenStream>,
+    empty: Option<&TokenStream>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let item = escape_text(
+        display_with(quote! { item }, with, bluth_crate),
+        escape,
+        bluth_crate,
+    );
+    let write = if skip_none {
+        quote! {
+            if let Some(item) = item {
+                write!(f, "{}", #item)?;
+            }
+        }
+    } else {
+        quote! { write!(f, "{}", #item)?; }
+    };
+
+    match empty {
+        Some(empty) => quote! {
+            let mut is_empty = true;
+            for item in #items {
+                is_empty = false;
+                #write
+            }
+            if is_empty {
+                #bluth_crate::Render::render(&(#empty), f)?;
+            }
+        },
+        None => quote! {
+            for item in #items {
+                #write
+            }
+        },
+    }
+}
+
+fn render_condition(spec: &FieldSpec, bluth_crate: &TokenStream) -> Option<TokenStream> {
+    let flag = spec
+        .if_flag
+        .as_ref()
+        .map(|flag| quote! { #bluth_crate::flags::is_enabled(#flag) });
+
+    match (flag, &spec.render_if) {
+        (Some(flag), Some(condition)) => Some(quote! { #flag && (#condition) }),
+        (Some(flag), None) => Some(flag),
+        (None, Some(condition)) => Some(condition.clone()),
+        (None, None) => None,
+    }
+}
+
 fn generate_tuple_struct_render(
     field_type: &syn::Type,
     map_or_value: &Option<String>,
+    escape: TextEscape,
+    bluth_crate: &TokenStream,
 ) -> TokenStream {
     if is_option_type(field_type) {
+        let v = escape_text(quote! { v }, escape, bluth_crate);
         if let Some(default_value) = map_or_value {
             quote! {
                 match &self.0 {
-                    Some(v) => write!(f, "{}", v)?,
+                    Some(v) => write!(f, "{}", #v)?,
                     None => write!(f, "{}", #default_value)?,
                 }
             }
         } else {
             quote! {
                 if let Some(ref v) = self.0 {
-                    write!(f, "{}", v)?;
+                    write!(f, "{}", #v)?;
                 }
             }
         }
     } else {
+        let value = escape_text(quote! { &self.0 }, escape, bluth_crate);
         quote! {
-            write!(f, "{}", self.0)?;
+            write!(f, "{}", #value)?;
         }
     }
 }
@@ -319,17 +826,30 @@ pub fn generate_enum_render(
     name: &Ident,
     data: &DataEnum,
     spec: &ElementSpec,
-    _bluth_crate: &TokenStream,
+    bluth_crate: &TokenStream,
 ) -> syn::Result<TokenStream> {
-    let enum_tag = spec
-        .tag
-        .as_ref()
-        .ok_or_else(|| syn::Error::new_spanned(name, "Enum requires #[element(\"tag\")]"))?;
+    if let Some(ref tag_field) = spec.tag_field {
+        return Err(syn::Error::new_spanned(
+            tag_field,
+            "#[element(tag = field)] is only supported on structs",
+        ));
+    }
+
+    let enum_tag = match &spec.tag {
+        Some(tag) => Some(tag.as_str()),
+        None if spec.transparent => None,
+        None => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Enum requires #[element(\"tag\")] or #[element(transparent)]",
+            ));
+        }
+    };
 
     let variant_matches: Vec<_> = data
         .variants
         .iter()
-        .map(|variant| generate_variant_match(name, variant, enum_tag))
+        .map(|variant| generate_variant_match(name, variant, enum_tag, spec.xml, bluth_crate))
         .collect::<syn::Result<_>>()?;
 
     Ok(quote! {
@@ -342,38 +862,222 @@ pub fn generate_enum_render(
 fn generate_variant_match(
     enum_name: &Ident,
     variant: &syn::Variant,
-    enum_tag: &str,
+    enum_tag: Option<&str>,
+    xml: bool,
+    bluth_crate: &TokenStream,
 ) -> syn::Result<TokenStream> {
     let variant_name = &variant.ident;
     let variant_spec = FieldSpec::from_attrs(&variant.attrs, variant_name, &syn::parse_quote!(()))?;
+    let condition = render_condition(&variant_spec, bluth_crate);
+    if enum_tag.is_none()
+        && variant_spec.tag.is_none()
+        && !variant_spec.attrs.is_empty()
+        && !matches!(variant.fields, Fields::Named(_))
+    {
+        return Err(syn::Error::new_spanned(
+            variant_name,
+            "attributes on a variant of a transparent enum require #[element(\"tag\")]",
+        ));
+    }
 
-    match &variant.fields {
+    let arm = match &variant.fields {
+        Fields::Named(fields) => generate_named_variant(
+            enum_name,
+            variant_name,
+            fields,
+            variant_spec,
+            enum_tag,
+            xml,
+            bluth_crate,
+        ),
         Fields::Unnamed(fields) if !fields.unnamed.is_empty() => Ok(generate_tuple_variant(
             enum_name,
             variant_name,
-            variant_spec.tag.as_deref(),
+            &variant_spec,
+            enum_tag,
+            &fields
+                .unnamed
+                .iter()
+                .map(|field| TextEscape::for_type(&field.ty, xml, variant_spec.raw))
+                .collect::<Vec<_>>(),
+            xml,
+            bluth_crate,
+        )),
+        Fields::Unit => Ok(generate_unit_variant(
+            enum_name,
+            variant_name,
+            &variant_spec,
             enum_tag,
-            fields.unnamed.len(),
-            variant_spec.format.as_ref(),
+            xml,
+            bluth_crate,
         )),
-        Fields::Unit => Ok(generate_unit_variant(enum_name, variant_name, enum_tag)),
-        _ => Err(syn::Error::new_spanned(
+        Fields::Unnamed(_) => Err(syn::Error::new_spanned(
             variant,
-            "Only unit variants and tuple variants are supported",
+            "Empty tuple variants are not supported",
         )),
+    }?;
+
+    let Some(condition) = condition else {
+        return Ok(arm);
+    };
+    let mut arm: syn::Arm = syn::parse2(arm)?;
+    let body = &arm.body;
+    arm.body = syn::parse_quote! {
+        if #condition {
+            #body
+        }
+    };
+    Ok(quote! { #arm })
+}
+
+fn generate_named_variant(
+    enum_name: &Ident,
+    variant_name: &Ident,
+    fields: &syn::FieldsNamed,
+    variant_spec: FieldSpec,
+    enum_tag: Option<&str>,
+    xml: bool,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let variant_fields = Fields::Named(fields.clone());
+    let signal_fields = collect_signal_fields(&variant_fields);
+    let field_attrs = collect_field_attrs(&variant_fields)?;
+    if enum_tag.is_none()
+        && variant_spec.tag.is_none()
+        && (!field_attrs.is_empty() || !variant_spec.attrs.is_empty())
+    {
+        return Err(syn::Error::new_spanned(
+            variant_name,
+            "attributes on a variant of a transparent enum require #[element(\"tag\")]",
+        ));
+    }
+    let content = generate_named_field_renders(fields, xml, &signal_fields, bluth_crate)?;
+
+    let render = match variant_spec.tag {
+        Some(tag) => {
+            let variant_element = ElementSpec {
+                tag: Some(tag),
+                xml,
+                attrs: variant_spec.attrs,
+                ..Default::default()
+            };
+            let inner = wrap_with_tag(
+                &content,
+                &variant_element,
+                &field_attrs,
+                &signal_fields,
+                bluth_crate,
+            );
+            let enum_element = ElementSpec {
+                tag: enum_tag.map(str::to_string),
+                xml,
+                ..Default::default()
+            };
+            wrap_with_tag(&inner, &enum_element, &[], &signal_fields, bluth_crate)
+        }
+        None => {
+            let enum_element = ElementSpec {
+                tag: enum_tag.map(str::to_string),
+                xml,
+                attrs: variant_spec.attrs,
+                ..Default::default()
+            };
+            wrap_with_tag(
+                &content,
+                &enum_element,
+                &field_attrs,
+                &signal_fields,
+                bluth_crate,
+            )
+        }
+    };
+
+    let bindings: HashMap<String, Ident> = fields
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .map(|ident| {
+            let binding = syn::Ident::new(&format!("__bluth_{}", ident), ident.span());
+            (ident.to_string(), binding)
+        })
+        .collect();
+    let pattern_fields = fields.named.iter().filter_map(|field| {
+        let ident = field.ident.as_ref()?;
+        let binding = &bindings[&ident.to_string()];
+        Some(quote! { #ident: #binding })
+    });
+    let render = replace_self_fields(render, &bindings);
+
+    Ok(quote! {
+        #enum_name::#variant_name { #(#pattern_fields),* } => {
+            #render
+        }
+    })
+}
+
+fn replace_self_fields(tokens: TokenStream, bindings: &HashMap<String, Ident>) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let field = match tokens.get(i + 2) {
+            Some(TokenTree::Ident(field)) => Some(field.to_string()),
+            Some(TokenTree::Literal(index)) => Some(index.to_string()),
+            _ => None,
+        };
+        if let (TokenTree::Ident(receiver), Some(TokenTree::Punct(dot)), Some(field)) =
+            (&tokens[i], tokens.get(i + 1), field)
+            && receiver == "self"
+            && dot.as_char() == '.'
+            && let Some(binding) = bindings.get(&field)
+        {
+            result.push(TokenTree::Group(proc_macro2::Group::new(
+                proc_macro2::Delimiter::Parenthesis,
+                quote! { *#binding },
+            )));
+            i += 3;
+            continue;
+        }
+
+        match &tokens[i] {
+            TokenTree::Group(group) => {
+                let inner = replace_self_fields(group.stream(), bindings);
+                let mut new_group = proc_macro2::Group::new(group.delimiter(), inner);
+                new_group.set_span(group.span());
+                result.push(TokenTree::Group(new_group));
+            }
+            token => result.push(token.clone()),
+        }
+        i += 1;
     }
+
+    result.into_iter().collect()
 }
 
 fn generate_tuple_variant(
     enum_name: &Ident,
     variant_name: &Ident,
-    variant_tag: Option<&str>,
-    enum_tag: &str,
-    field_count: usize,
-    format_spec: Option<&FormatSpec>,
+    variant_spec: &FieldSpec,
+    enum_tag: Option<&str>,
+    escapes: &[TextEscape],
+    xml: bool,
+    bluth_crate: &TokenStream,
 ) -> TokenStream {
-    let open_enum = format!("<{}>", enum_tag);
-    let close_enum = format!("</{}>", enum_tag);
+    let variant_tag = variant_spec.tag.as_deref();
+    let format_spec = variant_spec.format.as_ref();
+    let field_count = escapes.len();
+    let (open_enum, close_enum) = match enum_tag {
+        Some(tag) => {
+            let open = format!("<{}>", tag);
+            let close = format!("</{}>", tag);
+            (
+                quote! { write!(f, "{}", #open)?; },
+                quote! { write!(f, "{}", #close)?; },
+            )
+        }
+        None => (quote! {}, quote! {}),
+    };
 
     let field_bindings: Vec<_> = (0..field_count)
         .map(|i| syn::Ident::new(&format!("field{}", i), proc_macro2::Span::call_site()))
@@ -397,94 +1101,205 @@ fn generate_tuple_variant(
                 write!(f, #fmt_str, #(#field_bindings),*)?;
             }
         }
-    } else if field_count == 1 {
-        let field = &field_bindings[0];
-        quote! {
-            write!(f, "{}", #field)?;
-        }
     } else {
+        let values = field_bindings
+            .iter()
+            .zip(escapes)
+            .map(|(field, escape)| escape_text(quote! { #field }, *escape, bluth_crate));
         quote! {
-            #(write!(f, "{}", #field_bindings)?;)*
+            #(write!(f, "{}", #values)?;)*
         }
     };
 
+    if !variant_spec.attrs.is_empty() {
+        let bindings: HashMap<String, Ident> = field_bindings
+            .iter()
+            .enumerate()
+            .map(|(i, binding)| (i.to_string(), binding.clone()))
+            .collect();
+        let render = wrap_variant(&content, variant_spec, enum_tag, xml, bluth_crate);
+        let render = replace_self_fields(render, &bindings);
+        return quote! {
+            #pattern => {
+                #render
+            }
+        };
+    }
+
     match variant_tag {
         Some(tag) => {
             let open_variant = format!("<{}>", tag);
             let close_variant = format!("</{}>", tag);
             quote! {
                 #pattern => {
-                    write!(f, "{}", #open_enum)?;
+                    #open_enum
                     write!(f, "{}", #open_variant)?;
                     #content
                     write!(f, "{}", #close_variant)?;
-                    write!(f, "{}", #close_enum)?;
+                    #close_enum
                 }
             }
         }
         None => {
             quote! {
                 #pattern => {
-                    write!(f, "{}", #open_enum)?;
+                    #open_enum
                     #content
-                    write!(f, "{}", #close_enum)?;
+                    #close_enum
                 }
             }
         }
     }
 }
 
-fn generate_unit_variant(enum_name: &Ident, variant_name: &Ident, enum_tag: &str) -> TokenStream {
-    let open_tag = format!("<{}>", enum_tag);
-    let close_tag = format!("</{}>", enum_tag);
-    quote! {
-        #enum_name::#variant_name => {
-            write!(f, "{}", #open_tag)?;
-            write!(f, "{}", #close_tag)?;
+fn wrap_variant(
+    content: &TokenStream,
+    variant_spec: &FieldSpec,
+    enum_tag: Option<&str>,
+    xml: bool,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let signal_fields = HashMap::new();
+    let enum_element = |attrs| ElementSpec {
+        tag: enum_tag.map(str::to_string),
+        xml,
+        attrs,
+        ..Default::default()
+    };
+    match &variant_spec.tag {
+        Some(tag) => {
+            let variant_element = ElementSpec {
+                tag: Some(tag.clone()),
+                xml,
+                attrs: variant_spec.attrs.clone(),
+                ..Default::default()
+            };
+            let inner = wrap_with_tag(content, &variant_element, &[], &signal_fields, bluth_crate);
+            wrap_with_tag(
+                &inner,
+                &enum_element(Vec::new()),
+                &[],
+                &signal_fields,
+                bluth_crate,
+            )
         }
+        None => wrap_with_tag(
+            content,
+            &enum_element(variant_spec.attrs.clone()),
+            &[],
+            &signal_fields,
+            bluth_crate,
+        ),
     }
 }
 
-fn wrap_with_tag(
+fn generate_unit_variant(
+    enum_name: &Ident,
+    variant_name: &Ident,
+    variant_spec: &FieldSpec,
+    enum_tag: Option<&str>,
+    xml: bool,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    if variant_spec.tag.is_some() || !variant_spec.attrs.is_empty() {
+        let render = wrap_variant(&quote! {}, variant_spec, enum_tag, xml, bluth_crate);
+        return quote! {
+            #enum_name::#variant_name => {
+                #render
+            }
+        };
+    }
+    let Some(enum_tag) = enum_tag else {
+        return quote! {
+            #enum_name::#variant_name => {}
+        };
+    };
+    if xml {
+        let empty_tag = format!("<{}/>", enum_tag);
+        return quote! {
+            #enum_name::#variant_name => {
+                write!(f, "{}", #empty_tag)?;
+            }
+        };
+    }
+
+    let open_tag = format!("<{}>", enum_tag);
+    let close_tag = format!("</{}>", enum_tag);
+    quote! {
+        #enum_name::#variant_name => {
+            write!(f, "{}", #open_tag)?;
+            write!(f, "{}", #close_tag)?;
+        }
+    }
+}
+
+fn wrap_with_tag(
     content: &TokenStream,
     spec: &ElementSpec,
-    field_attrs: &[(Ident, syn::Type, String)],
+    field_attrs: &[FieldAttr],
     signal_fields: &HashMap<String, SignalFieldInfo>,
     bluth_crate: &TokenStream,
 ) -> TokenStream {
+    if let Some(ref tag_field) = spec.tag_field {
+        return wrap_with_dynamic_tag(
+            content,
+            tag_field,
+            spec,
+            field_attrs,
+            signal_fields,
+            bluth_crate,
+        );
+    }
     let Some(ref tag_name) = spec.tag else {
         return content.clone();
     };
+    let code = wrap_with_open_tag(
+        content,
+        tag_name,
+        spec,
+        field_attrs,
+        signal_fields,
+        bluth_crate,
+    );
+    guard_script(tag_name, code, bluth_crate)
+}
 
-    let is_void = is_void_element(tag_name);
-    let attr_code = emit_attrs(&spec.attrs, true, signal_fields, bluth_crate);
+fn wrap_with_open_tag(
+    content: &TokenStream,
+    tag_name: &str,
+    spec: &ElementSpec,
+    field_attrs: &[FieldAttr],
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let is_void = !spec.xml && is_void_element(tag_name);
+    let attr_code = emit_spec_attrs(spec, signal_fields, bluth_crate);
 
-    let field_attr_code: Vec<_> = field_attrs
-        .iter()
-        .map(|(field_name, field_type, attr_name)| {
-            if is_bool_type(field_type) {
-                quote! {
-                    if self.#field_name {
-                        write!(f, " {}", #attr_name)?;
-                    }
-                }
-            } else if is_option_type(field_type) {
-                quote! {
-                    if let Some(ref v) = self.#field_name {
-                        write!(f, " {}=\"{}\"", #attr_name, #bluth_crate::html::escape_attr(v))?;
-                    }
-                }
-            } else {
-                quote! {
-                    write!(f, " {}=\"{}\"", #attr_name, #bluth_crate::html::escape_attr(&self.#field_name))?;
-                }
-            }
-        })
-        .collect();
+    let field_attr_code = emit_field_attrs(field_attrs, bluth_crate);
+    let (attrs, end_hook) = element_hooks(
+        quote! { #tag_name },
+        quote! {
+            #attr_code
+            #(#field_attr_code)*
+        },
+        bluth_crate,
+    );
+
+    if spec.xml {
+        let element = xml_element(tag_name, attrs, content, bluth_crate);
+        return quote! {
+            #element
+            #end_hook
+        };
+    }
 
-    if spec.attrs.is_empty() && field_attrs.is_empty() {
+    if spec.attrs.is_empty()
+        && spec.test_id.is_none()
+        && field_attrs.is_empty()
+        && !enabled(Feature::RenderHooks)
+    {
         if is_void {
-            let full_tag = format!("<{}/>", tag_name);
+            let full_tag = format!("<{}{}", tag_name, void_close(tag_name));
             return quote! {
                 write!(f, "{}", #full_tag)?;
             };
@@ -502,33 +1317,321 @@ fn wrap_with_tag(
     let close_tag = format!("</{}>", tag_name);
 
     if is_void {
+        let close = void_close(tag_name);
         quote! {
             write!(f, "<{}", #tag_name)?;
-            #attr_code
-            #(#field_attr_code)*
-            write!(f, "/>")?;
+            #attrs
+            write!(f, #close)?;
+            #end_hook
         }
     } else {
         quote! {
             write!(f, "<{}", #tag_name)?;
-            #attr_code
-            #(#field_attr_code)*
+            #attrs
             write!(f, ">")?;
             #content
             write!(f, "{}", #close_tag)?;
+            #end_hook
         }
     }
 }
 
-fn emit_attrs(
+fn element_hooks(
+    tag: TokenStream,
+    attrs: TokenStream,
+    bluth_crate: &TokenStream,
+) -> (TokenStream, TokenStream) {
+    if !enabled(Feature::RenderHooks) {
+        return (attrs, TokenStream::new());
+    }
+    // The attributes are buffered so hooks can see what the element already
+    // carries before adding their own.
+    (
+        quote! {
+            let mut __bluth_attrs = #bluth_crate::render::Buffer::default();
+            {
+                let f: &mut dyn ::core::fmt::Write = &mut __bluth_attrs;
+                #attrs
+            }
+            let __bluth_attrs = __bluth_attrs.into_string();
+            f.write_str(&__bluth_attrs)?;
+            #bluth_crate::hooks::element_start(#tag, &__bluth_attrs, f)?;
+        },
+        quote! { #bluth_crate::hooks::element_end(#tag)?; },
+    )
+}
+
+fn emit_field_attrs(field_attrs: &[FieldAttr], bluth_crate: &TokenStream) -> Vec<TokenStream> {
+    field_attrs
+        .iter()
+        .map(|field_attr| {
+            let field_name = &field_attr.field;
+            let field_type = &field_attr.ty;
+            let attr_name = &field_attr.name;
+            if field_attr.spread {
+                let write = guard_attr(
+                    quote! { attr.name() },
+                    quote! {
+                        write!(f, "{}", attr)?;
+                    },
+                    bluth_crate,
+                );
+                return quote! {
+                    for attr in &self.#field_name {
+                        #write
+                    }
+                };
+            }
+            if field_attr.flatten {
+                let write = guard_attr(
+                    quote! { key },
+                    quote! {
+                        write!(f, " {}=\"{}\"", key, #bluth_crate::html::escape_attr(value))?;
+                    },
+                    bluth_crate,
+                );
+                return quote! {
+                    for (key, value) in &self.#field_name {
+                        if #bluth_crate::html::is_valid_attr_name(::core::convert::AsRef::<str>::as_ref(key)) {
+                            #write
+                        }
+                    }
+                };
+            }
+            if is_data_attrs_type(field_type) {
+                return quote! {
+                    write!(f, "{}", &self.#field_name)?;
+                };
+            }
+            let write = if is_bool_type(field_type) && field_attr.explicit {
+                quote! {
+                    write!(f, " {}=\"{}\"", #attr_name, self.#field_name)?;
+                }
+            } else if is_bool_type(field_type) {
+                quote! {
+                    if self.#field_name {
+                        write!(f, " {}", #attr_name)?;
+                    }
+                }
+            } else if is_option_type(field_type) && inner_type(field_type).is_some_and(is_bool_type) {
+                if field_attr.explicit {
+                    quote! {
+                        if let Some(v) = self.#field_name {
+                            write!(f, " {}=\"{}\"", #attr_name, v)?;
+                        }
+                    }
+                } else {
+                    quote! {
+                        if self.#field_name == Some(true) {
+                            write!(f, " {}", #attr_name)?;
+                        }
+                    }
+                }
+            } else if is_option_type(field_type) {
+                let value = if inner_type(field_type).is_some_and(is_raw_type) {
+                    quote! { v }
+                } else {
+                    quote! { #bluth_crate::html::escape_attr(v) }
+                };
+                let write = quote! {
+                    write!(f, " {}=\"{}\"", #attr_name, #value)?;
+                };
+                let write = if field_attr.skip_if_empty {
+                    quote! {
+                        if !::core::convert::AsRef::<str>::as_ref(v).is_empty() {
+                            #write
+                        }
+                    }
+                } else {
+                    write
+                };
+                quote! {
+                    if let Some(ref v) = self.#field_name {
+                        #write
+                    }
+                }
+            } else if field_attr.skip_if_empty {
+                quote! {
+                    if !::core::convert::AsRef::<str>::as_ref(&self.#field_name).is_empty() {
+                        write!(f, " {}=\"{}\"", #attr_name, #bluth_crate::html::escape_attr(&self.#field_name))?;
+                    }
+                }
+            } else if is_raw_type(field_type) {
+                quote! {
+                    write!(f, " {}=\"{}\"", #attr_name, &self.#field_name)?;
+                }
+            } else if is_style_type(field_type) {
+                quote! {
+                    if !self.#field_name.is_empty() {
+                        write!(f, " {}=\"{}\"", #attr_name, #bluth_crate::html::escape_attr(&self.#field_name))?;
+                    }
+                }
+            } else {
+                quote! {
+                    write!(f, " {}=\"{}\"", #attr_name, #bluth_crate::html::escape_attr(&self.#field_name))?;
+                }
+            };
+            guard_attr(quote! { #attr_name }, write, bluth_crate)
+        })
+        .collect()
+}
+
+fn emit_spec_attrs(
+    spec: &ElementSpec,
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let mut attr_code = emit_attrs(&spec.attrs, true, signal_fields, bluth_crate);
+    if let Some(ref test_id) = spec.test_id {
+        let test_id_attr = format!(" data-testid=\"{}\"", escape_attr_str(test_id));
+        attr_code.extend(quote! {
+            if cfg!(debug_assertions) {
+                f.write_str(#test_id_attr)?;
+            }
+        });
+    }
+    attr_code
+}
+
+fn wrap_with_dynamic_tag(
+    content: &TokenStream,
+    tag_field: &Ident,
+    spec: &ElementSpec,
+    field_attrs: &[FieldAttr],
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    let attr_code = emit_spec_attrs(spec, signal_fields, bluth_crate);
+    let field_attr_code = emit_field_attrs(field_attrs, bluth_crate);
+    let void_close = if enabled(Feature::Xhtml) {
+        quote! { write!(f, "></{}>", __bluth_tag)?; }
+    } else {
+        quote! { f.write_str("/>")?; }
+    };
+    let is_void = if spec.xml {
+        quote! { false }
+    } else {
+        quote! { #bluth_crate::html::is_void_element(__bluth_tag) }
+    };
+    let (attrs, end_hook) = element_hooks(
+        quote! { __bluth_tag },
+        quote! {
+            #attr_code
+            #(#field_attr_code)*
+        },
+        bluth_crate,
+    );
+
+    let element = quote! {
+        write!(f, "<{}", __bluth_tag)?;
+        #attrs
+        if #is_void {
+            #void_close
+        } else {
+            f.write_str(">")?;
+            #content
+            write!(f, "</{}>", __bluth_tag)?;
+        }
+        #end_hook
+    };
+    let element = if enabled(Feature::CspStrict) {
+        quote! {
+            let __bluth_is_script = __bluth_tag.eq_ignore_ascii_case("script");
+            let mut __bluth_script = #bluth_crate::render::Buffer::default();
+            {
+                let f: &mut dyn ::core::fmt::Write = if __bluth_is_script {
+                    &mut __bluth_script
+                } else {
+                    &mut *f
+                };
+                #element
+            }
+            if __bluth_is_script && #bluth_crate::csp::allow_script(__bluth_script.as_str())? {
+                f.write_str(&__bluth_script.into_string())?;
+            }
+        }
+    } else {
+        element
+    };
+
+    quote! {
+        let __bluth_tag = #bluth_crate::html::tag_name(&self.#tag_field)?;
+        #element
+    }
+}
+
+fn guard_attr(key_expr: TokenStream, write: TokenStream, bluth_crate: &TokenStream) -> TokenStream {
+    if !enabled(Feature::CspStrict) {
+        return write;
+    }
+    quote! {
+        if #bluth_crate::csp::allow_attr(::core::convert::AsRef::<str>::as_ref(&#key_expr))? {
+            #write
+        }
+    }
+}
+
+pub fn guard_script(tag: &str, code: TokenStream, bluth_crate: &TokenStream) -> TokenStream {
+    if !enabled(Feature::CspStrict) || !tag.eq_ignore_ascii_case("script") {
+        return code;
+    }
+    quote! {
+        let mut __bluth_script = #bluth_crate::render::Buffer::default();
+        {
+            let f: &mut dyn ::core::fmt::Write = &mut __bluth_script;
+            #code
+        }
+        if #bluth_crate::csp::allow_script(__bluth_script.as_str())? {
+            f.write_str(&__bluth_script.into_string())?;
+        }
+    }
+}
+
+pub fn emit_attrs(
     attrs: &[AttrSpec],
     use_self: bool,
     signal_fields: &HashMap<String, SignalFieldInfo>,
     bluth_crate: &TokenStream,
+) -> TokenStream {
+    let scope = if use_self {
+        Scope::SelfFields(&[])
+    } else {
+        Scope::Locals
+    };
+    emit_scoped_attrs(attrs, scope, signal_fields, bluth_crate)
+}
+
+fn emit_item_attrs(
+    attrs: &[AttrSpec],
+    locals: &[&str],
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
+) -> TokenStream {
+    emit_scoped_attrs(attrs, Scope::SelfFields(locals), signal_fields, bluth_crate)
+}
+
+#[derive(Clone, Copy)]
+enum Scope<'a> {
+    SelfFields(&'a [&'a str]),
+    Locals,
+}
+
+fn emit_scoped_attrs(
+    attrs: &[AttrSpec],
+    scope: Scope,
+    signal_fields: &HashMap<String, SignalFieldInfo>,
+    bluth_crate: &TokenStream,
 ) -> TokenStream {
     let attr_writes: Vec<_> = attrs
         .iter()
-        .map(|attr| emit_single_attr(attr, use_self, signal_fields, bluth_crate))
+        .map(|attr| {
+            let write = emit_single_attr(attr, scope, signal_fields, bluth_crate);
+            let key_expr = match &attr.key {
+                AttrKey::Literal(k) => quote! { #k },
+                AttrKey::Interpolated(k) => interpolate(k, scope),
+            };
+            guard_attr(key_expr, write, bluth_crate)
+        })
         .collect();
 
     quote! { #(#attr_writes)* }
@@ -536,13 +1639,13 @@ fn emit_attrs(
 
 fn emit_single_attr(
     attr: &AttrSpec,
-    use_self: bool,
+    scope: Scope,
     signal_fields: &HashMap<String, SignalFieldInfo>,
     bluth_crate: &TokenStream,
 ) -> TokenStream {
     let key_expr = match &attr.key {
         AttrKey::Literal(k) => quote! { #k },
-        AttrKey::Interpolated(k) => interpolate(k, use_self),
+        AttrKey::Interpolated(k) => interpolate(k, scope),
     };
 
     match &attr.value {
@@ -553,7 +1656,7 @@ fn emit_single_attr(
             }
         }
         AttrValue::Interpolated(v) => {
-            let val_expr = interpolate(v, use_self);
+            let val_expr = interpolate(v, scope);
             quote! {
                 write!(f, " {}=\"{}\"", #key_expr, #bluth_crate::html::escape_attr(#val_expr))?;
             }
@@ -568,15 +1671,24 @@ fn emit_single_attr(
         }
         AttrValue::Path(path) => {
             quote! {
-                write!(f, " {}=\"{}\"", #key_expr, #bluth_crate::html::escape_attr(<#path as ::core::convert::AsRef<str>>::as_ref(&#path)))?;
+                write!(f, " {}=\"{}\"", #key_expr, #bluth_crate::html::escape_attr(::core::convert::AsRef::<str>::as_ref(&#path)))?;
             }
         }
         AttrValue::SignalFieldBinding(field_ident) => {
             let field_name = field_ident.to_string();
             if let Some(signal_info) = signal_fields.get(&field_name) {
                 let selector_type = &signal_info.selector_type;
+                if matches!(&attr.key, AttrKey::Literal(k) if k == "bind") {
+                    return quote! {
+                        #bluth_crate::signal::track::<#selector_type>()?;
+                        let __bluth_name = <#selector_type as #bluth_crate::SignalSelector>::NAME;
+                        write!(f, " name=\"{}\" data-bind=\"{}\"", __bluth_name, __bluth_name)?;
+                        #bluth_crate::signal::BindValue::fmt_bind(&self.#field_ident.0, f)?;
+                    };
+                }
                 quote! {
                     let _ = &self.#field_ident;
+                    #bluth_crate::signal::track::<#selector_type>()?;
                     write!(f, " {}=\"{}\"", #key_expr, <#selector_type as #bluth_crate::SignalSelector>::NAME)?;
                 }
             } else {
@@ -589,9 +1701,57 @@ fn emit_single_attr(
                 }
             }
         }
+        AttrValue::Expr(expr) if matches!(&attr.key, AttrKey::Literal(k) if k == "style") => {
+            quote! {
+                let __bluth_style = ::std::string::ToString::to_string(&#expr);
+                if !__bluth_style.is_empty() {
+                    write!(f, " {}=\"{}\"", #key_expr, #bluth_crate::html::escape_attr(&__bluth_style))?;
+                }
+            }
+        }
         AttrValue::Expr(expr) => {
             quote! {
-                write!(f, " {}=\"{}\"", #key_expr, #bluth_crate::html::escape_attr(#expr))?;
+                {
+                    #[allow(unused_imports)]
+                    use #bluth_crate::html::{ViaBool as _, ViaValue as _};
+                    (&&#bluth_crate::html::AttrExpr(::core::convert::AsRef::<str>::as_ref(&#key_expr), &(#expr))).write_attr(f)?;
+                }
+            }
+        }
+        AttrValue::Conditional(cond) => {
+            quote! {
+                if #cond {
+                    write!(f, " {}", #key_expr)?;
+                }
+            }
+        }
+        AttrValue::AutoDir => {
+            quote! {
+                if #bluth_crate::context::is_rtl() {
+                    f.write_str(" dir=\"rtl\"")?;
+                }
+            }
+        }
+        AttrValue::List(entries) => {
+            let pushes = entries.iter().map(|entry| {
+                let name = escape_attr_str(&entry.name);
+                let push = quote! {
+                    if !__bluth_list.is_empty() {
+                        __bluth_list.push(' ');
+                    }
+                    __bluth_list.push_str(#name);
+                };
+                match &entry.cond {
+                    Some(cond) => quote! { if #cond { #push } },
+                    None => push,
+                }
+            });
+            quote! {
+                let mut __bluth_list = String::new();
+                #(#pushes)*
+                if !__bluth_list.is_empty() {
+                    write!(f, " {}=\"{}\"", #key_expr, __bluth_list)?;
+                }
             }
         }
     }
@@ -629,7 +1789,7 @@ fn escape_attr_str(value: &str) -> String {
     result
 }
 
-fn interpolate(template: &str, use_self: bool) -> TokenStream {
+fn interpolate(template: &str, scope: Scope) -> TokenStream {
     let mut format_parts = Vec::new();
     let mut value_parts: Vec<TokenStream> = Vec::new();
     let mut current_literal = String::new();
@@ -658,13 +1818,36 @@ fn interpolate(template: &str, use_self: bool) -> TokenStream {
                 field_name.push(next_ch);
             }
 
-            let field_ident = syn::Ident::new(&field_name, proc_macro2::Span::call_site());
-            format_parts.push("{}".to_string());
-
-            if use_self {
-                value_parts.push(quote! { &self.#field_ident });
+            let (field_name, spec) = match field_name.split_once(':') {
+                Some((path, spec)) => (path.to_string(), format!("{{:{}}}", spec)),
+                None => (field_name, "{}".to_string()),
+            };
+            let segments = field_name
+                .split('.')
+                .map(|segment| match segment.parse::<usize>() {
+                    Ok(index) => {
+                        let index = syn::Index::from(index);
+                        quote! { #index }
+                    }
+                    Err(_) => {
+                        let ident = syn::Ident::new(segment, proc_macro2::Span::call_site());
+                        quote! { #ident }
+                    }
+                });
+            let field_path = quote! { #(#segments).* };
+            format_parts.push(spec);
+
+            let is_local = match scope {
+                Scope::SelfFields(locals) => field_name
+                    .split('.')
+                    .next()
+                    .is_some_and(|root| locals.contains(&root)),
+                Scope::Locals => true,
+            };
+            if is_local {
+                value_parts.push(quote! { &#field_path });
             } else {
-                value_parts.push(quote! { &#field_ident });
+                value_parts.push(quote! { &self.#field_path });
             }
         } else if ch == '}' {
             if chars.peek() == Some(&'}') {
@@ -690,3 +1873,270 @@ fn interpolate(template: &str, use_self: bool) -> TokenStream {
         quote! { format!(#format_string, #(#value_parts),*) }
     }
 }
+
+pub fn generate_size_hint(
+    data: &syn::Data,
+    render_body: &TokenStream,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    if let syn::Data::Enum(_) = data {
+        let shortest_arm = syn::parse2::<syn::ExprMatch>(render_body.clone())
+            .ok()
+            .and_then(|expr| {
+                expr.arms
+                    .iter()
+                    .map(|arm| literal_output_bytes(arm.body.to_token_stream()))
+                    .min()
+            })
+            .unwrap_or(0);
+        return Ok(quote! { #shortest_arm });
+    }
+
+    let literal_bytes = literal_output_bytes(render_body.clone());
+    let syn::Data::Struct(DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = data
+    else {
+        return Ok(quote! { #literal_bytes });
+    };
+
+    let mut dynamic = Vec::new();
+    for field in &fields.named {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+        let spec = FieldSpec::from_attrs(&field.attrs, field_name, field_type)?;
+        if !spec.should_render
+            || spec.is_attr
+            || spec.lazy
+            || spec.render_if.is_some()
+            || spec.if_flag.is_some()
+        {
+            continue;
+        }
+        if is_text_type(field_type) {
+            dynamic.push(quote! { + self.#field_name.len() });
+        } else if iter_item_type(field_type).is_some() {
+            dynamic.push(quote! { + self.#field_name.len() * 16 });
+        } else if is_children_type(field_type) {
+            dynamic.push(quote! { + #bluth_crate::Render::size_hint(&self.#field_name) });
+        }
+    }
+
+    Ok(quote! { #literal_bytes #(#dynamic)* })
+}
+
+pub fn generate_describe(
+    data: &syn::Data,
+    spec: &ElementSpec,
+    bluth_crate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let tag = match (&spec.tag, &spec.tag_field) {
+        (Some(tag), _) => quote! { ::core::option::Option::Some(#tag) },
+        (None, Some(field)) => {
+            let field = format!("{{{}}}", field);
+            quote! { ::core::option::Option::Some(#field) }
+        }
+        (None, None) => quote! { ::core::option::Option::None },
+    };
+
+    let mut attrs: Vec<String> = spec
+        .attrs
+        .iter()
+        .map(|attr| match &attr.key {
+            AttrKey::Literal(key) | AttrKey::Interpolated(key) => key.clone(),
+        })
+        .collect();
+    let mut children = Vec::new();
+
+    let variants: Vec<(Option<&Ident>, &Fields)> = match data {
+        syn::Data::Struct(data) => vec![(None, &data.fields)],
+        syn::Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| (Some(&variant.ident), &variant.fields))
+            .collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+
+    for (variant, fields) in variants {
+        for (index, field) in fields.iter().enumerate() {
+            let member = field
+                .ident
+                .as_ref()
+                .map_or_else(|| index.to_string(), ToString::to_string);
+            let label = match variant {
+                Some(variant) => format!("{}::{}", variant, member),
+                None => member,
+            };
+            let field_type = &field.ty;
+            let field_spec = match &field.ident {
+                Some(ident) => FieldSpec::from_attrs(&field.attrs, ident, field_type)?,
+                None => FieldSpec {
+                    should_render: true,
+                    ..FieldSpec::default()
+                },
+            };
+
+            if field_spec.is_attr {
+                if field_spec.attr_flatten || field_spec.attr_spread {
+                    attrs.push(format!("..{}", label));
+                } else if let Some(ident) = &field.ident {
+                    attrs.push(
+                        field_spec
+                            .attr_rename
+                            .clone()
+                            .unwrap_or_else(|| attr_name_from_ident(ident)),
+                    );
+                }
+                continue;
+            }
+            if !field_spec.should_render {
+                continue;
+            }
+
+            let unwrapped = if is_option_type(field_type) {
+                inner_type(field_type).unwrap_or(field_type)
+            } else {
+                field_type
+            };
+            let item = iter_item_type(unwrapped);
+            let many = item.is_some() || field_spec.iter;
+            let child = item.unwrap_or(unwrapped);
+            let child = if is_option_type(child) {
+                inner_type(child).unwrap_or(child)
+            } else {
+                child
+            };
+            let child_tag = match &field_spec.tag {
+                Some(tag) => quote! { ::core::option::Option::Some(#tag) },
+                None => quote! { ::core::option::Option::None },
+            };
+
+            children.push(quote! {
+                #bluth_crate::introspect::ChildSchema {
+                    field: #label,
+                    ty: ::core::any::type_name::<#child>(),
+                    tag: #child_tag,
+                    many: #many,
+                    describe: (&&#bluth_crate::introspect::Probe::<#child>::new()).describe_fn(),
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[allow(unused_imports)]
+        use #bluth_crate::introspect::{ViaDescribe as _, ViaOpaque as _};
+        #bluth_crate::introspect::ComponentSchema {
+            name: ::core::any::type_name::<Self>(),
+            tag: #tag,
+            attrs: ::std::vec![#(#attrs),*],
+            children: ::std::vec![#(#children),*],
+        }
+    })
+}
+
+/// A lower bound on the bytes written by the string literals in `tokens`:
+/// only the cheapest `if`/`match` branch counts and loop bodies count as zero.
+pub fn literal_output_bytes(tokens: TokenStream) -> usize {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut total = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        let (bytes, next) = match &tokens[i] {
+            TokenTree::Ident(ident) if ident == "if" => if_output_bytes(&tokens, i),
+            TokenTree::Ident(ident) if ident == "match" => match_output_bytes(&tokens, i),
+            TokenTree::Ident(ident) if ident == "for" || ident == "while" => (
+                0,
+                next_block(&tokens, i + 1).map_or(tokens.len(), |body| body + 1),
+            ),
+            TokenTree::Group(group) => (literal_output_bytes(group.stream()), i + 1),
+            TokenTree::Literal(literal) => (
+                syn::parse2::<syn::LitStr>(TokenTree::Literal(literal.clone()).into())
+                    .map(|lit| format_literal_len(&lit.value()))
+                    .unwrap_or(0),
+                i + 1,
+            ),
+            _ => (0, i + 1),
+        };
+        total += bytes;
+        i = next;
+    }
+    total
+}
+
+fn next_block(tokens: &[TokenTree], from: usize) -> Option<usize> {
+    (from..tokens.len()).find(|&i| {
+        matches!(&tokens[i], TokenTree::Group(group) if group.delimiter() == proc_macro2::Delimiter::Brace)
+    })
+}
+
+fn block_output_bytes(token: &TokenTree) -> usize {
+    match token {
+        TokenTree::Group(group) => literal_output_bytes(group.stream()),
+        _ => 0,
+    }
+}
+
+fn if_output_bytes(tokens: &[TokenTree], start: usize) -> (usize, usize) {
+    let Some(then) = next_block(tokens, start + 1) else {
+        return (0, tokens.len());
+    };
+    let then_bytes = block_output_bytes(&tokens[then]);
+    match (tokens.get(then + 1), tokens.get(then + 2)) {
+        (Some(TokenTree::Ident(else_)), Some(TokenTree::Ident(if_)))
+            if else_ == "else" && if_ == "if" =>
+        {
+            let (else_bytes, next) = if_output_bytes(tokens, then + 2);
+            (then_bytes.min(else_bytes), next)
+        }
+        (Some(TokenTree::Ident(else_)), Some(block)) if else_ == "else" => {
+            (then_bytes.min(block_output_bytes(block)), then + 3)
+        }
+        _ => (0, then + 1),
+    }
+}
+
+fn match_output_bytes(tokens: &[TokenTree], start: usize) -> (usize, usize) {
+    let Some(arms) = next_block(tokens, start + 1) else {
+        return (0, tokens.len());
+    };
+    let expr: TokenStream = tokens[start..=arms].iter().cloned().collect();
+    let bytes = syn::parse2::<syn::ExprMatch>(expr)
+        .ok()
+        .and_then(|expr| {
+            expr.arms
+                .iter()
+                .map(|arm| literal_output_bytes(arm.body.to_token_stream()))
+                .min()
+        })
+        .unwrap_or(0);
+    (bytes, arms + 1)
+}
+
+fn format_literal_len(value: &str) -> usize {
+    let mut len = 0;
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                len += 1;
+            }
+            '{' => {
+                for next in chars.by_ref() {
+                    if next == '}' {
+                        break;
+                    }
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                len += 1;
+            }
+            _ => len += ch.len_utf8(),
+        }
+    }
+    len
+}
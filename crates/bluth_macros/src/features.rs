@@ -0,0 +1,52 @@
+#[cfg(test)]
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    TestIds,
+    CspStrict,
+    Linkme,
+    DevTemplates,
+    Metrics,
+    Xhtml,
+    Introspect,
+    RenderHooks,
+}
+
+#[cfg(test)]
+thread_local! {
+    static DEFAULTS_ONLY: Cell<bool> = const { Cell::new(false) };
+}
+
+// Expansion checks go through here rather than `cfg!` so the snapshot tests
+// can expand with default features whatever the crate was built with.
+pub fn enabled(feature: Feature) -> bool {
+    #[cfg(test)]
+    if DEFAULTS_ONLY.with(Cell::get) {
+        return false;
+    }
+    match feature {
+        Feature::TestIds => cfg!(feature = "test-ids"),
+        Feature::CspStrict => cfg!(feature = "csp-strict"),
+        Feature::Linkme => cfg!(feature = "linkme"),
+        Feature::DevTemplates => cfg!(feature = "dev-templates"),
+        Feature::Metrics => cfg!(feature = "metrics"),
+        Feature::Xhtml => cfg!(feature = "xhtml"),
+        Feature::Introspect => cfg!(feature = "introspect"),
+        Feature::RenderHooks => cfg!(feature = "render-hooks"),
+    }
+}
+
+#[cfg(test)]
+pub fn with_defaults_only<R>(run: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DEFAULTS_ONLY.with(|defaults| defaults.set(self.0));
+        }
+    }
+
+    let _restore = Restore(DEFAULTS_ONLY.with(|defaults| defaults.replace(true)));
+    run()
+}
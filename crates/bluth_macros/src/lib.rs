@@ -1,14 +1,22 @@
-use heck::ToLowerCamelCase;
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use proc_macro::TokenStream;
 use proc_macro_crate::{FoundCrate, crate_name};
 use quote::quote;
 use syn::{Data, DataEnum, DeriveInput, Fields, parse_macro_input};
 
 mod attributes;
+mod builder;
 mod codegen;
+mod features;
+mod markup;
+#[cfg(test)]
+mod snapshots;
 
 use attributes::ElementSpec;
-use codegen::{generate_enum_render, generate_struct_render};
+use codegen::{
+    generate_describe, generate_enum_render, generate_size_hint, generate_struct_render,
+};
+use features::{Feature, enabled};
 
 fn get_bluth_crate() -> proc_macro2::TokenStream {
     match crate_name("bluth") {
@@ -21,6 +29,17 @@ fn get_bluth_crate() -> proc_macro2::TokenStream {
     }
 }
 
+fn is_bluth_itself() -> bool {
+    matches!(crate_name("bluth"), Ok(FoundCrate::Itself))
+}
+
+fn pretty(tokens: &proc_macro2::TokenStream) -> String {
+    match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => tokens.to_string(),
+    }
+}
+
 #[proc_macro_derive(Element, attributes(element, format, attr, map_or))]
 pub fn derive_element(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -33,9 +52,18 @@ pub fn derive_element(input: TokenStream) -> TokenStream {
 
 fn derive_element_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
-    let spec = ElementSpec::from_attrs(&input.attrs)?;
+    let mut spec = ElementSpec::from_attrs(&input.attrs)?;
     let bluth_crate = get_bluth_crate();
 
+    if !enabled(Feature::TestIds) {
+        spec.test_id = None;
+    } else if spec.test_id.is_none()
+        && (spec.tag.is_some() || spec.tag_field.is_some())
+        && !is_bluth_itself()
+    {
+        spec.test_id = Some(name.to_string().to_kebab_case());
+    }
+
     let render_body = match &input.data {
         Data::Struct(data) => generate_struct_render(data, &spec, &bluth_crate)?,
         Data::Enum(data) => generate_enum_render(name, data, &spec, &bluth_crate)?,
@@ -47,17 +75,117 @@ fn derive_element_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStr
         }
     };
 
+    let size_hint = generate_size_hint(&input.data, &render_body, &bluth_crate)?;
+
+    let render_body = match &input.data {
+        Data::Struct(data) if enabled(Feature::DevTemplates) => {
+            with_template_override(name, &data.fields, render_body, &bluth_crate)
+        }
+        _ => render_body,
+    };
+
     let generics = &input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    Ok(quote! {
-        impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let timer = enabled(Feature::Metrics).then(|| {
+        quote! {
+            let _bluth_timer = #bluth_crate::metrics::RenderTimer::start::<Self>();
+        }
+    });
+
+    let describe = if enabled(Feature::Introspect) {
+        let schema = generate_describe(&input.data, &spec, &bluth_crate)?;
+        Some(quote! {
+            impl #impl_generics #bluth_crate::introspect::Describe for #name #ty_generics #where_clause {
+                fn describe() -> #bluth_crate::introspect::ComponentSchema {
+                    #schema
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let expanded = quote! {
+        impl #impl_generics #bluth_crate::Render for #name #ty_generics #where_clause {
+            fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                let _bluth_depth = #bluth_crate::render::DepthGuard::enter()?;
+                #timer
                 #render_body
                 Ok(())
             }
+
+            fn size_hint(&self) -> usize {
+                #size_hint
+            }
         }
-    })
+
+        impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #bluth_crate::Render::render(self, f)
+            }
+        }
+
+        impl #impl_generics #bluth_crate::html::RootAttrs for #name #ty_generics #where_clause {}
+
+        #describe
+    };
+
+    if spec.debug_expand {
+        eprintln!("// #[derive(Element)] for {}\n{}", name, pretty(&expanded));
+    }
+
+    Ok(expanded)
+}
+
+fn with_template_override(
+    name: &syn::Ident,
+    fields: &Fields,
+    render_body: proc_macro2::TokenStream,
+    bluth_crate: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Fields::Named(fields) = fields else {
+        return render_body;
+    };
+    let component = name.to_string();
+    let arms = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named field");
+        let key = ident.to_string();
+        let escape = attributes::is_text_type(&field.ty);
+        quote! {
+            #key => (&&#bluth_crate::templates::FieldValue(&self.#ident, #escape)).template_value(),
+        }
+    });
+
+    quote! {
+        if cfg!(debug_assertions)
+            && let ::core::option::Option::Some(template) = #bluth_crate::templates::lookup(#component)
+        {
+            #[allow(unused_imports)]
+            use #bluth_crate::templates::{ViaDisplay as _, ViaFallback as _};
+            return f.write_str(&#bluth_crate::templates::render(&template, |field| match field {
+                #(#arms)*
+                _ => ::core::option::Option::None,
+            }));
+        }
+        #render_body
+    }
+}
+
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let markup = parse_macro_input!(input as markup::Markup);
+    markup::generate_markup(&markup, &get_bluth_crate()).into()
+}
+
+#[proc_macro_derive(ElementBuilder, attributes(builder))]
+pub fn derive_element_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match builder::generate_builder(&input, &get_bluth_crate()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
 
 #[proc_macro_derive(Signal, attributes(signal))]
@@ -80,13 +208,203 @@ fn derive_signal_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStre
         ));
     };
 
-    generate_signal_enum(name, enum_data, &input.vis)
+    let args = parse_signal_args(&input.attrs)?;
+    let vis = args.vis.as_ref().unwrap_or(&input.vis);
+
+    generate_signal_enum(name, enum_data, vis, args.doc_hidden)
+}
+
+#[proc_macro_derive(SignalStore, attributes(signal, signal_store))]
+pub fn derive_signal_store(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_signal_store_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn signal_store_enum_name(input: &DeriveInput) -> syn::Result<syn::Ident> {
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("signal_store"))
+    else {
+        return Ok(quote::format_ident!("{}Signals", input.ident));
+    };
+
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let ident: syn::Ident = input.parse()?;
+        if ident != "name" {
+            return Err(syn::Error::new_spanned(ident, "expected `name`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        input.parse()
+    })
+}
+
+fn derive_signal_store_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let bluth = get_bluth_crate();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "SignalStore can only be derived for structs with named fields",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "SignalStore can only be derived for structs with named fields",
+        ));
+    };
+
+    let enum_name = signal_store_enum_name(input)?;
+    let args = parse_signal_args(&input.attrs)?;
+    let selector_vis = args.vis.as_ref().unwrap_or(vis);
+
+    let variants: Vec<syn::Variant> = fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().expect("named field");
+            let variant_name = quote::format_ident!("{}", ident.to_string().to_upper_camel_case());
+            let ty = &field.ty;
+            let signal_attrs = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("signal"));
+            syn::parse_quote! {
+                #(#signal_attrs)*
+                #variant_name(#ty)
+            }
+        })
+        .collect();
+
+    let enum_data: DataEnum = {
+        let item: syn::ItemEnum = syn::parse_quote! {
+            #vis enum #enum_name {
+                #(#variants),*
+            }
+        };
+        DataEnum {
+            enum_token: item.enum_token,
+            brace_token: item.brace_token,
+            variants: item.variants,
+        }
+    };
+
+    let enum_variants: Vec<_> = enum_data
+        .variants
+        .iter()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let Fields::Unnamed(fields) = &v.fields else {
+                unreachable!("variants are built with one unnamed field");
+            };
+            let ty = &fields.unnamed.first().expect("one field").ty;
+            quote! { #variant_name(#ty) }
+        })
+        .collect();
+
+    let signal_impls = generate_signal_enum(&enum_name, &enum_data, selector_vis, args.doc_hidden)?;
+
+    let field_idents: Vec<_> = fields
+        .named
+        .iter()
+        .map(|field| field.ident.clone().expect("named field"))
+        .collect();
+    let variant_idents: Vec<_> = enum_data.variants.iter().map(|v| v.ident.clone()).collect();
+
+    Ok(quote! {
+        #vis enum #enum_name {
+            #(#enum_variants),*
+        }
+
+        #signal_impls
+
+        impl #name {
+            #selector_vis fn signals(&self) -> ::std::vec::Vec<#enum_name> {
+                ::std::vec![
+                    #(#enum_name::#variant_idents(::core::clone::Clone::clone(&self.#field_idents))),*
+                ]
+            }
+
+            #selector_vis fn diff(old: &Self, new: &Self) -> ::std::vec::Vec<#enum_name> {
+                let mut changed = ::std::vec::Vec::new();
+                #(
+                    if old.#field_idents != new.#field_idents {
+                        changed.push(#enum_name::#variant_idents(::core::clone::Clone::clone(&new.#field_idents)));
+                    }
+                )*
+                changed
+            }
+
+            #selector_vis fn apply(&mut self, incoming: #bluth::signal::SignalMap) {
+                #(
+                    if let ::core::option::Option::Some(value) =
+                        #bluth::signal::signals_from_map::<#variant_idents>(&incoming)
+                    {
+                        self.#field_idents = value;
+                    }
+                )*
+            }
+        }
+    })
 }
 
 struct VariantInfo {
     variant_name: syn::Ident,
     signal_name: String,
     field_type: syn::Type,
+    vis: Option<syn::Visibility>,
+    doc_hidden: bool,
+}
+
+#[derive(Default)]
+struct SignalArgs {
+    name: Option<String>,
+    vis: Option<syn::Visibility>,
+    doc_hidden: bool,
+}
+
+fn parse_signal_args(attrs: &[syn::Attribute]) -> syn::Result<SignalArgs> {
+    let mut args = SignalArgs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("signal")) {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            while !input.is_empty() {
+                let ident: syn::Ident = input.parse()?;
+                match ident.to_string().as_str() {
+                    "name" => {
+                        input.parse::<syn::Token![=]>()?;
+                        let lit: syn::LitStr = input.parse()?;
+                        args.name = Some(lit.value());
+                    }
+                    "vis" => {
+                        input.parse::<syn::Token![=]>()?;
+                        let lit: syn::LitStr = input.parse()?;
+                        args.vis = Some(lit.parse()?);
+                    }
+                    "doc_hidden" => args.doc_hidden = true,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            "expected `name`, `vis` or `doc_hidden`",
+                        ));
+                    }
+                }
+                if !input.is_empty() {
+                    input.parse::<syn::Token![,]>()?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(args)
 }
 
 fn parse_variant(variant: &syn::Variant) -> syn::Result<VariantInfo> {
@@ -106,30 +424,24 @@ fn parse_variant(variant: &syn::Variant) -> syn::Result<VariantInfo> {
         ));
     }
 
-    let field_type = fields.unnamed.first().expect("checked len above").ty.clone();
+    let field_type = fields
+        .unnamed
+        .first()
+        .expect("checked len above")
+        .ty
+        .clone();
 
-    let signal_name = variant
-        .attrs
-        .iter()
-        .find(|attr| attr.path().is_ident("signal"))
-        .and_then(|attr| {
-            attr.parse_args_with(|input: syn::parse::ParseStream| {
-                let ident: syn::Ident = input.parse()?;
-                if ident != "name" {
-                    return Err(syn::Error::new_spanned(ident, "expected `name`"));
-                }
-                input.parse::<syn::Token![=]>()?;
-                let lit: syn::LitStr = input.parse()?;
-                Ok(lit.value())
-            })
-            .ok()
-        })
+    let args = parse_signal_args(&variant.attrs)?;
+    let signal_name = args
+        .name
         .unwrap_or_else(|| variant_name.to_string().to_lower_camel_case());
 
     Ok(VariantInfo {
         variant_name,
         signal_name,
         field_type,
+        vis: args.vis,
+        doc_hidden: args.doc_hidden,
     })
 }
 
@@ -137,6 +449,7 @@ fn generate_signal_enum(
     enum_name: &syn::Ident,
     data: &DataEnum,
     vis: &syn::Visibility,
+    doc_hidden: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let bluth = get_bluth_crate();
 
@@ -150,8 +463,11 @@ fn generate_signal_enum(
         .iter()
         .map(|v| {
             let selector_name = &v.variant_name;
+            let vis = v.vis.as_ref().unwrap_or(vis);
+            let doc_hidden = (doc_hidden || v.doc_hidden).then(|| quote! { #[doc(hidden)] });
             quote! {
                 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                #doc_hidden
                 #vis struct #selector_name;
             }
         })
@@ -163,6 +479,13 @@ fn generate_signal_enum(
             let selector_name = &v.variant_name;
             let signal_name = &v.signal_name;
             let field_type = &v.field_type;
+            let escaped = signal_name.replace('\\', "\\\\").replace('"', "\\\"");
+            let data_bind = format!(
+                "data-bind=\"{}\"",
+                signal_name.replace('&', "&amp;").replace('"', "&quot;")
+            );
+            let json_key = format!("\"{}\"", escaped);
+            let js_ref = format!("${}", signal_name);
 
             quote! {
                 impl #bluth::SignalSelector for #selector_name {
@@ -170,6 +493,9 @@ fn generate_signal_enum(
                     type Enum = #enum_name;
 
                     const NAME: &'static str = #signal_name;
+                    const DATA_BIND: &'static str = #data_bind;
+                    const JSON_KEY: &'static str = #json_key;
+                    const JS_REF: &'static str = #js_ref;
 
                     fn extract(value: &#enum_name) -> ::core::option::Option<&Self::Value> {
                         match value {
@@ -192,13 +518,35 @@ fn generate_signal_enum(
 
                 impl ::core::convert::AsRef<str> for #selector_name {
                     fn as_ref(&self) -> &str {
-                        <#selector_name as #bluth::SignalSelector>::NAME
+                        #bluth::signal::track_name::<#selector_name>()
                     }
                 }
             }
         })
         .collect();
 
+    let registrations: Vec<_> = variants
+        .iter()
+        .filter(|_| enabled(Feature::Linkme))
+        .map(|v| {
+            let selector_name = &v.variant_name;
+            let signal_name = &v.signal_name;
+            let static_name = quote::format_ident!(
+                "__BLUTH_SIGNAL_{}",
+                selector_name.to_string().to_shouty_snake_case()
+            );
+            quote! {
+                #[doc(hidden)]
+                #[#bluth::__linkme::distributed_slice(#bluth::signal::SIGNAL_NAMES)]
+                #[linkme(crate = #bluth::__linkme)]
+                static #static_name: (&'static str, &'static str) = (
+                    #signal_name,
+                    ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#selector_name)),
+                );
+            }
+        })
+        .collect();
+
     let signal_name_arms: Vec<_> = variants
         .iter()
         .map(|v| {
@@ -231,6 +579,12 @@ fn generate_signal_enum(
         })
         .collect();
 
+    let selector_names: Vec<_> = variants
+        .iter()
+        .map(|v| v.variant_name.to_string())
+        .collect();
+    let signal_names: Vec<_> = variants.iter().map(|v| &v.signal_name).collect();
+
     let signal_enum_impl = quote! {
         impl #bluth::SignalEnum for #enum_name {
             fn signal_name(&self) -> &'static str {
@@ -244,6 +598,10 @@ fn generate_signal_enum(
                     #(#to_json_arms)*
                 }
             }
+
+            fn selectors() -> &'static [(&'static str, &'static str)] {
+                &[#((#selector_names, #signal_names)),*]
+            }
         }
     };
 
@@ -311,6 +669,8 @@ fn generate_signal_enum(
 
         #(#selector_impls)*
 
+        #(#registrations)*
+
         #signal_enum_impl
 
         #serialize_impl
@@ -0,0 +1,316 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, LitStr, Pat, Token};
+
+use crate::attributes::{
+    AttrSpec, AttrValue, ListEntry, bare_attr, classify_key, classify_value, parse_attr_key,
+};
+use crate::codegen::{emit_attrs, guard_script, is_void_element, literal_output_bytes, void_close};
+
+pub struct Markup {
+    nodes: Vec<Node>,
+}
+
+enum Node {
+    Text(LitStr),
+    Expr(Expr),
+    Element {
+        tag: String,
+        attrs: Vec<AttrSpec>,
+        children: Option<Vec<Node>>,
+    },
+    If {
+        cond: Expr,
+        then: Vec<Node>,
+        otherwise: Option<Vec<Node>>,
+    },
+    For {
+        pat: Pat,
+        iter: Expr,
+        body: Vec<Node>,
+    },
+    Let(syn::Stmt),
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Markup {
+            nodes: parse_nodes(input)?,
+        })
+    }
+}
+
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+    }
+    Ok(nodes)
+}
+
+fn parse_block(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let content;
+    syn::braced!(content in input);
+    parse_nodes(&content)
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Node::Text(input.parse()?));
+        }
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            return Ok(Node::Expr(content.parse()?));
+        }
+
+        if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            return parse_control(input);
+        }
+
+        if input.peek(Ident::peek_any) {
+            return parse_element(input);
+        }
+
+        Err(input.error("expected an element, a string literal, `(expr)` or `@if`/`@for`/`@let`"))
+    }
+}
+
+fn parse_control(input: ParseStream) -> syn::Result<Node> {
+    if input.peek(Token![if]) {
+        input.parse::<Token![if]>()?;
+        let cond = Expr::parse_without_eager_brace(input)?;
+        let then = parse_block(input)?;
+        let otherwise = if input.peek(Token![@]) && input.peek2(Token![else]) {
+            input.parse::<Token![@]>()?;
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(vec![parse_control(input)?])
+            } else {
+                Some(parse_block(input)?)
+            }
+        } else {
+            None
+        };
+        return Ok(Node::If {
+            cond,
+            then,
+            otherwise,
+        });
+    }
+
+    if input.peek(Token![for]) {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![in]>()?;
+        let iter = Expr::parse_without_eager_brace(input)?;
+        let body = parse_block(input)?;
+        return Ok(Node::For { pat, iter, body });
+    }
+
+    if input.peek(Token![let]) {
+        return Ok(Node::Let(input.parse()?));
+    }
+
+    Err(input.error("expected `@if`, `@for` or `@let`"))
+}
+
+fn parse_element(input: ParseStream) -> syn::Result<Node> {
+    let first = Ident::parse_any(input)?;
+    let span = first.span();
+    let mut tag = first.to_string();
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        tag.push('-');
+        tag.push_str(&Ident::parse_any(input)?.to_string());
+    }
+
+    let mut attrs = Vec::new();
+    while !input.peek(syn::token::Brace) && !input.peek(Token![;]) {
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                format!("expected `{{ ... }}` or `;` after <{}>", tag),
+            ));
+        }
+        attrs.push(parse_attr(input)?);
+    }
+
+    let children = if input.peek(Token![;]) {
+        input.parse::<Token![;]>()?;
+        None
+    } else {
+        if is_void_element(&tag) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "<{}> is a void element and cannot have children; end it with `;`",
+                    tag
+                ),
+            ));
+        }
+        Some(parse_block(input)?)
+    };
+
+    Ok(Node::Element {
+        tag,
+        attrs,
+        children,
+    })
+}
+
+fn parse_attr(input: ParseStream) -> syn::Result<AttrSpec> {
+    let key = parse_attr_key(input)?;
+
+    if input.peek(Token![if]) {
+        input.parse::<Token![if]>()?;
+        let cond = Expr::parse_without_eager_brace(input)?;
+        return Ok(AttrSpec {
+            key: classify_key(&key),
+            value: AttrValue::Conditional(cond),
+        });
+    }
+
+    if !input.peek(Token![=]) {
+        return Ok(bare_attr(key));
+    }
+    input.parse::<Token![=]>()?;
+
+    let value = if input.peek(LitStr) {
+        classify_value(&input.parse::<LitStr>()?.value())
+    } else if input.peek(syn::LitBool) {
+        AttrValue::Bool(input.parse::<syn::LitBool>()?.value)
+    } else if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let entries =
+            syn::punctuated::Punctuated::<ListEntry, Token![,]>::parse_terminated(&content)?;
+        AttrValue::List(entries.into_iter().collect())
+    } else if input.peek(syn::token::Paren) {
+        AttrValue::Expr(parse_paren_expr(input)?)
+    } else {
+        AttrValue::Path(input.parse()?)
+    };
+
+    Ok(AttrSpec {
+        key: classify_key(&key),
+        value,
+    })
+}
+
+fn parse_paren_expr(input: ParseStream) -> syn::Result<Expr> {
+    let content;
+    syn::parenthesized!(content in input);
+    content.parse()
+}
+
+pub fn generate_markup(markup: &Markup, bluth_crate: &TokenStream) -> TokenStream {
+    let body = generate_nodes(&markup.nodes, bluth_crate);
+    let size_hint = literal_output_bytes(body.clone());
+
+    quote! {
+        #bluth_crate::render::Markup::new(#size_hint, move |f: &mut dyn ::core::fmt::Write| -> ::core::fmt::Result {
+            let _bluth_depth = #bluth_crate::render::DepthGuard::enter()?;
+            #body
+            Ok(())
+        })
+    }
+}
+
+fn generate_nodes(nodes: &[Node], bluth_crate: &TokenStream) -> TokenStream {
+    let code = nodes.iter().map(|node| generate_node(node, bluth_crate));
+    quote! { #(#code)* }
+}
+
+fn generate_node(node: &Node, bluth_crate: &TokenStream) -> TokenStream {
+    match node {
+        Node::Text(text) => {
+            let escaped = escape_text_str(&text.value());
+            quote! { f.write_str(#escaped)?; }
+        }
+        Node::Expr(expr) => quote! {
+            #bluth_crate::Render::render(&(#expr), f)?;
+        },
+        Node::Element {
+            tag,
+            attrs,
+            children,
+        } => {
+            let attr_code = emit_attrs(attrs, false, &HashMap::new(), bluth_crate);
+            let code = match children {
+                None if is_void_element(tag) => {
+                    let close = void_close(tag);
+                    quote! {
+                        write!(f, "<{}", #tag)?;
+                        #attr_code
+                        f.write_str(#close)?;
+                    }
+                }
+                None => {
+                    let close = format!("></{}>", tag);
+                    quote! {
+                        write!(f, "<{}", #tag)?;
+                        #attr_code
+                        f.write_str(#close)?;
+                    }
+                }
+                Some(children) => {
+                    let content = generate_nodes(children, bluth_crate);
+                    let close = format!("</{}>", tag);
+                    quote! {
+                        write!(f, "<{}", #tag)?;
+                        #attr_code
+                        f.write_str(">")?;
+                        #content
+                        f.write_str(#close)?;
+                    }
+                }
+            };
+            guard_script(tag, code, bluth_crate)
+        }
+        Node::If {
+            cond,
+            then,
+            otherwise,
+        } => {
+            let then = generate_nodes(then, bluth_crate);
+            let otherwise = otherwise.as_ref().map(|nodes| {
+                let nodes = generate_nodes(nodes, bluth_crate);
+                quote! { else { #nodes } }
+            });
+            quote! {
+                if #cond {
+                    #then
+                } #otherwise
+            }
+        }
+        Node::For { pat, iter, body } => {
+            let body = generate_nodes(body, bluth_crate);
+            quote! {
+                for #pat in #iter {
+                    #body
+                }
+            }
+        }
+        Node::Let(stmt) => quote! { #stmt },
+    }
+}
+
+fn escape_text_str(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
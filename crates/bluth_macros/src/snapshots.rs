@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use syn::DeriveInput;
+
+use crate::features::with_defaults_only;
+use crate::{derive_element_impl, derive_signal_impl, pretty};
+
+fn assert_snapshot(
+    name: &str,
+    derive: fn(&DeriveInput) -> syn::Result<proc_macro2::TokenStream>,
+    input: proc_macro2::TokenStream,
+) {
+    let input: DeriveInput = syn::parse2(input).expect("valid derive input");
+    let expanded = with_defaults_only(|| derive(&input)).expect("derive succeeds");
+    let actual = pretty(&expanded);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.expanded.rs", name));
+
+    if std::env::var_os("BLUTH_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; rerun with BLUTH_UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "expansion of `{}` changed; rerun with BLUTH_UPDATE_SNAPSHOTS=1 and review the diff of {}\n\n{}",
+        name,
+        path.display(),
+        actual
+    );
+}
+
+#[test]
+fn element_struct() {
+    assert_snapshot(
+        "element_struct",
+        derive_element_impl,
+        quote::quote! {
+            #[element("div")]
+            #[attr(class = "card", id = "{id}")]
+            struct Card {
+                id: u32,
+
+                #[attr]
+                title: String,
+
+                #[element("h2")]
+                heading: String,
+
+                #[element("ul")]
+                items: Vec<Item>,
+
+                #[element(raw)]
+                body: Option<String>,
+            }
+        },
+    );
+}
+
+#[test]
+fn element_enum() {
+    assert_snapshot(
+        "element_enum",
+        derive_element_impl,
+        quote::quote! {
+            #[element("li")]
+            enum Entry {
+                #[element("b")]
+                Bold(String),
+                Plain(String),
+                Empty,
+            }
+        },
+    );
+}
+
+#[test]
+fn signal_enum() {
+    assert_snapshot(
+        "signal_enum",
+        derive_signal_impl,
+        quote::quote! {
+            pub enum PageSignals {
+                Search(String),
+                #[signal(name = "pageNum")]
+                Page(u32),
+            }
+        },
+    );
+}
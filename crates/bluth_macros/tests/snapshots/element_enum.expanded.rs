@@ -0,0 +1,33 @@
+impl ::bluth::Render for Entry {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let _bluth_depth = ::bluth::render::DepthGuard::enter()?;
+        match self {
+            Entry::Bold(field0) => {
+                write!(f, "{}", "<li>")?;
+                write!(f, "{}", "<b>")?;
+                write!(f, "{}", ::bluth::html::escape_html(field0))?;
+                write!(f, "{}", "</b>")?;
+                write!(f, "{}", "</li>")?;
+            }
+            Entry::Plain(field0) => {
+                write!(f, "{}", "<li>")?;
+                write!(f, "{}", ::bluth::html::escape_html(field0))?;
+                write!(f, "{}", "</li>")?;
+            }
+            Entry::Empty => {
+                write!(f, "{}", "<li>")?;
+                write!(f, "{}", "</li>")?;
+            }
+        }
+        Ok(())
+    }
+    fn size_hint(&self) -> usize {
+        9usize
+    }
+}
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        ::bluth::Render::render(self, f)
+    }
+}
+impl ::bluth::html::RootAttrs for Entry {}
@@ -0,0 +1,36 @@
+impl ::bluth::Render for Card {
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let _bluth_depth = ::bluth::render::DepthGuard::enter()?;
+        write!(f, "<{}", "div")?;
+        write!(f, " {}=\"{}\"", "class", "card")?;
+        write!(
+            f, " {}=\"{}\"", "id", ::bluth::html::escape_attr(format!("{}", & self.id))
+        )?;
+        write!(f, " {}=\"{}\"", "title", ::bluth::html::escape_attr(& self.title))?;
+        write!(f, ">")?;
+        write!(f, "<{}", "h2")?;
+        write!(f, ">")?;
+        write!(f, "{}", ::bluth::html::escape_html(& self.heading))?;
+        write!(f, "</{}>", "h2")?;
+        write!(f, "<{}", "ul")?;
+        write!(f, ">")?;
+        for item in &self.items {
+            write!(f, "{}", item)?;
+        }
+        write!(f, "</{}>", "ul")?;
+        if let Some(ref v) = self.body {
+            write!(f, "{}", v)?;
+        }
+        write!(f, "{}", "</div>")?;
+        Ok(())
+    }
+    fn size_hint(&self) -> usize {
+        57usize + self.heading.len() + self.items.len() * 16
+    }
+}
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        ::bluth::Render::render(self, f)
+    }
+}
+impl ::bluth::html::RootAttrs for Card {}
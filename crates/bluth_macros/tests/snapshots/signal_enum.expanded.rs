@@ -0,0 +1,113 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Search;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Page;
+impl ::bluth::SignalSelector for Search {
+    type Value = String;
+    type Enum = PageSignals;
+    const NAME: &'static str = "search";
+    const DATA_BIND: &'static str = "data-bind=\"search\"";
+    const JSON_KEY: &'static str = "\"search\"";
+    const JS_REF: &'static str = "$search";
+    fn extract(value: &PageSignals) -> ::core::option::Option<&Self::Value> {
+        match value {
+            PageSignals::Search(v) => ::core::option::Option::Some(v),
+            _ => ::core::option::Option::None,
+        }
+    }
+    fn into_inner(value: PageSignals) -> ::core::option::Option<Self::Value> {
+        match value {
+            PageSignals::Search(v) => ::core::option::Option::Some(v),
+            _ => ::core::option::Option::None,
+        }
+    }
+    fn wrap(value: Self::Value) -> PageSignals {
+        PageSignals::Search(value)
+    }
+}
+impl ::core::convert::AsRef<str> for Search {
+    fn as_ref(&self) -> &str {
+        ::bluth::signal::track_name::<Search>()
+    }
+}
+impl ::bluth::SignalSelector for Page {
+    type Value = u32;
+    type Enum = PageSignals;
+    const NAME: &'static str = "pageNum";
+    const DATA_BIND: &'static str = "data-bind=\"pageNum\"";
+    const JSON_KEY: &'static str = "\"pageNum\"";
+    const JS_REF: &'static str = "$pageNum";
+    fn extract(value: &PageSignals) -> ::core::option::Option<&Self::Value> {
+        match value {
+            PageSignals::Page(v) => ::core::option::Option::Some(v),
+            _ => ::core::option::Option::None,
+        }
+    }
+    fn into_inner(value: PageSignals) -> ::core::option::Option<Self::Value> {
+        match value {
+            PageSignals::Page(v) => ::core::option::Option::Some(v),
+            _ => ::core::option::Option::None,
+        }
+    }
+    fn wrap(value: Self::Value) -> PageSignals {
+        PageSignals::Page(value)
+    }
+}
+impl ::core::convert::AsRef<str> for Page {
+    fn as_ref(&self) -> &str {
+        ::bluth::signal::track_name::<Page>()
+    }
+}
+impl ::bluth::SignalEnum for PageSignals {
+    fn signal_name(&self) -> &'static str {
+        match self {
+            Self::Search(_) => "search",
+            Self::Page(_) => "pageNum",
+        }
+    }
+    fn to_json_value(&self) -> ::serde_json::Value {
+        match self {
+            Self::Search(v) => {
+                ::serde_json::to_value(v).unwrap_or(::serde_json::Value::Null)
+            }
+            Self::Page(v) => {
+                ::serde_json::to_value(v).unwrap_or(::serde_json::Value::Null)
+            }
+        }
+    }
+    fn selectors() -> &'static [(&'static str, &'static str)] {
+        &[("Search", "search"), ("Page", "pageNum")]
+    }
+}
+impl ::serde::Serialize for PageSignals {
+    fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        use ::serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(::core::option::Option::Some(1))?;
+        match self {
+            Self::Search(v) => map.serialize_entry("search", v)?,
+            Self::Page(v) => map.serialize_entry("pageNum", v)?,
+        }
+        map.end()
+    }
+}
+#[automatically_derived]
+impl ::core::clone::Clone for PageSignals {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Search(v) => Self::Search(::core::clone::Clone::clone(v)),
+            Self::Page(v) => Self::Page(::core::clone::Clone::clone(v)),
+        }
+    }
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for PageSignals {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            Self::Search(v) => f.debug_tuple("Search").field(v).finish(),
+            Self::Page(v) => f.debug_tuple("Page").field(v).finish(),
+        }
+    }
+}